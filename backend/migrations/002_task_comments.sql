@@ -0,0 +1,40 @@
+-- ================================================
+-- Task comment threads with @mentions
+-- ================================================
+
+CREATE TABLE IF NOT EXISTS task_comments (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    task_id UUID NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+
+    -- Author
+    author_id UUID REFERENCES users(id),
+    author_name VARCHAR(100) NOT NULL,
+    author_email VARCHAR(255) NOT NULL,
+
+    -- Content
+    body TEXT NOT NULL,
+    mentioned_user_ids UUID[] NOT NULL DEFAULT '{}',
+
+    -- Dates
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    edited_at TIMESTAMP WITH TIME ZONE,
+    deleted_at TIMESTAMP WITH TIME ZONE
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_comments_task_id ON task_comments(task_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_task_comments_author_id ON task_comments(author_id);
+
+-- Previous versions of a comment, written on every edit
+CREATE TABLE IF NOT EXISTS task_comment_edits (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    comment_id UUID NOT NULL REFERENCES task_comments(id) ON DELETE CASCADE,
+    previous_body TEXT NOT NULL,
+    edited_by UUID REFERENCES users(id),
+    edited_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_comment_edits_comment_id ON task_comment_edits(comment_id, edited_at);
+
+CREATE TRIGGER update_task_comments_updated_at BEFORE UPDATE ON task_comments
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,29 @@
+-- ================================================
+-- Subtasks and checklists
+-- ================================================
+
+-- Parent/child relationship between tasks
+ALTER TABLE tasks ADD COLUMN IF NOT EXISTS parent_id UUID REFERENCES tasks(id) ON DELETE CASCADE;
+
+CREATE INDEX IF NOT EXISTS idx_tasks_parent_id ON tasks(parent_id);
+
+-- Checklist items within a task
+CREATE TABLE IF NOT EXISTS task_checklist_items (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    task_id UUID NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+
+    title TEXT NOT NULL,
+    position INTEGER NOT NULL DEFAULT 0,
+
+    is_done BOOLEAN NOT NULL DEFAULT false,
+    completed_at TIMESTAMP WITH TIME ZONE,
+    completed_by UUID REFERENCES users(id),
+
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_checklist_items_task_id ON task_checklist_items(task_id, position);
+
+CREATE TRIGGER update_task_checklist_items_updated_at BEFORE UPDATE ON task_checklist_items
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,45 @@
+-- ================================================
+-- Recurring task series (RRULE schedules)
+-- ================================================
+
+CREATE TABLE IF NOT EXISTS recurring_task_series (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+
+    -- Template for each occurrence
+    title TEXT NOT NULL,
+    description TEXT,
+    category VARCHAR(100) NOT NULL,
+    assigned_to VARCHAR(100) NOT NULL,
+    assigned_to_email VARCHAR(255) NOT NULL,
+    created_by VARCHAR(100) NOT NULL,
+    created_by_email VARCHAR(255) NOT NULL,
+    priority VARCHAR(50) NOT NULL DEFAULT 'רגילה',
+    notes TEXT,
+
+    -- Schedule
+    rrule TEXT NOT NULL,
+    start_date DATE NOT NULL,
+    lead_days INTEGER NOT NULL DEFAULT 0,
+    next_occurrence DATE,
+    occurrences_created INTEGER NOT NULL DEFAULT 0,
+    is_active BOOLEAN NOT NULL DEFAULT true,
+
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+
+    CONSTRAINT chk_series_priority CHECK (priority IN ('נמוכה', 'רגילה', 'גבוהה', 'דחופה')),
+    CONSTRAINT chk_series_lead_days CHECK (lead_days >= 0)
+);
+
+CREATE INDEX IF NOT EXISTS idx_recurring_task_series_next ON recurring_task_series(next_occurrence)
+    WHERE is_active;
+
+CREATE TRIGGER update_recurring_task_series_updated_at BEFORE UPDATE ON recurring_task_series
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- Materialized occurrences point back at their series
+ALTER TABLE tasks ADD COLUMN IF NOT EXISTS series_id UUID REFERENCES recurring_task_series(id) ON DELETE SET NULL;
+ALTER TABLE tasks ADD COLUMN IF NOT EXISTS occurrence_date DATE;
+
+-- One task per series occurrence, so materializing is idempotent
+CREATE UNIQUE INDEX IF NOT EXISTS idx_tasks_series_occurrence ON tasks(series_id, occurrence_date);
@@ -0,0 +1,33 @@
+-- ================================================
+-- Task templates for common legal workflows
+-- ================================================
+
+CREATE TABLE IF NOT EXISTS task_templates (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    name VARCHAR(200) UNIQUE NOT NULL,
+    description TEXT,
+
+    -- Defaults for the task created from the template; text fields may
+    -- contain {placeholders} filled in at instantiation time
+    title TEXT NOT NULL,
+    category VARCHAR(100) NOT NULL,
+    priority VARCHAR(50) NOT NULL DEFAULT 'רגילה',
+    task_description TEXT,
+    notes TEXT,
+    due_offset_days INTEGER,
+
+    -- Checklist item titles, and subtask definitions
+    checklist JSONB NOT NULL DEFAULT '[]',
+    subtasks JSONB NOT NULL DEFAULT '[]',
+    sequential_subtasks BOOLEAN NOT NULL DEFAULT false,
+
+    is_active BOOLEAN NOT NULL DEFAULT true,
+    created_by UUID REFERENCES users(id),
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+
+    CONSTRAINT chk_template_priority CHECK (priority IN ('נמוכה', 'רגילה', 'גבוהה', 'דחופה'))
+);
+
+CREATE TRIGGER update_task_templates_updated_at BEFORE UPDATE ON task_templates
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
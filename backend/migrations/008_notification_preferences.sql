@@ -0,0 +1,86 @@
+-- ================================================
+-- Per-user notification preferences, in-app inbox and digest batching
+-- ================================================
+
+-- Delivery settings that apply to all of a user's notifications
+CREATE TABLE IF NOT EXISTS notification_settings (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    timezone VARCHAR(64) NOT NULL DEFAULT 'Asia/Jerusalem',
+
+    -- Emails that fall inside quiet hours are held until they end; a window
+    -- may wrap past midnight (e.g. 22:00-07:00)
+    quiet_hours_start TIME,
+    quiet_hours_end TIME,
+
+    -- Local time batched notifications are sent at
+    digest_time TIME NOT NULL DEFAULT '08:00',
+    last_digest_sent_at TIMESTAMP WITH TIME ZONE,
+
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+
+    CONSTRAINT chk_quiet_hours_pair CHECK ((quiet_hours_start IS NULL) = (quiet_hours_end IS NULL))
+);
+
+-- Channels per event type; a missing row means the defaults (all channels on)
+CREATE TABLE IF NOT EXISTS notification_preferences (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    event_type VARCHAR(50) NOT NULL,
+
+    email_enabled BOOLEAN NOT NULL DEFAULT true,
+    push_enabled BOOLEAN NOT NULL DEFAULT true,
+    in_app_enabled BOOLEAN NOT NULL DEFAULT true,
+
+    -- Only notify about tasks of at least this priority
+    min_priority VARCHAR(50),
+
+    -- Batch emails into the daily digest instead of sending them right away
+    digest BOOLEAN NOT NULL DEFAULT false,
+
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+
+    CONSTRAINT uq_notification_preference UNIQUE (user_id, event_type),
+    CONSTRAINT chk_notification_event_type CHECK (event_type IN (
+        'task_created', 'task_assigned', 'task_updated', 'status_changed', 'comment', 'due_soon'
+    )),
+    CONSTRAINT chk_notification_min_priority CHECK (
+        min_priority IS NULL OR min_priority IN ('נמוכה', 'רגילה', 'גבוהה', 'דחופה')
+    )
+);
+
+-- In-app notification inbox
+CREATE TABLE IF NOT EXISTS notifications (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    event_type VARCHAR(50) NOT NULL,
+    title TEXT NOT NULL,
+    task_id UUID REFERENCES tasks(id) ON DELETE CASCADE,
+    read_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_notifications_user ON notifications(user_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_notifications_unread ON notifications(user_id) WHERE read_at IS NULL;
+
+-- Email notifications waiting for the user's next digest
+CREATE TABLE IF NOT EXISTS notification_digest_items (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    event_type VARCHAR(50) NOT NULL,
+    summary TEXT NOT NULL,
+    task_id UUID REFERENCES tasks(id) ON DELETE CASCADE,
+    sent_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_digest_items_pending ON notification_digest_items(user_id, created_at)
+    WHERE sent_at IS NULL;
+
+CREATE TRIGGER update_notification_settings_updated_at BEFORE UPDATE ON notification_settings
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TRIGGER update_notification_preferences_updated_at BEFORE UPDATE ON notification_preferences
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
@@ -0,0 +1,15 @@
+-- ================================================
+-- Opt-in daily/weekly task digests
+-- ================================================
+
+ALTER TABLE notification_settings
+    ADD COLUMN IF NOT EXISTS task_digest VARCHAR(10) NOT NULL DEFAULT 'off',
+    -- Day the weekly digest goes out, 0 = Sunday
+    ADD COLUMN IF NOT EXISTS digest_weekday SMALLINT NOT NULL DEFAULT 0;
+
+ALTER TABLE notification_settings
+    ADD CONSTRAINT chk_task_digest CHECK (task_digest IN ('off', 'daily', 'weekly')),
+    ADD CONSTRAINT chk_digest_weekday CHECK (digest_weekday BETWEEN 0 AND 6);
+
+CREATE INDEX IF NOT EXISTS idx_notification_settings_task_digest ON notification_settings(task_digest)
+    WHERE task_digest <> 'off';
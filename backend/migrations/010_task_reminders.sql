@@ -0,0 +1,20 @@
+-- ================================================
+-- Due-date reminders and overdue escalation
+-- ================================================
+
+-- One row per reminder sent. The unique key makes every reminder
+-- at-most-once across restarts and instances; keying on the due date lets a
+-- rescheduled task get a fresh set of reminders.
+CREATE TABLE IF NOT EXISTS task_reminders (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    task_id UUID NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    due_date DATE NOT NULL,
+    -- e.g. 'before_7d', 'before_0d', 'overdue_creator', 'overdue_admin'
+    reminder_key VARCHAR(50) NOT NULL,
+    recipient VARCHAR(255) NOT NULL,
+    sent_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+
+    CONSTRAINT uq_task_reminder UNIQUE (task_id, due_date, reminder_key)
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_reminders_task_id ON task_reminders(task_id);
@@ -0,0 +1,32 @@
+-- ================================================
+-- Real-time task event stream
+-- ================================================
+
+-- Append-only log of task and comment changes. The id doubles as the SSE
+-- event id, so clients can resume with Last-Event-ID after a disconnect.
+CREATE TABLE IF NOT EXISTS task_events (
+    id BIGSERIAL PRIMARY KEY,
+    -- e.g. 'task_created', 'task_updated', 'task_deleted', 'comment_created'
+    event_type VARCHAR(50) NOT NULL,
+    -- No foreign key: events about deleted tasks must survive the task
+    task_id UUID NOT NULL,
+    -- Lower-cased emails of the non-admin users allowed to see the event
+    visible_to TEXT[] NOT NULL DEFAULT '{}',
+    payload JSONB NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_events_created_at ON task_events(created_at);
+
+-- Wake every API instance listening on the channel. NOTIFY is delivered on
+-- commit, so listeners never see events from rolled-back transactions.
+CREATE OR REPLACE FUNCTION notify_task_event()
+RETURNS TRIGGER AS $$
+BEGIN
+    PERFORM pg_notify('task_events', NEW.id::text);
+    RETURN NEW;
+END;
+$$ language 'plpgsql';
+
+CREATE TRIGGER notify_task_events AFTER INSERT ON task_events
+    FOR EACH ROW EXECUTE FUNCTION notify_task_event();
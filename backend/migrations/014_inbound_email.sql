@@ -0,0 +1,28 @@
+-- ================================================
+-- Inbound email: replies as comments, intake as tasks
+-- ================================================
+
+-- Files received by email are stored in the database; file_url then points
+-- at the API's download endpoint
+ALTER TABLE attachments ADD COLUMN IF NOT EXISTS content BYTEA;
+ALTER TABLE attachments ADD COLUMN IF NOT EXISTS uploaded_by UUID REFERENCES users(id) ON DELETE SET NULL;
+
+-- Every message posted to the inbound endpoint and what became of it. The
+-- unique Message-ID makes redelivery by the MTA harmless.
+CREATE TABLE IF NOT EXISTS inbound_emails (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    message_id VARCHAR(998) NOT NULL UNIQUE,
+    sender VARCHAR(255),
+    subject TEXT,
+    -- 'comment', 'task' or 'rejected'
+    outcome VARCHAR(20) NOT NULL,
+    reason TEXT,
+    task_id UUID REFERENCES tasks(id) ON DELETE SET NULL,
+    comment_id UUID REFERENCES task_comments(id) ON DELETE SET NULL,
+    attachments_saved INTEGER NOT NULL DEFAULT 0,
+    received_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+
+    CONSTRAINT chk_inbound_email_outcome CHECK (outcome IN ('comment', 'task', 'rejected'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_inbound_emails_received_at ON inbound_emails(received_at DESC);
@@ -0,0 +1,67 @@
+-- ================================================
+-- Clients and legal matters, linked to tasks
+-- ================================================
+
+CREATE TABLE IF NOT EXISTS clients (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    name VARCHAR(300) NOT NULL,
+    client_type VARCHAR(20) NOT NULL DEFAULT 'individual',
+
+    -- ת"ז for individuals, ח"פ for companies
+    id_number VARCHAR(50),
+
+    email VARCHAR(255),
+    phone VARCHAR(50),
+    address TEXT,
+    notes TEXT,
+
+    is_active BOOLEAN NOT NULL DEFAULT true,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+
+    CONSTRAINT chk_client_type CHECK (client_type IN ('individual', 'organization'))
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_clients_id_number ON clients(id_number) WHERE id_number IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_clients_name ON clients(name);
+
+CREATE TRIGGER update_clients_updated_at BEFORE UPDATE ON clients
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TABLE IF NOT EXISTS matters (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    matter_number VARCHAR(50) UNIQUE NOT NULL,
+    title TEXT NOT NULL,
+
+    -- A client with matters cannot be deleted
+    client_id UUID NOT NULL REFERENCES clients(id) ON DELETE RESTRICT,
+
+    opposing_party TEXT,
+    court VARCHAR(200),
+    case_number VARCHAR(100),
+    responsible_lawyer_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    description TEXT,
+
+    status VARCHAR(20) NOT NULL DEFAULT 'open',
+    opened_at DATE NOT NULL DEFAULT CURRENT_DATE,
+    closed_at TIMESTAMP WITH TIME ZONE,
+
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+
+    CONSTRAINT chk_matter_status CHECK (status IN ('open', 'closed'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_matters_client ON matters(client_id);
+CREATE INDEX IF NOT EXISTS idx_matters_status ON matters(status);
+CREATE INDEX IF NOT EXISTS idx_matters_lawyer ON matters(responsible_lawyer_id);
+
+CREATE TRIGGER update_matters_updated_at BEFORE UPDATE ON matters
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- Tasks outlive the matter record they were filed under
+ALTER TABLE tasks ADD COLUMN IF NOT EXISTS matter_id UUID REFERENCES matters(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_tasks_matter ON tasks(matter_id) WHERE matter_id IS NOT NULL;
@@ -0,0 +1,23 @@
+-- ================================================
+-- Court deadlines computed for tasks
+-- ================================================
+
+CREATE TABLE IF NOT EXISTS task_deadlines (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    task_id UUID NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+
+    -- What the deadline was computed from, so it can be checked later
+    trigger_date DATE NOT NULL,
+    rule VARCHAR(200) NOT NULL,
+    due_date DATE NOT NULL,
+    previous_due_date DATE,
+
+    -- Weekends, holidays and recess days passed over, with the reason
+    skipped_days JSONB NOT NULL DEFAULT '[]',
+
+    note TEXT,
+    computed_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_deadlines_task ON task_deadlines(task_id, created_at DESC);
@@ -0,0 +1,67 @@
+-- ================================================
+-- Court hearings and their preparation tasks
+-- ================================================
+
+CREATE TABLE IF NOT EXISTS hearings (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    matter_id UUID NOT NULL REFERENCES matters(id) ON DELETE CASCADE,
+
+    -- E.g. קדם משפט, הוכחות, סיכומים
+    hearing_type VARCHAR(100) NOT NULL,
+    scheduled_at TIMESTAMP WITH TIME ZONE NOT NULL,
+
+    -- Defaults to the matter's court
+    court VARCHAR(200),
+    judge VARCHAR(200),
+    courtroom VARCHAR(50),
+    notes TEXT,
+
+    status VARCHAR(20) NOT NULL DEFAULT 'scheduled',
+    cancelled_at TIMESTAMP WITH TIME ZONE,
+
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+
+    CONSTRAINT chk_hearing_status CHECK (status IN ('scheduled', 'held', 'cancelled'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_hearings_matter ON hearings(matter_id);
+CREATE INDEX IF NOT EXISTS idx_hearings_scheduled ON hearings(scheduled_at) WHERE status = 'scheduled';
+
+CREATE TRIGGER update_hearings_updated_at BEFORE UPDATE ON hearings
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- The standard preparation for a hearing; a step without a hearing type
+-- applies to every hearing
+CREATE TABLE IF NOT EXISTS hearing_preparation_steps (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    hearing_type VARCHAR(100),
+    title TEXT NOT NULL,
+    days_before INTEGER NOT NULL,
+    category VARCHAR(100) NOT NULL DEFAULT 'משפטית',
+    priority VARCHAR(50) NOT NULL DEFAULT 'רגילה',
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+
+    CONSTRAINT chk_preparation_days_before CHECK (days_before >= 0),
+    CONSTRAINT chk_preparation_priority CHECK (priority IN ('נמוכה', 'רגילה', 'גבוהה', 'דחופה'))
+);
+
+INSERT INTO hearing_preparation_steps (hearing_type, title, days_before, priority) VALUES
+    (NULL, 'תיאום הדיון והכנת הלקוח', 7, 'רגילה'),
+    (NULL, 'הכנת תיק לדיון', 2, 'גבוהה'),
+    ('הוכחות', 'זימון עדים', 21, 'גבוהה'),
+    ('הוכחות', 'הכנת רשימת עדים', 14, 'גבוהה'),
+    ('הוכחות', 'הכנת עדים לחקירה', 7, 'גבוהה'),
+    ('סיכומים', 'הכנת סיכומים', 21, 'גבוהה');
+
+-- Tasks generated for a hearing, with the offset they were created at so
+-- that a rescheduled hearing can move them
+CREATE TABLE IF NOT EXISTS hearing_tasks (
+    hearing_id UUID NOT NULL REFERENCES hearings(id) ON DELETE CASCADE,
+    task_id UUID NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    days_before INTEGER NOT NULL,
+    PRIMARY KEY (hearing_id, task_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_hearing_tasks_task ON hearing_tasks(task_id);
@@ -0,0 +1,14 @@
+-- ================================================
+-- iCalendar feeds and invites
+-- ================================================
+
+-- One subscription URL per user; only the token's hash is stored
+CREATE TABLE IF NOT EXISTS calendar_feeds (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    token_hash VARCHAR(64) UNIQUE NOT NULL,
+    last_accessed_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+-- .ics attachment sent with a notification
+ALTER TABLE email_outbox ADD COLUMN IF NOT EXISTS calendar_invite TEXT;
@@ -0,0 +1,51 @@
+-- ================================================
+-- Time tracking against tasks and matters
+-- Money amounts are in agorot
+-- ================================================
+
+-- Default rate for the user's time entries
+ALTER TABLE users ADD COLUMN IF NOT EXISTS hourly_rate_agorot BIGINT;
+
+CREATE TABLE IF NOT EXISTS time_entries (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE RESTRICT,
+    task_id UUID REFERENCES tasks(id) ON DELETE SET NULL,
+    matter_id UUID REFERENCES matters(id) ON DELETE SET NULL,
+
+    -- Timer entries have a start (and, once stopped, an end); manual
+    -- entries may only have a duration. A running timer has no duration.
+    entry_date DATE NOT NULL,
+    started_at TIMESTAMP WITH TIME ZONE,
+    ended_at TIMESTAMP WITH TIME ZONE,
+    duration_minutes INTEGER,
+
+    billable BOOLEAN NOT NULL DEFAULT true,
+    hourly_rate_agorot BIGINT,
+    amount_agorot BIGINT GENERATED ALWAYS AS (
+        CASE WHEN billable AND duration_minutes IS NOT NULL
+            THEN ROUND(duration_minutes * COALESCE(hourly_rate_agorot, 0) / 60.0)::BIGINT
+            ELSE 0
+        END
+    ) STORED,
+    narrative TEXT,
+
+    -- Set when the entry is invoiced, after which it can no longer change
+    invoice_id UUID,
+    invoiced_at TIMESTAMP WITH TIME ZONE,
+
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+
+    CONSTRAINT chk_time_entry_duration CHECK (duration_minutes IS NULL OR duration_minutes > 0),
+    CONSTRAINT chk_time_entry_running CHECK (duration_minutes IS NOT NULL OR started_at IS NOT NULL),
+    CONSTRAINT chk_time_entry_rate CHECK (hourly_rate_agorot IS NULL OR hourly_rate_agorot >= 0)
+);
+
+-- At most one running timer per user
+CREATE UNIQUE INDEX IF NOT EXISTS idx_time_entries_running ON time_entries(user_id) WHERE duration_minutes IS NULL;
+CREATE INDEX IF NOT EXISTS idx_time_entries_user_date ON time_entries(user_id, entry_date);
+CREATE INDEX IF NOT EXISTS idx_time_entries_task ON time_entries(task_id);
+CREATE INDEX IF NOT EXISTS idx_time_entries_matter ON time_entries(matter_id, entry_date);
+
+CREATE TRIGGER update_time_entries_updated_at BEFORE UPDATE ON time_entries
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,74 @@
+-- ================================================
+-- Invoices drafted from billable time entries
+-- Money amounts are in agorot; VAT rates in basis points (1800 = 18%)
+-- ================================================
+
+-- Last number issued in each year. Issuing takes the row lock, so numbers
+-- are handed out in order and a rolled-back issue does not leave a gap.
+CREATE TABLE IF NOT EXISTS invoice_number_sequences (
+    invoice_year INTEGER PRIMARY KEY,
+    last_number INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS invoices (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+
+    -- Set when the draft is issued
+    invoice_year INTEGER,
+    sequence_number INTEGER,
+    invoice_number VARCHAR(20) GENERATED ALWAYS AS (
+        invoice_year::TEXT || '-' || LPAD(sequence_number::TEXT, 4, '0')
+    ) STORED,
+
+    client_id UUID NOT NULL REFERENCES clients(id) ON DELETE RESTRICT,
+    -- NULL for an invoice covering all of the client's matters
+    matter_id UUID REFERENCES matters(id) ON DELETE RESTRICT,
+    period_from DATE NOT NULL,
+    period_to DATE NOT NULL,
+
+    status VARCHAR(20) NOT NULL DEFAULT 'draft' CHECK (status IN ('draft', 'issued')),
+    subtotal_agorot BIGINT NOT NULL DEFAULT 0,
+    vat_rate_basis_points INTEGER NOT NULL,
+    vat_agorot BIGINT NOT NULL DEFAULT 0,
+    total_agorot BIGINT NOT NULL DEFAULT 0,
+    notes TEXT,
+
+    issued_at TIMESTAMP WITH TIME ZONE,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+
+    CONSTRAINT chk_invoice_period CHECK (period_from <= period_to),
+    CONSTRAINT chk_invoice_vat_rate CHECK (vat_rate_basis_points BETWEEN 0 AND 10000),
+    CONSTRAINT chk_invoice_number CHECK (
+        (status = 'draft' AND sequence_number IS NULL)
+        OR (status = 'issued' AND sequence_number IS NOT NULL AND invoice_year IS NOT NULL)
+    ),
+    CONSTRAINT uq_invoice_number UNIQUE (invoice_year, sequence_number)
+);
+
+CREATE INDEX IF NOT EXISTS idx_invoices_client ON invoices(client_id);
+CREATE INDEX IF NOT EXISTS idx_invoices_matter ON invoices(matter_id);
+CREATE INDEX IF NOT EXISTS idx_invoices_status ON invoices(status);
+
+CREATE TRIGGER update_invoices_updated_at BEFORE UPDATE ON invoices
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- One line per matter, task and rate
+CREATE TABLE IF NOT EXISTS invoice_lines (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    invoice_id UUID NOT NULL REFERENCES invoices(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    description TEXT NOT NULL,
+    entries INTEGER NOT NULL,
+    minutes INTEGER NOT NULL,
+    hourly_rate_agorot BIGINT NOT NULL,
+    amount_agorot BIGINT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_invoice_lines_invoice ON invoice_lines(invoice_id, position);
+
+-- Entries on an invoice are locked. Deleting a draft releases them.
+ALTER TABLE time_entries
+    ADD CONSTRAINT fk_time_entries_invoice
+    FOREIGN KEY (invoice_id) REFERENCES invoices(id) ON DELETE SET NULL;
@@ -0,0 +1,19 @@
+-- ================================================
+-- Other parties to a matter, for conflict-of-interest checks
+-- ================================================
+
+CREATE TABLE IF NOT EXISTS matter_parties (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    matter_id UUID NOT NULL REFERENCES matters(id) ON DELETE CASCADE,
+    name VARCHAR(300) NOT NULL,
+    -- Israeli ID or company number
+    id_number VARCHAR(50),
+    role VARCHAR(30) NOT NULL DEFAULT 'related'
+        CHECK (role IN ('opposing_party', 'opposing_counsel', 'related')),
+    notes TEXT,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_matter_parties_matter ON matter_parties(matter_id);
+CREATE INDEX IF NOT EXISTS idx_matter_parties_id_number ON matter_parties(id_number) WHERE id_number IS NOT NULL;
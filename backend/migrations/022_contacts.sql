@@ -0,0 +1,54 @@
+-- ================================================
+-- Contacts directory: witnesses, experts, courts, counterparties
+-- ================================================
+
+CREATE TABLE IF NOT EXISTS contacts (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    contact_type VARCHAR(20) NOT NULL DEFAULT 'person'
+        CHECK (contact_type IN ('person', 'organization')),
+    name VARCHAR(300) NOT NULL,
+    -- A person's employer, e.g. the firm of opposing counsel
+    organization VARCHAR(300),
+    job_title VARCHAR(200),
+    -- Israeli ID or company number
+    id_number VARCHAR(50),
+    -- Free tags, e.g. 'expert', 'court', 'witness'
+    roles TEXT[] NOT NULL DEFAULT '{}',
+    -- [{"label": "נייד", "value": "050-1234567"}, ...]
+    phones JSONB NOT NULL DEFAULT '[]',
+    emails JSONB NOT NULL DEFAULT '[]',
+    addresses JSONB NOT NULL DEFAULT '[]',
+    notes TEXT,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_contacts_name ON contacts(LOWER(name));
+CREATE INDEX IF NOT EXISTS idx_contacts_roles ON contacts USING GIN (roles);
+
+CREATE TRIGGER update_contacts_updated_at BEFORE UPDATE ON contacts
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- A contact's part in a task or matter, e.g. 'opposing counsel'
+CREATE TABLE IF NOT EXISTS task_contacts (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    task_id UUID NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    contact_id UUID NOT NULL REFERENCES contacts(id) ON DELETE CASCADE,
+    role VARCHAR(100) NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (task_id, contact_id, role)
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_contacts_contact ON task_contacts(contact_id);
+
+CREATE TABLE IF NOT EXISTS matter_contacts (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    matter_id UUID NOT NULL REFERENCES matters(id) ON DELETE CASCADE,
+    contact_id UUID NOT NULL REFERENCES contacts(id) ON DELETE CASCADE,
+    role VARCHAR(100) NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (matter_id, contact_id, role)
+);
+
+CREATE INDEX IF NOT EXISTS idx_matter_contacts_contact ON matter_contacts(contact_id);
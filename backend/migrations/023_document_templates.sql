@@ -0,0 +1,25 @@
+-- ================================================
+-- Document templates: powers of attorney, engagement letters, cover letters
+-- ================================================
+
+CREATE TABLE IF NOT EXISTS document_templates (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    name VARCHAR(200) NOT NULL,
+    description TEXT,
+    format VARCHAR(10) NOT NULL CHECK (format IN ('html', 'docx')),
+    -- Name of generated documents, without the extension; may use placeholders
+    output_file_name VARCHAR(300) NOT NULL,
+    -- UTF-8 text for HTML templates, the file itself for DOCX
+    content BYTEA NOT NULL,
+    -- The {placeholders} the template uses, found on upload
+    placeholders TEXT[] NOT NULL DEFAULT '{}',
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_document_templates_name ON document_templates(name) WHERE is_active;
+
+CREATE TRIGGER update_document_templates_updated_at BEFORE UPDATE ON document_templates
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
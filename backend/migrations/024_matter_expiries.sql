@@ -0,0 +1,40 @@
+-- ================================================
+-- Limitation periods, lien renewals and other expiry dates on matters
+-- ================================================
+
+CREATE TABLE IF NOT EXISTS matter_expiries (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    matter_id UUID NOT NULL REFERENCES matters(id) ON DELETE CASCADE,
+    expiry_type VARCHAR(30) NOT NULL
+        CHECK (expiry_type IN ('limitation', 'lien_renewal', 'license_expiry', 'other')),
+    expires_on DATE NOT NULL,
+    -- How the date was worked out, e.g. '7 years from the accident on 01/03/2020'
+    basis TEXT NOT NULL,
+    notes TEXT,
+    -- Set once dealt with (claim filed, lien renewed); stops the reminders
+    resolved_at TIMESTAMP WITH TIME ZONE,
+    resolved_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    resolution TEXT,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_matter_expiries_matter ON matter_expiries(matter_id);
+CREATE INDEX IF NOT EXISTS idx_matter_expiries_open ON matter_expiries(expires_on) WHERE resolved_at IS NULL;
+
+CREATE TRIGGER update_matter_expiries_updated_at BEFORE UPDATE ON matter_expiries
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- One row per reminder sent; a changed date starts its reminders afresh
+CREATE TABLE IF NOT EXISTS matter_expiry_reminders (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    expiry_id UUID NOT NULL REFERENCES matter_expiries(id) ON DELETE CASCADE,
+    expires_on DATE NOT NULL,
+    -- e.g. 'before_90d', 'before_1d', 'expired', 'expired_admin'
+    reminder_key VARCHAR(50) NOT NULL,
+    recipient VARCHAR(255) NOT NULL,
+    sent_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+
+    CONSTRAINT uq_matter_expiry_reminder UNIQUE (expiry_id, expires_on, reminder_key)
+);
@@ -0,0 +1,70 @@
+use crate::models::{Attachment, AttachmentContent, NewAttachment};
+use anyhow::Result;
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+/// Stores a file with a task and bumps the task's attachment count.
+/// `base_url` is the API's public URL, used for the download link.
+pub async fn create_attachment(
+    conn: &mut PgConnection,
+    task_id: Uuid,
+    attachment: &NewAttachment,
+    uploaded_by: Option<Uuid>,
+    base_url: &str,
+) -> Result<Attachment> {
+    let id = Uuid::new_v4();
+    let file_url = format!("{}/api/attachments/{}/content", base_url.trim_end_matches('/'), id);
+
+    let created = sqlx::query_as::<_, Attachment>(
+        r#"
+        INSERT INTO attachments (id, task_id, file_name, file_url, file_size, mime_type, content, uploaded_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        RETURNING id, task_id, file_name, file_url, file_size, mime_type, uploaded_by, uploaded_at
+        "#,
+    )
+    .bind(id)
+    .bind(task_id)
+    .bind(&attachment.file_name)
+    .bind(&file_url)
+    .bind(attachment.content.len() as i64)
+    .bind(&attachment.mime_type)
+    .bind(&attachment.content)
+    .bind(uploaded_by)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    sqlx::query("UPDATE tasks SET attachments_count = COALESCE(attachments_count, 0) + 1 WHERE id = $1")
+        .bind(task_id)
+        .execute(&mut *conn)
+        .await?;
+
+    Ok(created)
+}
+
+pub async fn get_attachments_by_task(pool: &PgPool, task_id: Uuid) -> Result<Vec<Attachment>> {
+    let attachments = sqlx::query_as::<_, Attachment>(
+        r#"
+        SELECT id, task_id, file_name, file_url, file_size, mime_type, uploaded_by, uploaded_at
+        FROM attachments
+        WHERE task_id = $1
+        ORDER BY uploaded_at ASC
+        "#,
+    )
+    .bind(task_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(attachments)
+}
+
+/// The stored bytes of an attachment; `None` also for links to external files.
+pub async fn get_attachment_content(pool: &PgPool, id: Uuid) -> Result<Option<AttachmentContent>> {
+    let content = sqlx::query_as::<_, AttachmentContent>(
+        "SELECT file_name, mime_type, content FROM attachments WHERE id = $1 AND content IS NOT NULL"
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(content)
+}
@@ -1,6 +1,7 @@
 use crate::models::{AuditLog, CreateAuditLogRequest};
 use anyhow::Result;
 use sqlx::PgPool;
+use uuid::Uuid;
 
 pub async fn create_audit_log(pool: &PgPool, req: &CreateAuditLogRequest) -> Result<AuditLog> {
     let log = sqlx::query_as::<_, AuditLog>(
@@ -22,3 +23,35 @@ pub async fn create_audit_log(pool: &PgPool, req: &CreateAuditLogRequest) -> Res
 
     Ok(log)
 }
+
+/// Audit records of the task and of edits to and deletions of its comments.
+/// Comment creation is left out, since the comment itself is in the feed.
+pub async fn get_task_audit_logs(pool: &PgPool, task_id: Uuid) -> Result<Vec<AuditLog>> {
+    let logs = sqlx::query_as::<_, AuditLog>(
+        r#"
+        SELECT * FROM audit_log
+        WHERE (entity_type = 'task' AND entity_id = $1)
+           OR (entity_type = 'comment'
+               AND action <> 'comment_created'
+               AND entity_id IN (SELECT id FROM task_comments WHERE task_id = $1))
+        ORDER BY created_at ASC
+        "#,
+    )
+    .bind(task_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(logs)
+}
+
+pub async fn get_audit_log_for_entity(pool: &PgPool, entity_type: &str, entity_id: Uuid) -> Result<Option<AuditLog>> {
+    let log = sqlx::query_as::<_, AuditLog>(
+        "SELECT * FROM audit_log WHERE entity_type = $1 AND entity_id = $2 ORDER BY created_at ASC LIMIT 1"
+    )
+    .bind(entity_type)
+    .bind(entity_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(log)
+}
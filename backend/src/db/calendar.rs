@@ -0,0 +1,121 @@
+use crate::models::{CalendarFeed, FeedHearing, Task, User};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Creates the user's feed, or replaces its token so the old URL stops working.
+pub async fn upsert_calendar_feed(pool: &PgPool, user_id: Uuid, token_hash: &str) -> Result<CalendarFeed> {
+    let feed = sqlx::query_as::<_, CalendarFeed>(
+        r#"
+        INSERT INTO calendar_feeds (user_id, token_hash)
+        VALUES ($1, $2)
+        ON CONFLICT (user_id) DO UPDATE
+        SET token_hash = EXCLUDED.token_hash,
+            last_accessed_at = NULL,
+            created_at = CURRENT_TIMESTAMP
+        RETURNING *
+        "#,
+    )
+    .bind(user_id)
+    .bind(token_hash)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(feed)
+}
+
+pub async fn get_calendar_feed(pool: &PgPool, user_id: Uuid) -> Result<Option<CalendarFeed>> {
+    let feed = sqlx::query_as::<_, CalendarFeed>(
+        "SELECT * FROM calendar_feeds WHERE user_id = $1"
+    )
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(feed)
+}
+
+pub async fn delete_calendar_feed(pool: &PgPool, user_id: Uuid) -> Result<bool> {
+    let result = sqlx::query("DELETE FROM calendar_feeds WHERE user_id = $1")
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// The active user a feed token belongs to, recording the access.
+pub async fn get_calendar_feed_user(pool: &PgPool, token_hash: &str) -> Result<Option<User>> {
+    let user = sqlx::query_as::<_, User>(
+        r#"
+        WITH feed AS (
+            UPDATE calendar_feeds
+            SET last_accessed_at = CURRENT_TIMESTAMP
+            WHERE token_hash = $1
+            RETURNING user_id
+        )
+        SELECT u.* FROM users u
+        JOIN feed ON feed.user_id = u.id
+        WHERE u.is_active
+        "#,
+    )
+    .bind(token_hash)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(user)
+}
+
+/// Open tasks with a due date assigned to `email`.
+pub async fn get_calendar_tasks(pool: &PgPool, email: &str) -> Result<Vec<Task>> {
+    let tasks = sqlx::query_as::<_, Task>(
+        r#"
+        SELECT * FROM tasks
+        WHERE lower(assigned_to_email) = lower($1)
+          AND due_date IS NOT NULL
+          AND status IN ('חדשה', 'בטיפול')
+        ORDER BY due_date ASC
+        "#,
+    )
+    .bind(email)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(tasks)
+}
+
+/// Hearings since `since` in the user's matters, or with preparation tasks
+/// assigned to them. Cancelled hearings are included so that calendars
+/// drop them.
+pub async fn get_calendar_hearings(
+    pool: &PgPool,
+    user_id: Uuid,
+    email: &str,
+    since: DateTime<Utc>,
+) -> Result<Vec<FeedHearing>> {
+    let hearings = sqlx::query_as::<_, FeedHearing>(
+        r#"
+        SELECT h.*, m.matter_number, m.title as matter_title, m.case_number
+        FROM hearings h
+        JOIN matters m ON m.id = h.matter_id
+        WHERE h.scheduled_at >= $3
+          AND (
+            m.responsible_lawyer_id = $1
+            OR EXISTS (
+                SELECT 1 FROM hearing_tasks ht
+                JOIN tasks t ON t.id = ht.task_id
+                WHERE ht.hearing_id = h.id AND lower(t.assigned_to_email) = lower($2)
+            )
+          )
+        ORDER BY h.scheduled_at ASC
+        "#,
+    )
+    .bind(user_id)
+    .bind(email)
+    .bind(since)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(hearings)
+}
@@ -0,0 +1,92 @@
+use crate::models::{ChecklistItem, CreateChecklistItemRequest, UpdateChecklistItemRequest};
+use anyhow::Result;
+use sqlx::{PgExecutor, PgPool};
+use uuid::Uuid;
+
+pub async fn create_checklist_item(
+    executor: impl PgExecutor<'_>,
+    task_id: Uuid,
+    req: &CreateChecklistItemRequest,
+) -> Result<ChecklistItem> {
+    // New items go to the end of the list unless a position is given
+    let item = sqlx::query_as::<_, ChecklistItem>(
+        r#"
+        INSERT INTO task_checklist_items (task_id, title, position)
+        VALUES (
+            $1, $2,
+            COALESCE($3, (SELECT COALESCE(MAX(position) + 1, 0) FROM task_checklist_items WHERE task_id = $1))
+        )
+        RETURNING *
+        "#,
+    )
+    .bind(task_id)
+    .bind(&req.title)
+    .bind(req.position)
+    .fetch_one(executor)
+    .await?;
+
+    Ok(item)
+}
+
+pub async fn get_checklist_items(pool: &PgPool, task_id: Uuid) -> Result<Vec<ChecklistItem>> {
+    let items = sqlx::query_as::<_, ChecklistItem>(
+        "SELECT * FROM task_checklist_items WHERE task_id = $1 ORDER BY position ASC, created_at ASC"
+    )
+    .bind(task_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(items)
+}
+
+pub async fn update_checklist_item(
+    pool: &PgPool,
+    task_id: Uuid,
+    item_id: Uuid,
+    req: &UpdateChecklistItemRequest,
+    user_id: Option<Uuid>,
+) -> Result<Option<ChecklistItem>> {
+    let item = sqlx::query_as::<_, ChecklistItem>(
+        r#"
+        UPDATE task_checklist_items
+        SET
+            title = COALESCE($1, title),
+            position = COALESCE($2, position),
+            is_done = COALESCE($3, is_done),
+            completed_at = CASE
+                WHEN $3 IS NULL THEN completed_at
+                WHEN $3 AND NOT is_done THEN CURRENT_TIMESTAMP
+                WHEN $3 THEN completed_at
+                ELSE NULL
+            END,
+            completed_by = CASE
+                WHEN $3 IS NULL THEN completed_by
+                WHEN $3 AND NOT is_done THEN $4
+                WHEN $3 THEN completed_by
+                ELSE NULL
+            END
+        WHERE id = $5 AND task_id = $6
+        RETURNING *
+        "#,
+    )
+    .bind(&req.title)
+    .bind(req.position)
+    .bind(req.is_done)
+    .bind(user_id)
+    .bind(item_id)
+    .bind(task_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(item)
+}
+
+pub async fn delete_checklist_item(pool: &PgPool, task_id: Uuid, item_id: Uuid) -> Result<bool> {
+    let result = sqlx::query("DELETE FROM task_checklist_items WHERE id = $1 AND task_id = $2")
+        .bind(item_id)
+        .bind(task_id)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
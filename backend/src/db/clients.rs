@@ -0,0 +1,109 @@
+use crate::models::{Client, ClientsQuery, CreateClientRequest, UpdateClientRequest};
+use anyhow::Result;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+pub async fn create_client(pool: &PgPool, req: &CreateClientRequest, created_by: Uuid) -> Result<Client> {
+    let client = sqlx::query_as::<_, Client>(
+        r#"
+        INSERT INTO clients (name, client_type, id_number, email, phone, address, notes, created_by)
+        VALUES ($1, COALESCE($2, 'individual'), $3, $4, $5, $6, $7, $8)
+        RETURNING *
+        "#,
+    )
+    .bind(&req.name)
+    .bind(&req.client_type)
+    .bind(&req.id_number)
+    .bind(&req.email)
+    .bind(&req.phone)
+    .bind(&req.address)
+    .bind(&req.notes)
+    .bind(created_by)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(client)
+}
+
+pub async fn get_clients(pool: &PgPool, params: &ClientsQuery) -> Result<Vec<Client>> {
+    let pattern = params.q.as_deref().map(|q| format!("%{}%", q.trim()));
+
+    let clients = sqlx::query_as::<_, Client>(
+        r#"
+        SELECT * FROM clients
+        WHERE ($1 OR is_active)
+          AND ($2::TEXT IS NULL
+               OR name ILIKE $2 OR id_number ILIKE $2 OR email ILIKE $2 OR phone ILIKE $2)
+        ORDER BY name ASC
+        "#,
+    )
+    .bind(params.include_inactive)
+    .bind(pattern)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(clients)
+}
+
+pub async fn get_client_by_id(pool: &PgPool, id: Uuid) -> Result<Option<Client>> {
+    let client = sqlx::query_as::<_, Client>(
+        "SELECT * FROM clients WHERE id = $1"
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(client)
+}
+
+pub async fn get_client_by_id_number(pool: &PgPool, id_number: &str) -> Result<Option<Client>> {
+    let client = sqlx::query_as::<_, Client>(
+        "SELECT * FROM clients WHERE id_number = $1"
+    )
+    .bind(id_number)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(client)
+}
+
+pub async fn update_client(pool: &PgPool, id: Uuid, req: &UpdateClientRequest) -> Result<Option<Client>> {
+    let client = sqlx::query_as::<_, Client>(
+        r#"
+        UPDATE clients
+        SET
+            name = COALESCE($1, name),
+            client_type = COALESCE($2, client_type),
+            id_number = COALESCE($3, id_number),
+            email = COALESCE($4, email),
+            phone = COALESCE($5, phone),
+            address = COALESCE($6, address),
+            notes = COALESCE($7, notes),
+            is_active = COALESCE($8, is_active)
+        WHERE id = $9
+        RETURNING *
+        "#,
+    )
+    .bind(&req.name)
+    .bind(&req.client_type)
+    .bind(&req.id_number)
+    .bind(&req.email)
+    .bind(&req.phone)
+    .bind(&req.address)
+    .bind(&req.notes)
+    .bind(req.is_active)
+    .bind(id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(client)
+}
+
+pub async fn delete_client(pool: &PgPool, id: Uuid) -> Result<bool> {
+    let result = sqlx::query("DELETE FROM clients WHERE id = $1")
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
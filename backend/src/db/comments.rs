@@ -0,0 +1,116 @@
+use crate::models::{TaskComment, TaskCommentEdit};
+use anyhow::Result;
+use sqlx::{PgConnection, PgExecutor, PgPool};
+use uuid::Uuid;
+
+pub async fn create_comment(
+    executor: impl PgExecutor<'_>,
+    task_id: Uuid,
+    author_id: Option<Uuid>,
+    author_name: &str,
+    author_email: &str,
+    body: &str,
+    mentioned_user_ids: &[Uuid],
+) -> Result<TaskComment> {
+    let comment = sqlx::query_as::<_, TaskComment>(
+        r#"
+        INSERT INTO task_comments (task_id, author_id, author_name, author_email, body, mentioned_user_ids)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING *
+        "#,
+    )
+    .bind(task_id)
+    .bind(author_id)
+    .bind(author_name)
+    .bind(author_email)
+    .bind(body)
+    .bind(mentioned_user_ids)
+    .fetch_one(executor)
+    .await?;
+
+    Ok(comment)
+}
+
+pub async fn get_comments_by_task(pool: &PgPool, task_id: Uuid) -> Result<Vec<TaskComment>> {
+    let comments = sqlx::query_as::<_, TaskComment>(
+        "SELECT * FROM task_comments WHERE task_id = $1 AND deleted_at IS NULL ORDER BY created_at ASC"
+    )
+    .bind(task_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(comments)
+}
+
+pub async fn get_comment_by_id(
+    pool: &PgPool,
+    task_id: Uuid,
+    comment_id: Uuid,
+) -> Result<Option<TaskComment>> {
+    let comment = sqlx::query_as::<_, TaskComment>(
+        "SELECT * FROM task_comments WHERE id = $1 AND task_id = $2 AND deleted_at IS NULL"
+    )
+    .bind(comment_id)
+    .bind(task_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(comment)
+}
+
+/// Replaces the comment body, keeping the previous version in `task_comment_edits`.
+/// Run it inside a transaction so both writes land together.
+pub async fn update_comment(
+    conn: &mut PgConnection,
+    comment: &TaskComment,
+    edited_by: Option<Uuid>,
+    body: &str,
+    mentioned_user_ids: &[Uuid],
+) -> Result<TaskComment> {
+    sqlx::query(
+        "INSERT INTO task_comment_edits (comment_id, previous_body, edited_by) VALUES ($1, $2, $3)"
+    )
+    .bind(comment.id)
+    .bind(&comment.body)
+    .bind(edited_by)
+    .execute(&mut *conn)
+    .await?;
+
+    let updated = sqlx::query_as::<_, TaskComment>(
+        r#"
+        UPDATE task_comments
+        SET body = $1, mentioned_user_ids = $2, edited_at = CURRENT_TIMESTAMP
+        WHERE id = $3
+        RETURNING *
+        "#,
+    )
+    .bind(body)
+    .bind(mentioned_user_ids)
+    .bind(comment.id)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    Ok(updated)
+}
+
+pub async fn soft_delete_comment(executor: impl PgExecutor<'_>, comment_id: Uuid) -> Result<bool> {
+    let result = sqlx::query(
+        "UPDATE task_comments SET deleted_at = CURRENT_TIMESTAMP WHERE id = $1 AND deleted_at IS NULL"
+    )
+    .bind(comment_id)
+    .execute(executor)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn get_comment_edits(pool: &PgPool, comment_id: Uuid) -> Result<Vec<TaskCommentEdit>> {
+    let edits = sqlx::query_as::<_, TaskCommentEdit>(
+        "SELECT * FROM task_comment_edits WHERE comment_id = $1 ORDER BY edited_at ASC"
+    )
+    .bind(comment_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(edits)
+}
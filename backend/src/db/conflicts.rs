@@ -0,0 +1,49 @@
+use crate::models::ConflictCandidate;
+use anyhow::Result;
+use sqlx::PgPool;
+
+/// Everyone a new matter could conflict with: every client (with each of its
+/// matters), every opposing party named on a matter, every matter party and
+/// every contact linked to a matter.
+pub async fn get_conflict_candidates(pool: &PgPool) -> Result<Vec<ConflictCandidate>> {
+    let candidates = sqlx::query_as::<_, ConflictCandidate>(
+        r#"
+        SELECT
+            'client' as source, c.name::TEXT as name, c.id_number::TEXT as id_number,
+            NULL::TEXT as role, c.id as client_id,
+            m.id as matter_id, m.matter_number::TEXT as matter_number,
+            m.title::TEXT as matter_title, m.status::TEXT as matter_status
+        FROM clients c
+        LEFT JOIN matters m ON m.client_id = c.id
+
+        UNION ALL
+
+        SELECT
+            'opposing_party', m.opposing_party, NULL, 'opposing_party', NULL,
+            m.id, m.matter_number, m.title, m.status
+        FROM matters m
+        WHERE m.opposing_party IS NOT NULL AND m.opposing_party <> ''
+
+        UNION ALL
+
+        SELECT
+            'related_party', p.name, p.id_number, p.role, NULL,
+            m.id, m.matter_number, m.title, m.status
+        FROM matter_parties p
+        JOIN matters m ON m.id = p.matter_id
+
+        UNION ALL
+
+        SELECT
+            'contact', c.name, c.id_number, mc.role, NULL,
+            m.id, m.matter_number, m.title, m.status
+        FROM matter_contacts mc
+        JOIN contacts c ON c.id = mc.contact_id
+        JOIN matters m ON m.id = mc.matter_id
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(candidates)
+}
@@ -0,0 +1,216 @@
+use crate::models::{
+    Contact, ContactsQuery, CreateContactRequest, LinkedContact, UpdateContactRequest,
+};
+use anyhow::Result;
+use sqlx::{types::Json, PgPool};
+use uuid::Uuid;
+
+pub async fn create_contact(pool: &PgPool, req: &CreateContactRequest, created_by: Uuid) -> Result<Contact> {
+    let contact = sqlx::query_as::<_, Contact>(
+        r#"
+        INSERT INTO contacts (
+            contact_type, name, organization, job_title, id_number, roles,
+            phones, emails, addresses, notes, created_by
+        )
+        VALUES (COALESCE($1, 'person'), $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+        RETURNING *
+        "#,
+    )
+    .bind(&req.contact_type)
+    .bind(&req.name)
+    .bind(&req.organization)
+    .bind(&req.job_title)
+    .bind(&req.id_number)
+    .bind(&req.roles)
+    .bind(Json(&req.phones))
+    .bind(Json(&req.emails))
+    .bind(Json(&req.addresses))
+    .bind(&req.notes)
+    .bind(created_by)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(contact)
+}
+
+pub async fn get_contacts(pool: &PgPool, params: &ContactsQuery) -> Result<Vec<Contact>> {
+    let pattern = params.q.as_deref().map(|q| format!("%{}%", q.trim()));
+
+    let contacts = sqlx::query_as::<_, Contact>(
+        r#"
+        SELECT * FROM contacts
+        WHERE ($1::TEXT IS NULL
+               OR name ILIKE $1 OR organization ILIKE $1 OR job_title ILIKE $1
+               OR id_number ILIKE $1 OR phones::TEXT ILIKE $1 OR emails::TEXT ILIKE $1)
+          AND ($2::TEXT IS NULL OR $2 = ANY(roles))
+          AND ($3::VARCHAR IS NULL OR contact_type = $3)
+        ORDER BY name ASC
+        "#,
+    )
+    .bind(pattern)
+    .bind(&params.role)
+    .bind(&params.contact_type)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(contacts)
+}
+
+pub async fn get_contact_by_id(pool: &PgPool, id: Uuid) -> Result<Option<Contact>> {
+    let contact = sqlx::query_as::<_, Contact>(
+        "SELECT * FROM contacts WHERE id = $1"
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(contact)
+}
+
+/// Contacts with the same name, ignoring case, for spotting duplicates.
+pub async fn get_contacts_by_name(pool: &PgPool, name: &str) -> Result<Vec<Contact>> {
+    let contacts = sqlx::query_as::<_, Contact>(
+        "SELECT * FROM contacts WHERE LOWER(name) = LOWER($1)"
+    )
+    .bind(name.trim())
+    .fetch_all(pool)
+    .await?;
+
+    Ok(contacts)
+}
+
+pub async fn update_contact(pool: &PgPool, id: Uuid, req: &UpdateContactRequest) -> Result<Option<Contact>> {
+    let contact = sqlx::query_as::<_, Contact>(
+        r#"
+        UPDATE contacts
+        SET
+            contact_type = COALESCE($1, contact_type),
+            name = COALESCE($2, name),
+            organization = COALESCE($3, organization),
+            job_title = COALESCE($4, job_title),
+            id_number = COALESCE($5, id_number),
+            roles = COALESCE($6, roles),
+            phones = COALESCE($7, phones),
+            emails = COALESCE($8, emails),
+            addresses = COALESCE($9, addresses),
+            notes = COALESCE($10, notes)
+        WHERE id = $11
+        RETURNING *
+        "#,
+    )
+    .bind(&req.contact_type)
+    .bind(&req.name)
+    .bind(&req.organization)
+    .bind(&req.job_title)
+    .bind(&req.id_number)
+    .bind(&req.roles)
+    .bind(req.phones.as_ref().map(Json))
+    .bind(req.emails.as_ref().map(Json))
+    .bind(req.addresses.as_ref().map(Json))
+    .bind(&req.notes)
+    .bind(id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(contact)
+}
+
+/// Links to tasks and matters go with it.
+pub async fn delete_contact(pool: &PgPool, id: Uuid) -> Result<bool> {
+    let result = sqlx::query("DELETE FROM contacts WHERE id = $1")
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn link_task_contact(pool: &PgPool, task_id: Uuid, contact_id: Uuid, role: &str) -> Result<Uuid> {
+    let id: Uuid = sqlx::query_scalar(
+        r#"
+        INSERT INTO task_contacts (task_id, contact_id, role)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (task_id, contact_id, role) DO UPDATE SET role = EXCLUDED.role
+        RETURNING id
+        "#,
+    )
+    .bind(task_id)
+    .bind(contact_id)
+    .bind(role.trim())
+    .fetch_one(pool)
+    .await?;
+
+    Ok(id)
+}
+
+pub async fn get_task_contacts(pool: &PgPool, task_id: Uuid) -> Result<Vec<LinkedContact>> {
+    let contacts = sqlx::query_as::<_, LinkedContact>(
+        r#"
+        SELECT tc.id as link_id, tc.role as link_role, c.*
+        FROM task_contacts tc
+        JOIN contacts c ON c.id = tc.contact_id
+        WHERE tc.task_id = $1
+        ORDER BY tc.role ASC, c.name ASC
+        "#,
+    )
+    .bind(task_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(contacts)
+}
+
+pub async fn unlink_task_contact(pool: &PgPool, task_id: Uuid, link_id: Uuid) -> Result<bool> {
+    let result = sqlx::query("DELETE FROM task_contacts WHERE id = $1 AND task_id = $2")
+        .bind(link_id)
+        .bind(task_id)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn link_matter_contact(pool: &PgPool, matter_id: Uuid, contact_id: Uuid, role: &str) -> Result<Uuid> {
+    let id: Uuid = sqlx::query_scalar(
+        r#"
+        INSERT INTO matter_contacts (matter_id, contact_id, role)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (matter_id, contact_id, role) DO UPDATE SET role = EXCLUDED.role
+        RETURNING id
+        "#,
+    )
+    .bind(matter_id)
+    .bind(contact_id)
+    .bind(role.trim())
+    .fetch_one(pool)
+    .await?;
+
+    Ok(id)
+}
+
+pub async fn get_matter_contacts(pool: &PgPool, matter_id: Uuid) -> Result<Vec<LinkedContact>> {
+    let contacts = sqlx::query_as::<_, LinkedContact>(
+        r#"
+        SELECT mc.id as link_id, mc.role as link_role, c.*
+        FROM matter_contacts mc
+        JOIN contacts c ON c.id = mc.contact_id
+        WHERE mc.matter_id = $1
+        ORDER BY mc.role ASC, c.name ASC
+        "#,
+    )
+    .bind(matter_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(contacts)
+}
+
+pub async fn unlink_matter_contact(pool: &PgPool, matter_id: Uuid, link_id: Uuid) -> Result<bool> {
+    let result = sqlx::query("DELETE FROM matter_contacts WHERE id = $1 AND matter_id = $2")
+        .bind(link_id)
+        .bind(matter_id)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
@@ -0,0 +1,47 @@
+use crate::models::{DeadlineCalculation, TaskDeadline};
+use anyhow::Result;
+use chrono::NaiveDate;
+use sqlx::{types::Json, PgExecutor, PgPool};
+use uuid::Uuid;
+
+pub async fn create_task_deadline(
+    executor: impl PgExecutor<'_>,
+    task_id: Uuid,
+    calculation: &DeadlineCalculation,
+    previous_due_date: Option<NaiveDate>,
+    note: Option<&str>,
+    computed_by: Uuid,
+) -> Result<TaskDeadline> {
+    let deadline = sqlx::query_as::<_, TaskDeadline>(
+        r#"
+        INSERT INTO task_deadlines (
+            task_id, trigger_date, rule, due_date, previous_due_date, skipped_days, note, computed_by
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        RETURNING *
+        "#,
+    )
+    .bind(task_id)
+    .bind(calculation.trigger_date)
+    .bind(&calculation.rule)
+    .bind(calculation.due_date)
+    .bind(previous_due_date)
+    .bind(Json(&calculation.skipped_days))
+    .bind(note)
+    .bind(computed_by)
+    .fetch_one(executor)
+    .await?;
+
+    Ok(deadline)
+}
+
+pub async fn get_task_deadlines(pool: &PgPool, task_id: Uuid) -> Result<Vec<TaskDeadline>> {
+    let deadlines = sqlx::query_as::<_, TaskDeadline>(
+        "SELECT * FROM task_deadlines WHERE task_id = $1 ORDER BY created_at DESC"
+    )
+    .bind(task_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(deadlines)
+}
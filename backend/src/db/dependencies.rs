@@ -0,0 +1,152 @@
+use crate::models::{Task, TaskDependency};
+use anyhow::Result;
+use sqlx::{PgConnection, PgExecutor, PgPool};
+use uuid::Uuid;
+
+/// Outcome of trying to add a dependency edge.
+pub enum AddDependencyResult {
+    Added(TaskDependency),
+    AlreadyExists,
+    WouldCreateCycle,
+}
+
+/// Adds "`task_id` is blocked by `blocked_by_id`", refusing edges that would
+/// close a cycle. Must run inside a transaction: writes are serialized with a
+/// transaction-scoped advisory lock so that two concurrent inserts cannot each
+/// pass the check and form a cycle together.
+pub async fn add_task_dependency(
+    conn: &mut PgConnection,
+    task_id: Uuid,
+    blocked_by_id: Uuid,
+    created_by: Option<Uuid>,
+) -> Result<AddDependencyResult> {
+    sqlx::query("SELECT pg_advisory_xact_lock(hashtext('task_dependencies'))")
+        .execute(&mut *conn)
+        .await?;
+
+    // Would blocked_by_id (transitively) end up waiting on task_id?
+    let creates_cycle: bool = sqlx::query_scalar(
+        r#"
+        WITH RECURSIVE chain(id) AS (
+            SELECT blocked_by_id FROM task_dependencies WHERE task_id = $1
+            UNION
+            SELECT d.blocked_by_id
+            FROM task_dependencies d
+            JOIN chain c ON d.task_id = c.id
+        )
+        SELECT EXISTS(SELECT 1 FROM chain WHERE id = $2)
+        "#,
+    )
+    .bind(blocked_by_id)
+    .bind(task_id)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    if creates_cycle {
+        return Ok(AddDependencyResult::WouldCreateCycle);
+    }
+
+    let dependency = sqlx::query_as::<_, TaskDependency>(
+        r#"
+        INSERT INTO task_dependencies (task_id, blocked_by_id, created_by)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (task_id, blocked_by_id) DO NOTHING
+        RETURNING *
+        "#,
+    )
+    .bind(task_id)
+    .bind(blocked_by_id)
+    .bind(created_by)
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    Ok(match dependency {
+        Some(d) => AddDependencyResult::Added(d),
+        None => AddDependencyResult::AlreadyExists,
+    })
+}
+
+pub async fn remove_task_dependency(pool: &PgPool, task_id: Uuid, blocked_by_id: Uuid) -> Result<bool> {
+    let result = sqlx::query("DELETE FROM task_dependencies WHERE task_id = $1 AND blocked_by_id = $2")
+        .bind(task_id)
+        .bind(blocked_by_id)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Tasks that `task_id` waits on.
+pub async fn get_blocking_tasks(pool: &PgPool, task_id: Uuid) -> Result<Vec<Task>> {
+    let tasks = sqlx::query_as::<_, Task>(
+        r#"
+        SELECT t.* FROM tasks t
+        JOIN task_dependencies d ON d.blocked_by_id = t.id
+        WHERE d.task_id = $1
+        ORDER BY t.created_at ASC
+        "#,
+    )
+    .bind(task_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(tasks)
+}
+
+/// Tasks that wait on `task_id`.
+pub async fn get_dependent_tasks(pool: &PgPool, task_id: Uuid) -> Result<Vec<Task>> {
+    let tasks = sqlx::query_as::<_, Task>(
+        r#"
+        SELECT t.* FROM tasks t
+        JOIN task_dependencies d ON d.task_id = t.id
+        WHERE d.blocked_by_id = $1
+        ORDER BY t.created_at ASC
+        "#,
+    )
+    .bind(task_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(tasks)
+}
+
+/// `(task_id, blocked_by_id)` pairs for every blocker of the given tasks that
+/// is still open.
+pub async fn get_open_blockers(pool: &PgPool, ids: &[Uuid]) -> Result<Vec<(Uuid, Uuid)>> {
+    let blockers = sqlx::query_as::<_, (Uuid, Uuid)>(
+        r#"
+        SELECT d.task_id, d.blocked_by_id
+        FROM task_dependencies d
+        JOIN tasks b ON b.id = d.blocked_by_id
+        WHERE d.task_id = ANY($1) AND b.status IN ('חדשה', 'בטיפול')
+        "#,
+    )
+    .bind(ids)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(blockers)
+}
+
+/// Open tasks that were waiting on `blocker_id` and have no other open
+/// blockers left.
+pub async fn get_unblocked_tasks(executor: impl PgExecutor<'_>, blocker_id: Uuid) -> Result<Vec<Task>> {
+    let tasks = sqlx::query_as::<_, Task>(
+        r#"
+        SELECT t.* FROM tasks t
+        JOIN task_dependencies d ON d.task_id = t.id
+        WHERE d.blocked_by_id = $1
+          AND t.status IN ('חדשה', 'בטיפול')
+          AND NOT EXISTS (
+              SELECT 1 FROM task_dependencies o
+              JOIN tasks b ON b.id = o.blocked_by_id
+              WHERE o.task_id = t.id AND b.status IN ('חדשה', 'בטיפול')
+          )
+        "#,
+    )
+    .bind(blocker_id)
+    .fetch_all(executor)
+    .await?;
+
+    Ok(tasks)
+}
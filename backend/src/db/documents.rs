@@ -0,0 +1,147 @@
+use crate::models::{
+    CreateDocumentTemplateRequest, DocumentTemplate, DocumentTemplateContent,
+    UpdateDocumentTemplateRequest,
+};
+use anyhow::Result;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+pub async fn create_document_template(
+    pool: &PgPool,
+    req: &CreateDocumentTemplateRequest,
+    content: &[u8],
+    placeholders: &[String],
+    created_by: Uuid,
+) -> Result<DocumentTemplate> {
+    let template = sqlx::query_as::<_, DocumentTemplate>(
+        r#"
+        INSERT INTO document_templates (
+            name, description, format, output_file_name, content, placeholders, created_by
+        )
+        VALUES ($1, $2, $3, COALESCE($4, $1), $5, $6, $7)
+        RETURNING id, name, description, format, output_file_name, placeholders,
+                  is_active, created_by, created_at, updated_at
+        "#,
+    )
+    .bind(&req.name)
+    .bind(&req.description)
+    .bind(&req.format)
+    .bind(&req.output_file_name)
+    .bind(content)
+    .bind(placeholders)
+    .bind(created_by)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(template)
+}
+
+pub async fn get_document_templates(pool: &PgPool) -> Result<Vec<DocumentTemplate>> {
+    let templates = sqlx::query_as::<_, DocumentTemplate>(
+        r#"
+        SELECT id, name, description, format, output_file_name, placeholders,
+               is_active, created_by, created_at, updated_at
+        FROM document_templates
+        WHERE is_active
+        ORDER BY name ASC
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(templates)
+}
+
+pub async fn get_document_template_by_id(pool: &PgPool, id: Uuid) -> Result<Option<DocumentTemplate>> {
+    let template = sqlx::query_as::<_, DocumentTemplate>(
+        r#"
+        SELECT id, name, description, format, output_file_name, placeholders,
+               is_active, created_by, created_at, updated_at
+        FROM document_templates
+        WHERE id = $1 AND is_active
+        "#,
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(template)
+}
+
+pub async fn get_document_template_by_name(pool: &PgPool, name: &str) -> Result<Option<DocumentTemplate>> {
+    let template = sqlx::query_as::<_, DocumentTemplate>(
+        r#"
+        SELECT id, name, description, format, output_file_name, placeholders,
+               is_active, created_by, created_at, updated_at
+        FROM document_templates
+        WHERE name = $1 AND is_active
+        "#,
+    )
+    .bind(name)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(template)
+}
+
+pub async fn get_document_template_content(
+    pool: &PgPool,
+    id: Uuid,
+) -> Result<Option<DocumentTemplateContent>> {
+    let content = sqlx::query_as::<_, DocumentTemplateContent>(
+        r#"
+        SELECT name, format, output_file_name, placeholders, content
+        FROM document_templates
+        WHERE id = $1 AND is_active
+        "#,
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(content)
+}
+
+/// `content` and `placeholders` go together: a new file and what it uses.
+pub async fn update_document_template(
+    pool: &PgPool,
+    id: Uuid,
+    req: &UpdateDocumentTemplateRequest,
+    content: Option<&[u8]>,
+    placeholders: Option<&[String]>,
+) -> Result<Option<DocumentTemplate>> {
+    let template = sqlx::query_as::<_, DocumentTemplate>(
+        r#"
+        UPDATE document_templates
+        SET
+            name = COALESCE($1, name),
+            description = COALESCE($2, description),
+            output_file_name = COALESCE($3, output_file_name),
+            content = COALESCE($4, content),
+            placeholders = COALESCE($5, placeholders)
+        WHERE id = $6 AND is_active
+        RETURNING id, name, description, format, output_file_name, placeholders,
+                  is_active, created_by, created_at, updated_at
+        "#,
+    )
+    .bind(&req.name)
+    .bind(&req.description)
+    .bind(&req.output_file_name)
+    .bind(content)
+    .bind(placeholders)
+    .bind(id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(template)
+}
+
+/// Documents already generated from the template stay with their tasks.
+pub async fn deactivate_document_template(pool: &PgPool, id: Uuid) -> Result<bool> {
+    let result = sqlx::query("UPDATE document_templates SET is_active = false WHERE id = $1 AND is_active")
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
@@ -0,0 +1,76 @@
+use crate::models::{NewTaskEvent, TaskEvent};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::{PgExecutor, PgPool};
+
+/// Appends an event to the stream and queues it for every active webhook
+/// subscribed to its type. Listeners are woken when the surrounding
+/// transaction commits.
+pub async fn record_task_event(executor: impl PgExecutor<'_>, event: &NewTaskEvent) -> Result<i64> {
+    let id: i64 = sqlx::query_scalar(
+        r#"
+        WITH event AS (
+            INSERT INTO task_events (event_type, task_id, visible_to, payload)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id, event_type, payload
+        ),
+        deliveries AS (
+            INSERT INTO webhook_deliveries (subscription_id, event_id, event_type, payload)
+            SELECT s.id, e.id, e.event_type, e.payload
+            FROM webhook_subscriptions s, event e
+            WHERE s.is_active
+              AND (cardinality(s.event_types) = 0 OR e.event_type = ANY(s.event_types))
+        )
+        SELECT id FROM event
+        "#,
+    )
+    .bind(event.event_type.as_str())
+    .bind(event.task_id)
+    .bind(&event.visible_to)
+    .bind(&event.payload)
+    .fetch_one(executor)
+    .await?;
+
+    Ok(id)
+}
+
+pub async fn get_task_event_by_id(pool: &PgPool, id: i64) -> Result<Option<TaskEvent>> {
+    let event = sqlx::query_as::<_, TaskEvent>(
+        "SELECT * FROM task_events WHERE id = $1"
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(event)
+}
+
+/// Events after `after_id`, oldest first.
+pub async fn get_task_events_after(pool: &PgPool, after_id: i64, limit: i64) -> Result<Vec<TaskEvent>> {
+    let events = sqlx::query_as::<_, TaskEvent>(
+        "SELECT * FROM task_events WHERE id > $1 ORDER BY id LIMIT $2"
+    )
+    .bind(after_id)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(events)
+}
+
+pub async fn get_latest_task_event_id(pool: &PgPool) -> Result<i64> {
+    let id: i64 = sqlx::query_scalar("SELECT COALESCE(MAX(id), 0) FROM task_events")
+        .fetch_one(pool)
+        .await?;
+
+    Ok(id)
+}
+
+pub async fn prune_task_events(pool: &PgPool, before: DateTime<Utc>) -> Result<u64> {
+    let result = sqlx::query("DELETE FROM task_events WHERE created_at < $1")
+        .bind(before)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected())
+}
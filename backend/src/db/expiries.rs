@@ -0,0 +1,158 @@
+use crate::models::{
+    CreateMatterExpiryRequest, MatterExpiry, UpcomingExpiry, UpdateMatterExpiryRequest,
+};
+use anyhow::Result;
+use chrono::NaiveDate;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+pub async fn create_matter_expiry(
+    pool: &PgPool,
+    matter_id: Uuid,
+    req: &CreateMatterExpiryRequest,
+    created_by: Uuid,
+) -> Result<MatterExpiry> {
+    let expiry = sqlx::query_as::<_, MatterExpiry>(
+        r#"
+        INSERT INTO matter_expiries (matter_id, expiry_type, expires_on, basis, notes, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING *
+        "#,
+    )
+    .bind(matter_id)
+    .bind(&req.expiry_type)
+    .bind(req.expires_on)
+    .bind(&req.basis)
+    .bind(&req.notes)
+    .bind(created_by)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(expiry)
+}
+
+pub async fn get_matter_expiries(pool: &PgPool, matter_id: Uuid) -> Result<Vec<MatterExpiry>> {
+    let expiries = sqlx::query_as::<_, MatterExpiry>(
+        "SELECT * FROM matter_expiries WHERE matter_id = $1 ORDER BY expires_on ASC"
+    )
+    .bind(matter_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(expiries)
+}
+
+pub async fn get_matter_expiry(pool: &PgPool, matter_id: Uuid, id: Uuid) -> Result<Option<MatterExpiry>> {
+    let expiry = sqlx::query_as::<_, MatterExpiry>(
+        "SELECT * FROM matter_expiries WHERE id = $1 AND matter_id = $2"
+    )
+    .bind(id)
+    .bind(matter_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(expiry)
+}
+
+pub async fn update_matter_expiry(
+    pool: &PgPool,
+    matter_id: Uuid,
+    id: Uuid,
+    req: &UpdateMatterExpiryRequest,
+) -> Result<Option<MatterExpiry>> {
+    let expiry = sqlx::query_as::<_, MatterExpiry>(
+        r#"
+        UPDATE matter_expiries
+        SET
+            expiry_type = COALESCE($1, expiry_type),
+            expires_on = COALESCE($2, expires_on),
+            basis = COALESCE($3, basis),
+            notes = COALESCE($4, notes)
+        WHERE id = $5 AND matter_id = $6
+        RETURNING *
+        "#,
+    )
+    .bind(&req.expiry_type)
+    .bind(req.expires_on)
+    .bind(&req.basis)
+    .bind(&req.notes)
+    .bind(id)
+    .bind(matter_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(expiry)
+}
+
+/// Returns `None` if the expiry does not exist or is already resolved.
+pub async fn resolve_matter_expiry(
+    pool: &PgPool,
+    matter_id: Uuid,
+    id: Uuid,
+    resolution: &str,
+    resolved_by: Uuid,
+) -> Result<Option<MatterExpiry>> {
+    let expiry = sqlx::query_as::<_, MatterExpiry>(
+        r#"
+        UPDATE matter_expiries
+        SET resolved_at = NOW(), resolved_by = $1, resolution = $2
+        WHERE id = $3 AND matter_id = $4 AND resolved_at IS NULL
+        RETURNING *
+        "#,
+    )
+    .bind(resolved_by)
+    .bind(resolution.trim())
+    .bind(id)
+    .bind(matter_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(expiry)
+}
+
+pub async fn delete_matter_expiry(pool: &PgPool, matter_id: Uuid, id: Uuid) -> Result<bool> {
+    let result = sqlx::query("DELETE FROM matter_expiries WHERE id = $1 AND matter_id = $2")
+        .bind(id)
+        .bind(matter_id)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Unresolved expiries up to `until`, including those already past, soonest
+/// first, across all matters.
+pub async fn get_upcoming_expiries(
+    pool: &PgPool,
+    today: NaiveDate,
+    until: NaiveDate,
+    expiry_type: Option<&str>,
+    lawyer_id: Option<Uuid>,
+) -> Result<Vec<UpcomingExpiry>> {
+    let expiries = sqlx::query_as::<_, UpcomingExpiry>(
+        r#"
+        SELECT
+            e.*,
+            m.matter_number, m.title as matter_title, c.name as client_name,
+            m.responsible_lawyer_id as lawyer_id, u.name as lawyer_name, u.email as lawyer_email,
+            (e.expires_on - $1::DATE) as days_left
+        FROM matter_expiries e
+        JOIN matters m ON m.id = e.matter_id
+        JOIN clients c ON c.id = m.client_id
+        LEFT JOIN users u ON u.id = m.responsible_lawyer_id AND u.is_active
+        WHERE e.resolved_at IS NULL
+          AND e.expires_on <= $2
+          AND ($3::VARCHAR IS NULL OR e.expiry_type = $3)
+          AND ($4::UUID IS NULL OR m.responsible_lawyer_id = $4)
+        ORDER BY e.expires_on ASC, m.matter_number ASC
+        "#,
+    )
+    .bind(today)
+    .bind(until)
+    .bind(expiry_type)
+    .bind(lawyer_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(expiries)
+}
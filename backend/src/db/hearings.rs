@@ -0,0 +1,232 @@
+use crate::models::{
+    CreateHearingRequest, CreatePreparationStepRequest, Hearing, HearingPreparationStep,
+    HearingTaskLink, HearingsQuery, Task, UpdateHearingRequest,
+};
+use anyhow::Result;
+use sqlx::{PgExecutor, PgPool};
+use uuid::Uuid;
+
+pub async fn create_hearing(
+    executor: impl PgExecutor<'_>,
+    req: &CreateHearingRequest,
+    court: Option<&str>,
+    created_by: Uuid,
+) -> Result<Hearing> {
+    let hearing = sqlx::query_as::<_, Hearing>(
+        r#"
+        INSERT INTO hearings (matter_id, hearing_type, scheduled_at, court, judge, courtroom, notes, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        RETURNING *
+        "#,
+    )
+    .bind(req.matter_id)
+    .bind(&req.hearing_type)
+    .bind(req.scheduled_at)
+    .bind(court)
+    .bind(&req.judge)
+    .bind(&req.courtroom)
+    .bind(&req.notes)
+    .bind(created_by)
+    .fetch_one(executor)
+    .await?;
+
+    Ok(hearing)
+}
+
+pub async fn get_hearings(pool: &PgPool, params: &HearingsQuery) -> Result<Vec<Hearing>> {
+    let hearings = sqlx::query_as::<_, Hearing>(
+        r#"
+        SELECT * FROM hearings
+        WHERE ($1::UUID IS NULL OR matter_id = $1)
+          AND ($2::TIMESTAMPTZ IS NULL OR scheduled_at >= $2)
+          AND ($3::TIMESTAMPTZ IS NULL OR scheduled_at < $3)
+          AND ($4 OR status <> 'cancelled')
+        ORDER BY scheduled_at ASC
+        "#,
+    )
+    .bind(params.matter_id)
+    .bind(params.from)
+    .bind(params.to)
+    .bind(params.include_cancelled)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(hearings)
+}
+
+pub async fn get_hearing_by_id(pool: &PgPool, id: Uuid) -> Result<Option<Hearing>> {
+    let hearing = sqlx::query_as::<_, Hearing>(
+        "SELECT * FROM hearings WHERE id = $1"
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(hearing)
+}
+
+pub async fn update_hearing(
+    executor: impl PgExecutor<'_>,
+    id: Uuid,
+    req: &UpdateHearingRequest,
+) -> Result<Option<Hearing>> {
+    let hearing = sqlx::query_as::<_, Hearing>(
+        r#"
+        UPDATE hearings
+        SET
+            hearing_type = COALESCE($1, hearing_type),
+            scheduled_at = COALESCE($2, scheduled_at),
+            court = COALESCE($3, court),
+            judge = COALESCE($4, judge),
+            courtroom = COALESCE($5, courtroom),
+            notes = COALESCE($6, notes),
+            status = COALESCE($7, status)
+        WHERE id = $8
+        RETURNING *
+        "#,
+    )
+    .bind(&req.hearing_type)
+    .bind(req.scheduled_at)
+    .bind(&req.court)
+    .bind(&req.judge)
+    .bind(&req.courtroom)
+    .bind(&req.notes)
+    .bind(&req.status)
+    .bind(id)
+    .fetch_optional(executor)
+    .await?;
+
+    Ok(hearing)
+}
+
+pub async fn cancel_hearing(executor: impl PgExecutor<'_>, id: Uuid) -> Result<Hearing> {
+    let hearing = sqlx::query_as::<_, Hearing>(
+        r#"
+        UPDATE hearings
+        SET status = 'cancelled', cancelled_at = CURRENT_TIMESTAMP
+        WHERE id = $1
+        RETURNING *
+        "#,
+    )
+    .bind(id)
+    .fetch_one(executor)
+    .await?;
+
+    Ok(hearing)
+}
+
+/// Generated tasks are kept, without their link to the hearing.
+pub async fn delete_hearing(pool: &PgPool, id: Uuid) -> Result<bool> {
+    let result = sqlx::query("DELETE FROM hearings WHERE id = $1")
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn link_hearing_task(
+    executor: impl PgExecutor<'_>,
+    hearing_id: Uuid,
+    task_id: Uuid,
+    days_before: i32,
+) -> Result<()> {
+    sqlx::query("INSERT INTO hearing_tasks (hearing_id, task_id, days_before) VALUES ($1, $2, $3)")
+        .bind(hearing_id)
+        .bind(task_id)
+        .bind(days_before)
+        .execute(executor)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn get_hearing_tasks(pool: &PgPool, hearing_id: Uuid) -> Result<Vec<Task>> {
+    let tasks = sqlx::query_as::<_, Task>(
+        r#"
+        SELECT t.* FROM tasks t
+        JOIN hearing_tasks ht ON ht.task_id = t.id
+        WHERE ht.hearing_id = $1
+        ORDER BY t.due_date ASC NULLS LAST, t.created_at ASC
+        "#,
+    )
+    .bind(hearing_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(tasks)
+}
+
+/// Generated tasks that are still new or in progress.
+pub async fn get_open_hearing_task_links(
+    executor: impl PgExecutor<'_>,
+    hearing_id: Uuid,
+) -> Result<Vec<HearingTaskLink>> {
+    let links = sqlx::query_as::<_, HearingTaskLink>(
+        r#"
+        SELECT ht.task_id, ht.days_before
+        FROM hearing_tasks ht
+        JOIN tasks t ON t.id = ht.task_id
+        WHERE ht.hearing_id = $1 AND t.status IN ('חדשה', 'בטיפול')
+        "#,
+    )
+    .bind(hearing_id)
+    .fetch_all(executor)
+    .await?;
+
+    Ok(links)
+}
+
+/// The steps for a hearing type, including those for every type, earliest first.
+pub async fn get_preparation_steps_for(pool: &PgPool, hearing_type: &str) -> Result<Vec<HearingPreparationStep>> {
+    let steps = sqlx::query_as::<_, HearingPreparationStep>(
+        r#"
+        SELECT * FROM hearing_preparation_steps
+        WHERE hearing_type IS NULL OR hearing_type = $1
+        ORDER BY days_before DESC, title ASC
+        "#,
+    )
+    .bind(hearing_type)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(steps)
+}
+
+pub async fn get_all_preparation_steps(pool: &PgPool) -> Result<Vec<HearingPreparationStep>> {
+    let steps = sqlx::query_as::<_, HearingPreparationStep>(
+        "SELECT * FROM hearing_preparation_steps ORDER BY hearing_type NULLS FIRST, days_before DESC"
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(steps)
+}
+
+pub async fn create_preparation_step(pool: &PgPool, req: &CreatePreparationStepRequest) -> Result<HearingPreparationStep> {
+    let step = sqlx::query_as::<_, HearingPreparationStep>(
+        r#"
+        INSERT INTO hearing_preparation_steps (hearing_type, title, days_before, category, priority)
+        VALUES ($1, $2, $3, COALESCE($4, 'משפטית'), COALESCE($5, 'רגילה'))
+        RETURNING *
+        "#,
+    )
+    .bind(&req.hearing_type)
+    .bind(&req.title)
+    .bind(req.days_before)
+    .bind(&req.category)
+    .bind(&req.priority)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(step)
+}
+
+pub async fn delete_preparation_step(pool: &PgPool, id: Uuid) -> Result<bool> {
+    let result = sqlx::query("DELETE FROM hearing_preparation_steps WHERE id = $1")
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
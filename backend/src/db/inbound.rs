@@ -0,0 +1,61 @@
+use crate::models::{InboundEmail, NewInboundEmail};
+use anyhow::Result;
+use sqlx::{PgExecutor, PgPool};
+
+/// Logs a processed message. Returns `None` if a message with the same
+/// Message-ID was already logged, in which case the caller must roll back.
+pub async fn record_inbound_email(
+    executor: impl PgExecutor<'_>,
+    email: &NewInboundEmail,
+) -> Result<Option<InboundEmail>> {
+    let recorded = sqlx::query_as::<_, InboundEmail>(
+        r#"
+        INSERT INTO inbound_emails (
+            message_id, sender, subject, outcome, reason, task_id, comment_id, attachments_saved
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        ON CONFLICT (message_id) DO NOTHING
+        RETURNING *
+        "#,
+    )
+    .bind(&email.message_id)
+    .bind(&email.sender)
+    .bind(&email.subject)
+    .bind(email.outcome.as_str())
+    .bind(&email.reason)
+    .bind(email.task_id)
+    .bind(email.comment_id)
+    .bind(email.attachments_saved)
+    .fetch_optional(executor)
+    .await?;
+
+    Ok(recorded)
+}
+
+pub async fn get_inbound_email_by_message_id(pool: &PgPool, message_id: &str) -> Result<Option<InboundEmail>> {
+    let email = sqlx::query_as::<_, InboundEmail>(
+        "SELECT * FROM inbound_emails WHERE message_id = $1"
+    )
+    .bind(message_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(email)
+}
+
+pub async fn get_inbound_emails(pool: &PgPool, outcome: Option<&str>, limit: i64) -> Result<Vec<InboundEmail>> {
+    let emails = sqlx::query_as::<_, InboundEmail>(
+        r#"
+        SELECT * FROM inbound_emails
+        WHERE ($1::VARCHAR IS NULL OR outcome = $1)
+        ORDER BY received_at DESC
+        LIMIT $2
+        "#,
+    )
+    .bind(outcome)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(emails)
+}
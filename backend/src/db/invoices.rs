@@ -0,0 +1,237 @@
+use crate::models::{
+    CreateInvoiceRequest, Invoice, InvoiceLine, InvoiceableEntry, InvoicesQuery, NewInvoiceLine,
+};
+use anyhow::Result;
+use sqlx::{PgExecutor, PgPool};
+use uuid::Uuid;
+
+/// Finished, billable, uninvoiced entries on the client's matters (or the
+/// one matter) in the period, locked until the transaction ends so that two
+/// drafts cannot take the same entry.
+pub async fn lock_invoiceable_entries(
+    executor: impl PgExecutor<'_>,
+    client_id: Uuid,
+    req: &CreateInvoiceRequest,
+) -> Result<Vec<InvoiceableEntry>> {
+    let entries = sqlx::query_as::<_, InvoiceableEntry>(
+        r#"
+        SELECT e.*, m.matter_number, t.title as task_title, t.task_id as task_code
+        FROM time_entries e
+        JOIN matters m ON m.id = e.matter_id
+        LEFT JOIN tasks t ON t.id = e.task_id
+        WHERE m.client_id = $1
+          AND ($2::UUID IS NULL OR e.matter_id = $2)
+          AND e.entry_date BETWEEN $3 AND $4
+          AND e.billable
+          AND e.duration_minutes IS NOT NULL
+          AND e.invoice_id IS NULL
+        ORDER BY m.matter_number ASC, t.title ASC NULLS FIRST, e.entry_date ASC
+        FOR UPDATE OF e
+        "#,
+    )
+    .bind(client_id)
+    .bind(req.matter_id)
+    .bind(req.period_from)
+    .bind(req.period_to)
+    .fetch_all(executor)
+    .await?;
+
+    Ok(entries)
+}
+
+pub async fn create_invoice(
+    executor: impl PgExecutor<'_>,
+    client_id: Uuid,
+    req: &CreateInvoiceRequest,
+    subtotal_agorot: i64,
+    vat_rate_basis_points: i32,
+    vat_agorot: i64,
+    created_by: Uuid,
+) -> Result<Invoice> {
+    let invoice = sqlx::query_as::<_, Invoice>(
+        r#"
+        INSERT INTO invoices (
+            client_id, matter_id, period_from, period_to, subtotal_agorot,
+            vat_rate_basis_points, vat_agorot, total_agorot, notes, created_by
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $5 + $7, $8, $9)
+        RETURNING *
+        "#,
+    )
+    .bind(client_id)
+    .bind(req.matter_id)
+    .bind(req.period_from)
+    .bind(req.period_to)
+    .bind(subtotal_agorot)
+    .bind(vat_rate_basis_points)
+    .bind(vat_agorot)
+    .bind(&req.notes)
+    .bind(created_by)
+    .fetch_one(executor)
+    .await?;
+
+    Ok(invoice)
+}
+
+pub async fn create_invoice_line(
+    executor: impl PgExecutor<'_>,
+    invoice_id: Uuid,
+    position: i32,
+    line: &NewInvoiceLine,
+) -> Result<InvoiceLine> {
+    let line = sqlx::query_as::<_, InvoiceLine>(
+        r#"
+        INSERT INTO invoice_lines (invoice_id, position, description, entries, minutes, hourly_rate_agorot, amount_agorot)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING *
+        "#,
+    )
+    .bind(invoice_id)
+    .bind(position)
+    .bind(&line.description)
+    .bind(line.entries)
+    .bind(line.minutes)
+    .bind(line.hourly_rate_agorot)
+    .bind(line.amount_agorot)
+    .fetch_one(executor)
+    .await?;
+
+    Ok(line)
+}
+
+/// Puts the entries on the invoice, which locks them.
+pub async fn assign_time_entries_to_invoice(
+    executor: impl PgExecutor<'_>,
+    invoice_id: Uuid,
+    entry_ids: &[Uuid],
+) -> Result<()> {
+    sqlx::query(
+        "UPDATE time_entries SET invoice_id = $1, invoiced_at = CURRENT_TIMESTAMP WHERE id = ANY($2)"
+    )
+    .bind(invoice_id)
+    .bind(entry_ids)
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn get_invoices(pool: &PgPool, params: &InvoicesQuery) -> Result<Vec<Invoice>> {
+    let invoices = sqlx::query_as::<_, Invoice>(
+        r#"
+        SELECT * FROM invoices
+        WHERE ($1::UUID IS NULL OR client_id = $1)
+          AND ($2::UUID IS NULL OR matter_id = $2)
+          AND ($3::VARCHAR IS NULL OR status = $3)
+          AND ($4::INTEGER IS NULL OR invoice_year = $4)
+        ORDER BY invoice_year DESC NULLS FIRST, sequence_number DESC, created_at DESC
+        "#,
+    )
+    .bind(params.client_id)
+    .bind(params.matter_id)
+    .bind(&params.status)
+    .bind(params.year)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(invoices)
+}
+
+pub async fn get_invoice_by_id(pool: &PgPool, id: Uuid) -> Result<Option<Invoice>> {
+    let invoice = sqlx::query_as::<_, Invoice>(
+        "SELECT * FROM invoices WHERE id = $1"
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(invoice)
+}
+
+/// The invoice, locked until the transaction ends.
+pub async fn get_invoice_for_update(executor: impl PgExecutor<'_>, id: Uuid) -> Result<Option<Invoice>> {
+    let invoice = sqlx::query_as::<_, Invoice>(
+        "SELECT * FROM invoices WHERE id = $1 FOR UPDATE"
+    )
+    .bind(id)
+    .fetch_optional(executor)
+    .await?;
+
+    Ok(invoice)
+}
+
+pub async fn get_invoice_lines(pool: &PgPool, invoice_id: Uuid) -> Result<Vec<InvoiceLine>> {
+    let lines = sqlx::query_as::<_, InvoiceLine>(
+        "SELECT * FROM invoice_lines WHERE invoice_id = $1 ORDER BY position ASC"
+    )
+    .bind(invoice_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(lines)
+}
+
+/// Takes the next number in the year. The counter row stays locked until
+/// the transaction ends, so numbers are issued in order and without gaps.
+pub async fn next_invoice_number(executor: impl PgExecutor<'_>, year: i32) -> Result<i32> {
+    let number: i32 = sqlx::query_scalar(
+        r#"
+        INSERT INTO invoice_number_sequences (invoice_year, last_number)
+        VALUES ($1, 1)
+        ON CONFLICT (invoice_year) DO UPDATE SET last_number = invoice_number_sequences.last_number + 1
+        RETURNING last_number
+        "#,
+    )
+    .bind(year)
+    .fetch_one(executor)
+    .await?;
+
+    Ok(number)
+}
+
+pub async fn issue_invoice(
+    executor: impl PgExecutor<'_>,
+    id: Uuid,
+    year: i32,
+    sequence_number: i32,
+) -> Result<Invoice> {
+    let invoice = sqlx::query_as::<_, Invoice>(
+        r#"
+        UPDATE invoices
+        SET status = 'issued', invoice_year = $1, sequence_number = $2, issued_at = CURRENT_TIMESTAMP
+        WHERE id = $3 AND status = 'draft'
+        RETURNING *
+        "#,
+    )
+    .bind(year)
+    .bind(sequence_number)
+    .bind(id)
+    .fetch_one(executor)
+    .await?;
+
+    Ok(invoice)
+}
+
+/// Deletes a draft and releases its entries. Issued invoices are kept.
+pub async fn delete_draft_invoice(pool: &PgPool, id: Uuid) -> Result<bool> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query(
+        r#"
+        UPDATE time_entries SET invoice_id = NULL, invoiced_at = NULL
+        WHERE invoice_id = $1 AND EXISTS (SELECT 1 FROM invoices WHERE id = $1 AND status = 'draft')
+        "#,
+    )
+    .bind(id)
+    .execute(&mut *tx)
+    .await?;
+
+    let result = sqlx::query("DELETE FROM invoices WHERE id = $1 AND status = 'draft'")
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    Ok(result.rows_affected() > 0)
+}
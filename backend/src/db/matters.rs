@@ -0,0 +1,211 @@
+use crate::models::{
+    CreateMatterPartyRequest, CreateMatterRequest, Matter, MatterParty, MatterStats, MattersQuery,
+    UpdateMatterRequest,
+};
+use anyhow::Result;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+pub async fn create_matter(pool: &PgPool, req: &CreateMatterRequest, created_by: Uuid) -> Result<Matter> {
+    let matter = sqlx::query_as::<_, Matter>(
+        r#"
+        INSERT INTO matters (
+            matter_number, title, client_id, opposing_party, court, case_number,
+            responsible_lawyer_id, description, opened_at, created_by
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, COALESCE($9, CURRENT_DATE), $10)
+        RETURNING *
+        "#,
+    )
+    .bind(&req.matter_number)
+    .bind(&req.title)
+    .bind(req.client_id)
+    .bind(&req.opposing_party)
+    .bind(&req.court)
+    .bind(&req.case_number)
+    .bind(req.responsible_lawyer_id)
+    .bind(&req.description)
+    .bind(req.opened_at)
+    .bind(created_by)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(matter)
+}
+
+pub async fn get_matters(pool: &PgPool, params: &MattersQuery) -> Result<Vec<Matter>> {
+    let pattern = params.q.as_deref().map(|q| format!("%{}%", q.trim()));
+
+    let matters = sqlx::query_as::<_, Matter>(
+        r#"
+        SELECT * FROM matters
+        WHERE ($1::UUID IS NULL OR client_id = $1)
+          AND ($2::VARCHAR IS NULL OR status = $2)
+          AND ($3::UUID IS NULL OR responsible_lawyer_id = $3)
+          AND ($4::TEXT IS NULL
+               OR matter_number ILIKE $4 OR title ILIKE $4
+               OR case_number ILIKE $4 OR opposing_party ILIKE $4)
+        ORDER BY opened_at DESC, matter_number DESC
+        "#,
+    )
+    .bind(params.client_id)
+    .bind(&params.status)
+    .bind(params.responsible_lawyer_id)
+    .bind(pattern)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(matters)
+}
+
+pub async fn get_matter_by_id(pool: &PgPool, id: Uuid) -> Result<Option<Matter>> {
+    let matter = sqlx::query_as::<_, Matter>(
+        "SELECT * FROM matters WHERE id = $1"
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(matter)
+}
+
+pub async fn get_matter_by_number(pool: &PgPool, matter_number: &str) -> Result<Option<Matter>> {
+    let matter = sqlx::query_as::<_, Matter>(
+        "SELECT * FROM matters WHERE matter_number = $1"
+    )
+    .bind(matter_number)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(matter)
+}
+
+/// Closing stamps `closed_at`; reopening clears it.
+pub async fn update_matter(pool: &PgPool, id: Uuid, req: &UpdateMatterRequest) -> Result<Option<Matter>> {
+    let matter = sqlx::query_as::<_, Matter>(
+        r#"
+        UPDATE matters
+        SET
+            matter_number = COALESCE($1, matter_number),
+            title = COALESCE($2, title),
+            client_id = COALESCE($3, client_id),
+            opposing_party = COALESCE($4, opposing_party),
+            court = COALESCE($5, court),
+            case_number = COALESCE($6, case_number),
+            responsible_lawyer_id = COALESCE($7, responsible_lawyer_id),
+            description = COALESCE($8, description),
+            opened_at = COALESCE($9, opened_at),
+            status = COALESCE($10, status),
+            closed_at = CASE
+                WHEN $10 = 'closed' AND status <> 'closed' THEN CURRENT_TIMESTAMP
+                WHEN $10 = 'open' THEN NULL
+                ELSE closed_at
+            END
+        WHERE id = $11
+        RETURNING *
+        "#,
+    )
+    .bind(&req.matter_number)
+    .bind(&req.title)
+    .bind(req.client_id)
+    .bind(&req.opposing_party)
+    .bind(&req.court)
+    .bind(&req.case_number)
+    .bind(req.responsible_lawyer_id)
+    .bind(&req.description)
+    .bind(req.opened_at)
+    .bind(&req.status)
+    .bind(id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(matter)
+}
+
+/// Tasks filed under the matter are kept, without a matter.
+pub async fn delete_matter(pool: &PgPool, id: Uuid) -> Result<bool> {
+    let result = sqlx::query("DELETE FROM matters WHERE id = $1")
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn count_matters_by_client(pool: &PgPool, client_id: Uuid) -> Result<i64> {
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM matters WHERE client_id = $1")
+        .bind(client_id)
+        .fetch_one(pool)
+        .await?;
+
+    Ok(count)
+}
+
+pub async fn get_matter_stats(pool: &PgPool, matter_id: Uuid) -> Result<MatterStats> {
+    let stats = sqlx::query_as::<_, MatterStats>(
+        r#"
+        SELECT
+            $1 as matter_id,
+            COUNT(*) as total,
+            COALESCE(SUM(CASE WHEN status = 'חדשה' THEN 1 ELSE 0 END), 0) as new,
+            COALESCE(SUM(CASE WHEN status = 'בטיפול' THEN 1 ELSE 0 END), 0) as in_progress,
+            COALESCE(SUM(CASE WHEN status = 'הושלמה' THEN 1 ELSE 0 END), 0) as completed,
+            COALESCE(SUM(CASE WHEN status = 'בוטלה' THEN 1 ELSE 0 END), 0) as cancelled,
+            COALESCE(SUM(CASE WHEN status IN ('חדשה', 'בטיפול') AND due_date < CURRENT_DATE THEN 1 ELSE 0 END), 0) as overdue,
+            MIN(due_date) FILTER (WHERE status IN ('חדשה', 'בטיפול')) as next_due_date
+        FROM tasks
+        WHERE matter_id = $1
+        "#,
+    )
+    .bind(matter_id)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(stats)
+}
+
+pub async fn create_matter_party(
+    pool: &PgPool,
+    matter_id: Uuid,
+    req: &CreateMatterPartyRequest,
+    created_by: Uuid,
+) -> Result<MatterParty> {
+    let party = sqlx::query_as::<_, MatterParty>(
+        r#"
+        INSERT INTO matter_parties (matter_id, name, id_number, role, notes, created_by)
+        VALUES ($1, $2, $3, COALESCE($4, 'related'), $5, $6)
+        RETURNING *
+        "#,
+    )
+    .bind(matter_id)
+    .bind(&req.name)
+    .bind(&req.id_number)
+    .bind(&req.role)
+    .bind(&req.notes)
+    .bind(created_by)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(party)
+}
+
+pub async fn get_matter_parties(pool: &PgPool, matter_id: Uuid) -> Result<Vec<MatterParty>> {
+    let parties = sqlx::query_as::<_, MatterParty>(
+        "SELECT * FROM matter_parties WHERE matter_id = $1 ORDER BY role, name"
+    )
+    .bind(matter_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(parties)
+}
+
+pub async fn delete_matter_party(pool: &PgPool, matter_id: Uuid, id: Uuid) -> Result<bool> {
+    let result = sqlx::query("DELETE FROM matter_parties WHERE id = $1 AND matter_id = $2")
+        .bind(id)
+        .bind(matter_id)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
@@ -1,7 +1,55 @@
 pub mod tasks;
 pub mod users;
 pub mod audit;
+pub mod comments;
+pub mod checklist;
+pub mod dependencies;
+pub mod recurring;
+pub mod templates;
+pub mod outbox;
+pub mod notifications;
+pub mod reminders;
+pub mod push;
+pub mod events;
+pub mod webhooks;
+pub mod attachments;
+pub mod inbound;
+pub mod clients;
+pub mod matters;
+pub mod deadlines;
+pub mod hearings;
+pub mod calendar;
+pub mod time_entries;
+pub mod invoices;
+pub mod conflicts;
+pub mod contacts;
+pub mod documents;
+pub mod expiries;
 
 pub use tasks::*;
 pub use users::*;
 pub use audit::*;
+pub use comments::*;
+pub use checklist::*;
+pub use dependencies::*;
+pub use recurring::*;
+pub use templates::*;
+pub use outbox::*;
+pub use notifications::*;
+pub use reminders::*;
+pub use push::*;
+pub use events::*;
+pub use webhooks::*;
+pub use attachments::*;
+pub use inbound::*;
+pub use clients::*;
+pub use matters::*;
+pub use deadlines::*;
+pub use hearings::*;
+pub use calendar::*;
+pub use time_entries::*;
+pub use invoices::*;
+pub use conflicts::*;
+pub use contacts::*;
+pub use documents::*;
+pub use expiries::*;
@@ -0,0 +1,274 @@
+use crate::models::{
+    DigestItem, EventPreferenceRequest, InAppNotification, NotificationEvent,
+    NotificationPreference, NotificationSettings, UpdateNotificationPreferencesRequest,
+};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::{PgExecutor, PgPool};
+use uuid::Uuid;
+
+pub async fn get_notification_settings(pool: &PgPool, user_id: Uuid) -> Result<Option<NotificationSettings>> {
+    let settings = sqlx::query_as::<_, NotificationSettings>(
+        "SELECT * FROM notification_settings WHERE user_id = $1"
+    )
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(settings)
+}
+
+pub async fn upsert_notification_settings(
+    executor: impl PgExecutor<'_>,
+    user_id: Uuid,
+    req: &UpdateNotificationPreferencesRequest,
+    timezone: &str,
+) -> Result<NotificationSettings> {
+    let settings = sqlx::query_as::<_, NotificationSettings>(
+        r#"
+        INSERT INTO notification_settings (
+            user_id, timezone, quiet_hours_start, quiet_hours_end, digest_time,
+            task_digest, digest_weekday
+        )
+        VALUES ($1, $2, $3, $4, COALESCE($5, '08:00'::TIME), COALESCE($6, 'off'), COALESCE($7, 0))
+        ON CONFLICT (user_id) DO UPDATE
+        SET timezone = EXCLUDED.timezone,
+            quiet_hours_start = EXCLUDED.quiet_hours_start,
+            quiet_hours_end = EXCLUDED.quiet_hours_end,
+            digest_time = EXCLUDED.digest_time,
+            task_digest = EXCLUDED.task_digest,
+            digest_weekday = EXCLUDED.digest_weekday
+        RETURNING *
+        "#,
+    )
+    .bind(user_id)
+    .bind(timezone)
+    .bind(req.quiet_hours_start)
+    .bind(req.quiet_hours_end)
+    .bind(req.digest_time)
+    .bind(&req.task_digest)
+    .bind(req.digest_weekday)
+    .fetch_one(executor)
+    .await?;
+
+    Ok(settings)
+}
+
+pub async fn get_notification_preferences(pool: &PgPool, user_id: Uuid) -> Result<Vec<NotificationPreference>> {
+    let preferences = sqlx::query_as::<_, NotificationPreference>(
+        "SELECT * FROM notification_preferences WHERE user_id = $1"
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(preferences)
+}
+
+pub async fn get_notification_preference(
+    pool: &PgPool,
+    user_id: Uuid,
+    event: NotificationEvent,
+) -> Result<Option<NotificationPreference>> {
+    let preference = sqlx::query_as::<_, NotificationPreference>(
+        "SELECT * FROM notification_preferences WHERE user_id = $1 AND event_type = $2"
+    )
+    .bind(user_id)
+    .bind(event.as_str())
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(preference)
+}
+
+pub async fn upsert_notification_preference(
+    executor: impl PgExecutor<'_>,
+    user_id: Uuid,
+    req: &EventPreferenceRequest,
+) -> Result<NotificationPreference> {
+    let preference = sqlx::query_as::<_, NotificationPreference>(
+        r#"
+        INSERT INTO notification_preferences (
+            user_id, event_type, email_enabled, push_enabled, in_app_enabled, min_priority, digest
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        ON CONFLICT (user_id, event_type) DO UPDATE
+        SET email_enabled = EXCLUDED.email_enabled,
+            push_enabled = EXCLUDED.push_enabled,
+            in_app_enabled = EXCLUDED.in_app_enabled,
+            min_priority = EXCLUDED.min_priority,
+            digest = EXCLUDED.digest
+        RETURNING *
+        "#,
+    )
+    .bind(user_id)
+    .bind(req.event_type.as_str())
+    .bind(req.email_enabled)
+    .bind(req.push_enabled)
+    .bind(req.in_app_enabled)
+    .bind(&req.min_priority)
+    .bind(req.digest)
+    .fetch_one(executor)
+    .await?;
+
+    Ok(preference)
+}
+
+pub async fn create_in_app_notification(
+    executor: impl PgExecutor<'_>,
+    user_id: Uuid,
+    event: NotificationEvent,
+    title: &str,
+    task_id: Option<Uuid>,
+) -> Result<InAppNotification> {
+    let notification = sqlx::query_as::<_, InAppNotification>(
+        r#"
+        INSERT INTO notifications (user_id, event_type, title, task_id)
+        VALUES ($1, $2, $3, $4)
+        RETURNING *
+        "#,
+    )
+    .bind(user_id)
+    .bind(event.as_str())
+    .bind(title)
+    .bind(task_id)
+    .fetch_one(executor)
+    .await?;
+
+    Ok(notification)
+}
+
+pub async fn get_in_app_notifications(
+    pool: &PgPool,
+    user_id: Uuid,
+    unread_only: bool,
+    limit: i64,
+) -> Result<Vec<InAppNotification>> {
+    let notifications = sqlx::query_as::<_, InAppNotification>(
+        r#"
+        SELECT * FROM notifications
+        WHERE user_id = $1 AND (NOT $2 OR read_at IS NULL)
+        ORDER BY created_at DESC
+        LIMIT $3
+        "#,
+    )
+    .bind(user_id)
+    .bind(unread_only)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(notifications)
+}
+
+pub async fn mark_notification_read(pool: &PgPool, user_id: Uuid, id: Uuid) -> Result<Option<InAppNotification>> {
+    let notification = sqlx::query_as::<_, InAppNotification>(
+        r#"
+        UPDATE notifications
+        SET read_at = COALESCE(read_at, CURRENT_TIMESTAMP)
+        WHERE id = $1 AND user_id = $2
+        RETURNING *
+        "#,
+    )
+    .bind(id)
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(notification)
+}
+
+pub async fn mark_all_notifications_read(pool: &PgPool, user_id: Uuid) -> Result<u64> {
+    let result = sqlx::query(
+        "UPDATE notifications SET read_at = CURRENT_TIMESTAMP WHERE user_id = $1 AND read_at IS NULL"
+    )
+    .bind(user_id)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+pub async fn add_digest_item(
+    executor: impl PgExecutor<'_>,
+    user_id: Uuid,
+    event: NotificationEvent,
+    summary: &str,
+    task_id: Option<Uuid>,
+) -> Result<DigestItem> {
+    let item = sqlx::query_as::<_, DigestItem>(
+        r#"
+        INSERT INTO notification_digest_items (user_id, event_type, summary, task_id)
+        VALUES ($1, $2, $3, $4)
+        RETURNING *
+        "#,
+    )
+    .bind(user_id)
+    .bind(event.as_str())
+    .bind(summary)
+    .bind(task_id)
+    .fetch_one(executor)
+    .await?;
+
+    Ok(item)
+}
+
+/// Users who may be due a digest: those with batched notifications waiting
+/// and those subscribed to the task digest.
+pub async fn get_digest_recipients(pool: &PgPool) -> Result<Vec<Uuid>> {
+    let user_ids = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        SELECT user_id FROM notification_digest_items WHERE sent_at IS NULL
+        UNION
+        SELECT user_id FROM notification_settings WHERE task_digest <> 'off'
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(user_ids)
+}
+
+/// Marks the user's pending digest items as sent and returns them. Run it in
+/// the transaction that queues the digest email.
+pub async fn take_pending_digest_items(executor: impl PgExecutor<'_>, user_id: Uuid) -> Result<Vec<DigestItem>> {
+    let items = sqlx::query_as::<_, DigestItem>(
+        r#"
+        UPDATE notification_digest_items
+        SET sent_at = CURRENT_TIMESTAMP
+        WHERE user_id = $1 AND sent_at IS NULL
+        RETURNING *
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(executor)
+    .await?;
+
+    Ok(items)
+}
+
+/// Records that the user's digest is going out, unless another instance got
+/// there first: `last_sent` is when the caller saw the previous digest go
+/// out, and the claim fails if that has changed since. Run it in the
+/// transaction that queues the digest email.
+pub async fn claim_digest(
+    executor: impl PgExecutor<'_>,
+    user_id: Uuid,
+    last_sent: Option<DateTime<Utc>>,
+) -> Result<bool> {
+    let claimed = sqlx::query(
+        r#"
+        INSERT INTO notification_settings (user_id, last_digest_sent_at)
+        VALUES ($1, CURRENT_TIMESTAMP)
+        ON CONFLICT (user_id) DO UPDATE SET last_digest_sent_at = CURRENT_TIMESTAMP
+        WHERE notification_settings.last_digest_sent_at IS NOT DISTINCT FROM $2
+        RETURNING user_id
+        "#,
+    )
+    .bind(user_id)
+    .bind(last_sent)
+    .fetch_optional(executor)
+    .await?;
+
+    Ok(claimed.is_some())
+}
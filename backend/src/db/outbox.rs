@@ -0,0 +1,133 @@
+use crate::models::{NewEmail, OutboxEmail};
+use anyhow::Result;
+use sqlx::{PgExecutor, PgPool};
+use uuid::Uuid;
+
+/// Queues an email. Pass the transaction of the change that triggered it so
+/// the message is only sent if that change commits.
+pub async fn enqueue_email(executor: impl PgExecutor<'_>, email: &NewEmail) -> Result<OutboxEmail> {
+    let queued = sqlx::query_as::<_, OutboxEmail>(
+        r#"
+        INSERT INTO email_outbox (
+            recipient, subject, html_body, text_body, kind, task_id, next_attempt_at, calendar_invite
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, COALESCE($7, CURRENT_TIMESTAMP), $8)
+        RETURNING *
+        "#,
+    )
+    .bind(&email.recipient)
+    .bind(&email.subject)
+    .bind(&email.html_body)
+    .bind(&email.text_body)
+    .bind(&email.kind)
+    .bind(email.task_id)
+    .bind(email.send_after)
+    .bind(&email.calendar_invite)
+    .fetch_one(executor)
+    .await?;
+
+    Ok(queued)
+}
+
+/// Claims up to `limit` messages that are due, including ones whose previous
+/// claim expired (e.g. the instance sending them crashed). `SKIP LOCKED`
+/// keeps several workers from claiming the same message.
+pub async fn claim_due_emails(pool: &PgPool, limit: i64, lock_seconds: i64) -> Result<Vec<OutboxEmail>> {
+    let emails = sqlx::query_as::<_, OutboxEmail>(
+        r#"
+        UPDATE email_outbox
+        SET status = 'sending',
+            attempts = attempts + 1,
+            locked_until = CURRENT_TIMESTAMP + make_interval(secs => $2)
+        WHERE id IN (
+            SELECT id FROM email_outbox
+            WHERE (status = 'pending' AND next_attempt_at <= CURRENT_TIMESTAMP)
+               OR (status = 'sending' AND locked_until < CURRENT_TIMESTAMP)
+            ORDER BY next_attempt_at ASC
+            LIMIT $1
+            FOR UPDATE SKIP LOCKED
+        )
+        RETURNING *
+        "#,
+    )
+    .bind(limit)
+    .bind(lock_seconds as f64)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(emails)
+}
+
+pub async fn mark_email_sent(pool: &PgPool, id: Uuid, smtp_response: &str) -> Result<()> {
+    sqlx::query(
+        r#"
+        UPDATE email_outbox
+        SET status = 'sent', sent_at = CURRENT_TIMESTAMP, locked_until = NULL,
+            last_error = NULL, smtp_response = $1
+        WHERE id = $2
+        "#,
+    )
+    .bind(smtp_response)
+    .bind(id)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Schedules another attempt after `retry_in_seconds`, or dead-letters the
+/// message when `retry_in_seconds` is `None`.
+pub async fn mark_email_failed(
+    pool: &PgPool,
+    id: Uuid,
+    error: &str,
+    retry_in_seconds: Option<i64>,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        UPDATE email_outbox
+        SET status = CASE WHEN $1::BIGINT IS NULL THEN 'dead' ELSE 'pending' END,
+            next_attempt_at = CURRENT_TIMESTAMP + make_interval(secs => COALESCE($1, 0)::DOUBLE PRECISION),
+            locked_until = NULL,
+            last_error = $2
+        WHERE id = $3
+        "#,
+    )
+    .bind(retry_in_seconds)
+    .bind(error)
+    .bind(id)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn get_outbox_emails_by_status(pool: &PgPool, status: &str, limit: i64) -> Result<Vec<OutboxEmail>> {
+    let emails = sqlx::query_as::<_, OutboxEmail>(
+        "SELECT * FROM email_outbox WHERE status = $1 ORDER BY created_at DESC LIMIT $2"
+    )
+    .bind(status)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(emails)
+}
+
+/// Puts a dead-lettered message back in the queue with a fresh retry budget.
+pub async fn requeue_email(pool: &PgPool, id: Uuid) -> Result<Option<OutboxEmail>> {
+    let email = sqlx::query_as::<_, OutboxEmail>(
+        r#"
+        UPDATE email_outbox
+        SET status = 'pending', attempts = 0, next_attempt_at = CURRENT_TIMESTAMP,
+            locked_until = NULL
+        WHERE id = $1 AND status = 'dead'
+        RETURNING *
+        "#,
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(email)
+}
@@ -0,0 +1,172 @@
+use crate::models::{CreatePushSubscriptionRequest, NewPush, PushDelivery, PushSubscription};
+use anyhow::Result;
+use sqlx::{PgExecutor, PgPool};
+use uuid::Uuid;
+
+/// Stores a subscription. A browser re-subscribing with the same endpoint
+/// (possibly after a different user logged in) takes the row over.
+pub async fn upsert_push_subscription(
+    pool: &PgPool,
+    user_id: Uuid,
+    req: &CreatePushSubscriptionRequest,
+    user_agent: Option<&str>,
+) -> Result<PushSubscription> {
+    let subscription = sqlx::query_as::<_, PushSubscription>(
+        r#"
+        INSERT INTO push_subscriptions (user_id, endpoint, p256dh, auth, user_agent)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (endpoint) DO UPDATE
+        SET user_id = EXCLUDED.user_id,
+            p256dh = EXCLUDED.p256dh,
+            auth = EXCLUDED.auth,
+            user_agent = EXCLUDED.user_agent
+        RETURNING *
+        "#,
+    )
+    .bind(user_id)
+    .bind(&req.endpoint)
+    .bind(&req.keys.p256dh)
+    .bind(&req.keys.auth)
+    .bind(user_agent)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(subscription)
+}
+
+pub async fn get_push_subscriptions_by_user(pool: &PgPool, user_id: Uuid) -> Result<Vec<PushSubscription>> {
+    let subscriptions = sqlx::query_as::<_, PushSubscription>(
+        "SELECT * FROM push_subscriptions WHERE user_id = $1 ORDER BY created_at DESC"
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(subscriptions)
+}
+
+pub async fn get_push_subscription_by_id(pool: &PgPool, id: Uuid) -> Result<Option<PushSubscription>> {
+    let subscription = sqlx::query_as::<_, PushSubscription>(
+        "SELECT * FROM push_subscriptions WHERE id = $1"
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(subscription)
+}
+
+pub async fn delete_push_subscription(pool: &PgPool, user_id: Uuid, endpoint: &str) -> Result<bool> {
+    let result = sqlx::query("DELETE FROM push_subscriptions WHERE user_id = $1 AND endpoint = $2")
+        .bind(user_id)
+        .bind(endpoint)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Removes a subscription the push service reported as gone (404/410).
+pub async fn prune_push_subscription(pool: &PgPool, id: Uuid) -> Result<()> {
+    sqlx::query("DELETE FROM push_subscriptions WHERE id = $1")
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+/// Queues a push for every device of the user. Returns how many were queued.
+pub async fn enqueue_push(executor: impl PgExecutor<'_>, user_id: Uuid, push: &NewPush) -> Result<u64> {
+    let result = sqlx::query(
+        r#"
+        INSERT INTO push_deliveries (subscription_id, title, body, url, task_id, next_attempt_at)
+        SELECT id, $2, $3, $4, $5, COALESCE($6, CURRENT_TIMESTAMP)
+        FROM push_subscriptions
+        WHERE user_id = $1
+        "#,
+    )
+    .bind(user_id)
+    .bind(&push.title)
+    .bind(&push.body)
+    .bind(&push.url)
+    .bind(push.task_id)
+    .bind(push.send_after)
+    .execute(executor)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// Claims due deliveries; see `claim_due_emails` for the locking scheme.
+pub async fn claim_due_push_deliveries(pool: &PgPool, limit: i64, lock_seconds: i64) -> Result<Vec<PushDelivery>> {
+    let deliveries = sqlx::query_as::<_, PushDelivery>(
+        r#"
+        UPDATE push_deliveries
+        SET status = 'sending',
+            attempts = attempts + 1,
+            locked_until = CURRENT_TIMESTAMP + make_interval(secs => $2)
+        WHERE id IN (
+            SELECT id FROM push_deliveries
+            WHERE (status = 'pending' AND next_attempt_at <= CURRENT_TIMESTAMP)
+               OR (status = 'sending' AND locked_until < CURRENT_TIMESTAMP)
+            ORDER BY next_attempt_at ASC
+            LIMIT $1
+            FOR UPDATE SKIP LOCKED
+        )
+        RETURNING *
+        "#,
+    )
+    .bind(limit)
+    .bind(lock_seconds as f64)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(deliveries)
+}
+
+pub async fn mark_push_sent(pool: &PgPool, delivery: &PushDelivery) -> Result<()> {
+    sqlx::query(
+        r#"
+        UPDATE push_deliveries
+        SET status = 'sent', sent_at = CURRENT_TIMESTAMP, locked_until = NULL, last_error = NULL
+        WHERE id = $1
+        "#,
+    )
+    .bind(delivery.id)
+    .execute(pool)
+    .await?;
+
+    sqlx::query("UPDATE push_subscriptions SET last_success_at = CURRENT_TIMESTAMP WHERE id = $1")
+        .bind(delivery.subscription_id)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+/// Schedules a retry after `retry_in_seconds`, or gives up when `None`.
+pub async fn mark_push_failed(
+    pool: &PgPool,
+    id: Uuid,
+    error: &str,
+    retry_in_seconds: Option<i64>,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        UPDATE push_deliveries
+        SET status = CASE WHEN $1::BIGINT IS NULL THEN 'dead' ELSE 'pending' END,
+            next_attempt_at = CURRENT_TIMESTAMP + make_interval(secs => COALESCE($1, 0)::DOUBLE PRECISION),
+            locked_until = NULL,
+            last_error = $2
+        WHERE id = $3
+        "#,
+    )
+    .bind(retry_in_seconds)
+    .bind(error)
+    .bind(id)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,213 @@
+use crate::models::{CreateRecurringTaskRequest, RecurringTaskSeries, Task, UpdateRecurringTaskRequest};
+use anyhow::Result;
+use chrono::NaiveDate;
+use sqlx::{PgExecutor, PgPool};
+use uuid::Uuid;
+
+pub async fn create_recurring_series(
+    pool: &PgPool,
+    req: &CreateRecurringTaskRequest,
+    next_occurrence: Option<NaiveDate>,
+) -> Result<RecurringTaskSeries> {
+    let priority = req.priority.clone().unwrap_or_else(|| "רגילה".to_string());
+
+    let series = sqlx::query_as::<_, RecurringTaskSeries>(
+        r#"
+        INSERT INTO recurring_task_series (
+            title, description, category,
+            assigned_to, assigned_to_email,
+            created_by, created_by_email,
+            priority, notes,
+            rrule, start_date, lead_days, next_occurrence
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+        RETURNING *
+        "#,
+    )
+    .bind(&req.title)
+    .bind(&req.description)
+    .bind(&req.category)
+    .bind(&req.assigned_to)
+    .bind(&req.assigned_to_email)
+    .bind(&req.created_by)
+    .bind(&req.created_by_email)
+    .bind(priority)
+    .bind(&req.notes)
+    .bind(&req.rrule)
+    .bind(req.start_date)
+    .bind(req.lead_days.unwrap_or(0))
+    .bind(next_occurrence)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(series)
+}
+
+pub async fn get_all_recurring_series(pool: &PgPool) -> Result<Vec<RecurringTaskSeries>> {
+    let series = sqlx::query_as::<_, RecurringTaskSeries>(
+        "SELECT * FROM recurring_task_series WHERE is_active ORDER BY created_at DESC"
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(series)
+}
+
+pub async fn get_recurring_series_by_id(pool: &PgPool, id: Uuid) -> Result<Option<RecurringTaskSeries>> {
+    let series = sqlx::query_as::<_, RecurringTaskSeries>(
+        "SELECT * FROM recurring_task_series WHERE id = $1"
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(series)
+}
+
+/// Applies an "all future occurrences" edit to the series template.
+pub async fn update_recurring_series(
+    executor: impl PgExecutor<'_>,
+    id: Uuid,
+    req: &UpdateRecurringTaskRequest,
+    next_occurrence: Option<NaiveDate>,
+) -> Result<RecurringTaskSeries> {
+    let series = sqlx::query_as::<_, RecurringTaskSeries>(
+        r#"
+        UPDATE recurring_task_series
+        SET
+            title = COALESCE($1, title),
+            description = COALESCE($2, description),
+            category = COALESCE($3, category),
+            assigned_to = COALESCE($4, assigned_to),
+            assigned_to_email = COALESCE($5, assigned_to_email),
+            priority = COALESCE($6, priority),
+            notes = COALESCE($7, notes),
+            rrule = COALESCE($8, rrule),
+            lead_days = COALESCE($9, lead_days),
+            next_occurrence = $10
+        WHERE id = $11
+        RETURNING *
+        "#,
+    )
+    .bind(&req.title)
+    .bind(&req.description)
+    .bind(&req.category)
+    .bind(&req.assigned_to)
+    .bind(&req.assigned_to_email)
+    .bind(&req.priority)
+    .bind(&req.notes)
+    .bind(&req.rrule)
+    .bind(req.lead_days)
+    .bind(next_occurrence)
+    .bind(id)
+    .fetch_one(executor)
+    .await?;
+
+    Ok(series)
+}
+
+pub async fn deactivate_recurring_series(pool: &PgPool, id: Uuid) -> Result<bool> {
+    let result = sqlx::query(
+        "UPDATE recurring_task_series SET is_active = false, next_occurrence = NULL WHERE id = $1 AND is_active"
+    )
+    .bind(id)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Active series whose next occurrence is within its lead time of `today`.
+pub async fn get_due_recurring_series(pool: &PgPool, today: NaiveDate) -> Result<Vec<RecurringTaskSeries>> {
+    let series = sqlx::query_as::<_, RecurringTaskSeries>(
+        r#"
+        SELECT * FROM recurring_task_series
+        WHERE is_active
+          AND next_occurrence IS NOT NULL
+          AND next_occurrence - lead_days <= $1
+        ORDER BY next_occurrence ASC
+        "#,
+    )
+    .bind(today)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(series)
+}
+
+/// Moves the series from `current` to `next`. Returns false when another
+/// scheduler instance already advanced it.
+pub async fn advance_recurring_series(
+    pool: &PgPool,
+    id: Uuid,
+    current: NaiveDate,
+    next: Option<NaiveDate>,
+    created: bool,
+) -> Result<bool> {
+    let result = sqlx::query(
+        r#"
+        UPDATE recurring_task_series
+        SET next_occurrence = $1,
+            occurrences_created = occurrences_created + CASE WHEN $2 THEN 1 ELSE 0 END
+        WHERE id = $3 AND next_occurrence = $4
+        "#,
+    )
+    .bind(next)
+    .bind(created)
+    .bind(id)
+    .bind(current)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn get_series_occurrence_task(
+    pool: &PgPool,
+    series_id: Uuid,
+    occurrence_date: NaiveDate,
+) -> Result<Option<Task>> {
+    let task = sqlx::query_as::<_, Task>(
+        "SELECT * FROM tasks WHERE series_id = $1 AND occurrence_date = $2"
+    )
+    .bind(series_id)
+    .bind(occurrence_date)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(task)
+}
+
+/// Materialized occurrences on or after `from` that are still open.
+pub async fn get_open_series_tasks_from(
+    pool: &PgPool,
+    series_id: Uuid,
+    from: NaiveDate,
+) -> Result<Vec<Task>> {
+    let tasks = sqlx::query_as::<_, Task>(
+        r#"
+        SELECT * FROM tasks
+        WHERE series_id = $1
+          AND occurrence_date >= $2
+          AND status IN ('חדשה', 'בטיפול')
+        ORDER BY occurrence_date ASC
+        "#,
+    )
+    .bind(series_id)
+    .bind(from)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(tasks)
+}
+
+pub async fn get_series_tasks(pool: &PgPool, series_id: Uuid) -> Result<Vec<Task>> {
+    let tasks = sqlx::query_as::<_, Task>(
+        "SELECT * FROM tasks WHERE series_id = $1 ORDER BY occurrence_date DESC"
+    )
+    .bind(series_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(tasks)
+}
@@ -0,0 +1,56 @@
+use anyhow::Result;
+use chrono::NaiveDate;
+use sqlx::PgExecutor;
+use uuid::Uuid;
+
+/// Claims a reminder for sending. Returns `false` if it was already sent
+/// (or is being sent by another instance), in which case the caller must
+/// not send it. Run it in the transaction that queues the email.
+pub async fn record_task_reminder(
+    executor: impl PgExecutor<'_>,
+    task_id: Uuid,
+    due_date: NaiveDate,
+    reminder_key: &str,
+    recipient: &str,
+) -> Result<bool> {
+    let result = sqlx::query(
+        r#"
+        INSERT INTO task_reminders (task_id, due_date, reminder_key, recipient)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (task_id, due_date, reminder_key) DO NOTHING
+        "#,
+    )
+    .bind(task_id)
+    .bind(due_date)
+    .bind(reminder_key)
+    .bind(recipient)
+    .execute(executor)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Like [`record_task_reminder`], for reminders of a matter's expiry date.
+pub async fn record_expiry_reminder(
+    executor: impl PgExecutor<'_>,
+    expiry_id: Uuid,
+    expires_on: NaiveDate,
+    reminder_key: &str,
+    recipient: &str,
+) -> Result<bool> {
+    let result = sqlx::query(
+        r#"
+        INSERT INTO matter_expiry_reminders (expiry_id, expires_on, reminder_key, recipient)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (expiry_id, expires_on, reminder_key) DO NOTHING
+        "#,
+    )
+    .bind(expiry_id)
+    .bind(expires_on)
+    .bind(reminder_key)
+    .bind(recipient)
+    .execute(executor)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
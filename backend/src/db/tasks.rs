@@ -1,9 +1,10 @@
-use crate::models::{CreateTaskRequest, Task, UpdateTaskRequest};
+use crate::models::{CreateTaskRequest, Task, TaskProgress, UpdateTaskRequest};
 use anyhow::Result;
-use sqlx::PgPool;
+use chrono::NaiveDate;
+use sqlx::{PgExecutor, PgPool};
 use uuid::Uuid;
 
-pub async fn create_task(pool: &PgPool, req: &CreateTaskRequest, task_id: &str) -> Result<Task> {
+pub async fn create_task(executor: impl PgExecutor<'_>, req: &CreateTaskRequest, task_id: &str) -> Result<Task> {
     let priority = req.priority.clone().unwrap_or_else(|| "רגילה".to_string());
 
     let task = sqlx::query_as::<_, Task>(
@@ -13,9 +14,10 @@ pub async fn create_task(pool: &PgPool, req: &CreateTaskRequest, task_id: &str)
             assigned_to, assigned_to_email,
             created_by, created_by_email,
             due_date, priority, status,
-            attachments_folder_url, notes
+            attachments_folder_url, notes, parent_id,
+            series_id, occurrence_date, matter_id
         )
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, 'חדשה', $11, $12)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, 'חדשה', $11, $12, $13, $14, $15, $16)
         RETURNING *
         "#,
     )
@@ -31,7 +33,11 @@ pub async fn create_task(pool: &PgPool, req: &CreateTaskRequest, task_id: &str)
     .bind(priority)
     .bind(&req.attachments_folder_url)
     .bind(&req.notes)
-    .fetch_one(pool)
+    .bind(req.parent_id)
+    .bind(req.series_id)
+    .bind(req.occurrence_date)
+    .bind(req.matter_id)
+    .fetch_one(executor)
     .await?;
 
     Ok(task)
@@ -69,7 +75,7 @@ pub async fn get_task_by_task_id(pool: &PgPool, task_id: &str) -> Result<Option<
     Ok(task)
 }
 
-pub async fn update_task(pool: &PgPool, id: Uuid, req: &UpdateTaskRequest) -> Result<Task> {
+pub async fn update_task(executor: impl PgExecutor<'_>, id: Uuid, req: &UpdateTaskRequest) -> Result<Task> {
     let mut query = String::from("UPDATE tasks SET ");
     let mut bindings = Vec::new();
     let mut param_count = 1;
@@ -122,6 +128,13 @@ pub async fn update_task(pool: &PgPool, id: Uuid, req: &UpdateTaskRequest) -> Re
         param_count += 1;
     }
 
+    let matter_id = req.matter_id.map(|id| id.to_string());
+    if let Some(matter_id) = &matter_id {
+        query.push_str(&format!("matter_id = ${}::uuid, ", param_count));
+        bindings.push(matter_id.as_str());
+        param_count += 1;
+    }
+
     // Remove trailing comma and space
     query.truncate(query.len() - 2);
     query.push_str(&format!(" WHERE id = ${} RETURNING *", param_count));
@@ -132,15 +145,27 @@ pub async fn update_task(pool: &PgPool, id: Uuid, req: &UpdateTaskRequest) -> Re
     }
     sql_query = sql_query.bind(id);
 
-    let task = sql_query.fetch_one(pool).await?;
+    let task = sql_query.fetch_one(executor).await?;
+
+    Ok(task)
+}
+
+pub async fn set_task_due_date(executor: impl PgExecutor<'_>, id: Uuid, due_date: NaiveDate) -> Result<Task> {
+    let task = sqlx::query_as::<_, Task>(
+        "UPDATE tasks SET due_date = $1 WHERE id = $2 RETURNING *"
+    )
+    .bind(due_date)
+    .bind(id)
+    .fetch_one(executor)
+    .await?;
 
     Ok(task)
 }
 
-pub async fn delete_task(pool: &PgPool, id: Uuid) -> Result<bool> {
+pub async fn delete_task(executor: impl PgExecutor<'_>, id: Uuid) -> Result<bool> {
     let result = sqlx::query("DELETE FROM tasks WHERE id = $1")
         .bind(id)
-        .execute(pool)
+        .execute(executor)
         .await?;
 
     Ok(result.rows_affected() > 0)
@@ -167,3 +192,64 @@ pub async fn get_tasks_by_status(pool: &PgPool, status: &str) -> Result<Vec<Task
 
     Ok(tasks)
 }
+
+pub async fn get_subtasks(pool: &PgPool, parent_id: Uuid) -> Result<Vec<Task>> {
+    let tasks = sqlx::query_as::<_, Task>(
+        "SELECT * FROM tasks WHERE parent_id = $1 ORDER BY created_at ASC"
+    )
+    .bind(parent_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(tasks)
+}
+
+pub async fn get_tasks_by_matter(pool: &PgPool, matter_id: Uuid) -> Result<Vec<Task>> {
+    let tasks = sqlx::query_as::<_, Task>(
+        "SELECT * FROM tasks WHERE matter_id = $1 ORDER BY due_date ASC NULLS LAST, created_at DESC"
+    )
+    .bind(matter_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(tasks)
+}
+
+/// Subtask and checklist counts for each of the given tasks. Cancelled
+/// subtasks do not count towards the total.
+pub async fn get_task_progress(pool: &PgPool, ids: &[Uuid]) -> Result<Vec<TaskProgress>> {
+    let progress = sqlx::query_as::<_, TaskProgress>(
+        r#"
+        SELECT
+            t.id as task_id,
+            (SELECT COUNT(*) FROM tasks c WHERE c.parent_id = t.id AND c.status <> 'בוטלה') as subtasks_total,
+            (SELECT COUNT(*) FROM tasks c WHERE c.parent_id = t.id AND c.status = 'הושלמה') as subtasks_completed,
+            (SELECT COUNT(*) FROM tasks c WHERE c.parent_id = t.id AND c.status IN ('חדשה', 'בטיפול')) as subtasks_open,
+            (SELECT COUNT(*) FROM task_checklist_items i WHERE i.task_id = t.id) as checklist_total,
+            (SELECT COUNT(*) FROM task_checklist_items i WHERE i.task_id = t.id AND i.is_done) as checklist_completed
+        FROM tasks t
+        WHERE t.id = ANY($1)
+        "#,
+    )
+    .bind(ids)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(progress)
+}
+
+/// Open tasks due on or before `date`, including overdue ones.
+pub async fn get_open_tasks_due_by(pool: &PgPool, date: NaiveDate) -> Result<Vec<Task>> {
+    let tasks = sqlx::query_as::<_, Task>(
+        r#"
+        SELECT * FROM tasks
+        WHERE due_date <= $1 AND status IN ('חדשה', 'בטיפול')
+        ORDER BY due_date ASC
+        "#,
+    )
+    .bind(date)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(tasks)
+}
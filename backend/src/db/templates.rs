@@ -0,0 +1,123 @@
+use crate::models::{CreateTaskTemplateRequest, TaskTemplate, UpdateTaskTemplateRequest};
+use anyhow::Result;
+use sqlx::{types::Json, PgPool};
+use uuid::Uuid;
+
+pub async fn create_task_template(
+    pool: &PgPool,
+    req: &CreateTaskTemplateRequest,
+    created_by: Uuid,
+) -> Result<TaskTemplate> {
+    let priority = req.priority.clone().unwrap_or_else(|| "רגילה".to_string());
+
+    let template = sqlx::query_as::<_, TaskTemplate>(
+        r#"
+        INSERT INTO task_templates (
+            name, description, title, category, priority,
+            task_description, notes, due_offset_days,
+            checklist, subtasks, sequential_subtasks, created_by
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+        RETURNING *
+        "#,
+    )
+    .bind(&req.name)
+    .bind(&req.description)
+    .bind(&req.title)
+    .bind(&req.category)
+    .bind(priority)
+    .bind(&req.task_description)
+    .bind(&req.notes)
+    .bind(req.due_offset_days)
+    .bind(Json(&req.checklist))
+    .bind(Json(&req.subtasks))
+    .bind(req.sequential_subtasks)
+    .bind(created_by)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(template)
+}
+
+pub async fn get_all_task_templates(pool: &PgPool) -> Result<Vec<TaskTemplate>> {
+    let templates = sqlx::query_as::<_, TaskTemplate>(
+        "SELECT * FROM task_templates WHERE is_active ORDER BY name ASC"
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(templates)
+}
+
+pub async fn get_task_template_by_id(pool: &PgPool, id: Uuid) -> Result<Option<TaskTemplate>> {
+    let template = sqlx::query_as::<_, TaskTemplate>(
+        "SELECT * FROM task_templates WHERE id = $1 AND is_active"
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(template)
+}
+
+pub async fn get_task_template_by_name(pool: &PgPool, name: &str) -> Result<Option<TaskTemplate>> {
+    let template = sqlx::query_as::<_, TaskTemplate>(
+        "SELECT * FROM task_templates WHERE name = $1"
+    )
+    .bind(name)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(template)
+}
+
+pub async fn update_task_template(
+    pool: &PgPool,
+    id: Uuid,
+    req: &UpdateTaskTemplateRequest,
+) -> Result<Option<TaskTemplate>> {
+    let template = sqlx::query_as::<_, TaskTemplate>(
+        r#"
+        UPDATE task_templates
+        SET
+            name = COALESCE($1, name),
+            description = COALESCE($2, description),
+            title = COALESCE($3, title),
+            category = COALESCE($4, category),
+            priority = COALESCE($5, priority),
+            task_description = COALESCE($6, task_description),
+            notes = COALESCE($7, notes),
+            due_offset_days = COALESCE($8, due_offset_days),
+            checklist = COALESCE($9, checklist),
+            subtasks = COALESCE($10, subtasks),
+            sequential_subtasks = COALESCE($11, sequential_subtasks)
+        WHERE id = $12 AND is_active
+        RETURNING *
+        "#,
+    )
+    .bind(&req.name)
+    .bind(&req.description)
+    .bind(&req.title)
+    .bind(&req.category)
+    .bind(&req.priority)
+    .bind(&req.task_description)
+    .bind(&req.notes)
+    .bind(req.due_offset_days)
+    .bind(req.checklist.as_ref().map(Json))
+    .bind(req.subtasks.as_ref().map(Json))
+    .bind(req.sequential_subtasks)
+    .bind(id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(template)
+}
+
+pub async fn deactivate_task_template(pool: &PgPool, id: Uuid) -> Result<bool> {
+    let result = sqlx::query("UPDATE task_templates SET is_active = false WHERE id = $1 AND is_active")
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
@@ -0,0 +1,287 @@
+use crate::models::{
+    CreateTimeEntryRequest, StartTimerRequest, TaskTimeTotal, TimeEntriesQuery, TimeEntry,
+    TimeReportQuery, TimeReportRow, UpdateTimeEntryRequest, User,
+};
+use anyhow::Result;
+use chrono::NaiveDate;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Starts a timer now, dated in office time, at the user's hourly rate.
+pub async fn start_timer(
+    pool: &PgPool,
+    user_id: Uuid,
+    req: &StartTimerRequest,
+    matter_id: Option<Uuid>,
+) -> Result<TimeEntry> {
+    let entry = sqlx::query_as::<_, TimeEntry>(
+        r#"
+        INSERT INTO time_entries (user_id, task_id, matter_id, entry_date, started_at, billable, hourly_rate_agorot, narrative)
+        VALUES (
+            $1, $2, $3,
+            (CURRENT_TIMESTAMP AT TIME ZONE 'Asia/Jerusalem')::DATE,
+            CURRENT_TIMESTAMP,
+            COALESCE($4, true),
+            (SELECT hourly_rate_agorot FROM users WHERE id = $1),
+            $5
+        )
+        RETURNING *
+        "#,
+    )
+    .bind(user_id)
+    .bind(req.task_id)
+    .bind(matter_id)
+    .bind(req.billable)
+    .bind(&req.narrative)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(entry)
+}
+
+pub async fn get_running_timer(pool: &PgPool, user_id: Uuid) -> Result<Option<TimeEntry>> {
+    let entry = sqlx::query_as::<_, TimeEntry>(
+        "SELECT * FROM time_entries WHERE user_id = $1 AND duration_minutes IS NULL"
+    )
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(entry)
+}
+
+/// Stops a running timer, rounding up to whole minutes.
+pub async fn stop_timer(pool: &PgPool, id: Uuid, narrative: Option<&str>) -> Result<TimeEntry> {
+    let entry = sqlx::query_as::<_, TimeEntry>(
+        r#"
+        UPDATE time_entries
+        SET
+            ended_at = CURRENT_TIMESTAMP,
+            duration_minutes = GREATEST(1, CEIL(EXTRACT(EPOCH FROM CURRENT_TIMESTAMP - started_at) / 60))::INTEGER,
+            narrative = COALESCE($1, narrative)
+        WHERE id = $2 AND duration_minutes IS NULL
+        RETURNING *
+        "#,
+    )
+    .bind(narrative)
+    .bind(id)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(entry)
+}
+
+pub async fn create_time_entry(
+    pool: &PgPool,
+    user_id: Uuid,
+    req: &CreateTimeEntryRequest,
+    matter_id: Option<Uuid>,
+    entry_date: NaiveDate,
+    duration_minutes: i32,
+) -> Result<TimeEntry> {
+    let entry = sqlx::query_as::<_, TimeEntry>(
+        r#"
+        INSERT INTO time_entries (
+            user_id, task_id, matter_id, entry_date, started_at, ended_at,
+            duration_minutes, billable, hourly_rate_agorot, narrative
+        )
+        VALUES (
+            $1, $2, $3, $4, $5, $6, $7,
+            COALESCE($8, true),
+            COALESCE($9, (SELECT hourly_rate_agorot FROM users WHERE id = $1)),
+            $10
+        )
+        RETURNING *
+        "#,
+    )
+    .bind(user_id)
+    .bind(req.task_id)
+    .bind(matter_id)
+    .bind(entry_date)
+    .bind(req.started_at)
+    .bind(req.ended_at)
+    .bind(duration_minutes)
+    .bind(req.billable)
+    .bind(req.hourly_rate_agorot)
+    .bind(&req.narrative)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(entry)
+}
+
+pub async fn get_time_entries(pool: &PgPool, params: &TimeEntriesQuery) -> Result<Vec<TimeEntry>> {
+    let entries = sqlx::query_as::<_, TimeEntry>(
+        r#"
+        SELECT * FROM time_entries
+        WHERE ($1::UUID IS NULL OR user_id = $1)
+          AND ($2::UUID IS NULL OR task_id = $2)
+          AND ($3::UUID IS NULL OR matter_id = $3)
+          AND ($4::DATE IS NULL OR entry_date >= $4)
+          AND ($5::DATE IS NULL OR entry_date <= $5)
+          AND ($6::BOOLEAN IS NULL OR billable = $6)
+          AND (NOT $7 OR invoice_id IS NULL)
+        ORDER BY entry_date DESC, started_at DESC NULLS LAST, created_at DESC
+        "#,
+    )
+    .bind(params.user_id)
+    .bind(params.task_id)
+    .bind(params.matter_id)
+    .bind(params.from)
+    .bind(params.to)
+    .bind(params.billable)
+    .bind(params.uninvoiced)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(entries)
+}
+
+pub async fn get_time_entry_by_id(pool: &PgPool, id: Uuid) -> Result<Option<TimeEntry>> {
+    let entry = sqlx::query_as::<_, TimeEntry>(
+        "SELECT * FROM time_entries WHERE id = $1"
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(entry)
+}
+
+/// Invoiced entries are never changed.
+pub async fn update_time_entry(
+    pool: &PgPool,
+    id: Uuid,
+    req: &UpdateTimeEntryRequest,
+    matter_id: Option<Uuid>,
+) -> Result<Option<TimeEntry>> {
+    let entry = sqlx::query_as::<_, TimeEntry>(
+        r#"
+        UPDATE time_entries
+        SET
+            task_id = COALESCE($1, task_id),
+            matter_id = $2,
+            entry_date = COALESCE($3, entry_date),
+            duration_minutes = COALESCE($4, duration_minutes),
+            billable = COALESCE($5, billable),
+            hourly_rate_agorot = COALESCE($6, hourly_rate_agorot),
+            narrative = COALESCE($7, narrative)
+        WHERE id = $8 AND invoice_id IS NULL
+        RETURNING *
+        "#,
+    )
+    .bind(req.task_id)
+    .bind(matter_id)
+    .bind(req.entry_date)
+    .bind(req.duration_minutes)
+    .bind(req.billable)
+    .bind(req.hourly_rate_agorot)
+    .bind(&req.narrative)
+    .bind(id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(entry)
+}
+
+pub async fn delete_time_entry(pool: &PgPool, id: Uuid) -> Result<bool> {
+    let result = sqlx::query("DELETE FROM time_entries WHERE id = $1 AND invoice_id IS NULL")
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Minutes logged against each of the tasks, leaving out running timers.
+/// Tasks without time are not returned.
+pub async fn get_task_time_totals(pool: &PgPool, ids: &[Uuid]) -> Result<Vec<TaskTimeTotal>> {
+    let totals = sqlx::query_as::<_, TaskTimeTotal>(
+        r#"
+        SELECT task_id, SUM(duration_minutes)::BIGINT as total_minutes
+        FROM time_entries
+        WHERE task_id = ANY($1) AND duration_minutes IS NOT NULL
+        GROUP BY task_id
+        "#,
+    )
+    .bind(ids)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(totals)
+}
+
+/// Totals of finished entries grouped by `group_by` (`user`, `matter` or
+/// `period`), which the caller has already checked. Periods are truncated
+/// with `period` (`day`, `week` or `month`); weeks start on Monday.
+pub async fn get_time_report(
+    pool: &PgPool,
+    group_by: &str,
+    period: &str,
+    params: &TimeReportQuery,
+) -> Result<Vec<TimeReportRow>> {
+    let (key, label, order) = match group_by {
+        "user" => ("e.user_id::TEXT", "u.name", "total_minutes DESC"),
+        "matter" => (
+            "e.matter_id::TEXT",
+            "m.matter_number || ' – ' || m.title",
+            "total_minutes DESC",
+        ),
+        _ => (
+            "TO_CHAR(DATE_TRUNC($5, e.entry_date::TIMESTAMP), 'YYYY-MM-DD')",
+            "TO_CHAR(DATE_TRUNC($5, e.entry_date::TIMESTAMP), 'YYYY-MM-DD')",
+            "key ASC",
+        ),
+    };
+
+    let sql = format!(
+        r#"
+        SELECT
+            {key} as key,
+            {label} as label,
+            COUNT(*) as entries,
+            COALESCE(SUM(e.duration_minutes), 0)::BIGINT as total_minutes,
+            COALESCE(SUM(e.duration_minutes) FILTER (WHERE e.billable), 0)::BIGINT as billable_minutes,
+            COALESCE(SUM(e.amount_agorot), 0)::BIGINT as billable_amount_agorot
+        FROM time_entries e
+        JOIN users u ON u.id = e.user_id
+        LEFT JOIN matters m ON m.id = e.matter_id
+        WHERE e.duration_minutes IS NOT NULL
+          AND ($1::DATE IS NULL OR e.entry_date >= $1)
+          AND ($2::DATE IS NULL OR e.entry_date <= $2)
+          AND ($3::UUID IS NULL OR e.user_id = $3)
+          AND ($4::UUID IS NULL OR e.matter_id = $4)
+        GROUP BY 1, 2
+        ORDER BY {order}
+        "#,
+        key = key,
+        label = label,
+        order = order,
+    );
+
+    let mut query = sqlx::query_as::<_, TimeReportRow>(&sql)
+        .bind(params.from)
+        .bind(params.to)
+        .bind(params.user_id)
+        .bind(params.matter_id);
+    if group_by == "period" {
+        query = query.bind(period);
+    }
+
+    let rows = query.fetch_all(pool).await?;
+
+    Ok(rows)
+}
+
+/// `None` clears the rate. Existing entries keep the rate they were logged at.
+pub async fn set_user_hourly_rate(pool: &PgPool, user_id: Uuid, rate: Option<i64>) -> Result<Option<User>> {
+    let user = sqlx::query_as::<_, User>(
+        "UPDATE users SET hourly_rate_agorot = $1 WHERE id = $2 RETURNING *"
+    )
+    .bind(rate)
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(user)
+}
@@ -34,6 +34,16 @@ pub async fn get_user_by_email(pool: &PgPool, email: &str) -> Result<Option<User
     Ok(user)
 }
 
+/// Mail headers do not preserve the case users registered with.
+pub async fn get_user_by_email_ignore_case(pool: &PgPool, email: &str) -> Result<Option<User>> {
+    let user = sqlx::query_as::<_, User>("SELECT * FROM users WHERE LOWER(email) = LOWER($1)")
+        .bind(email)
+        .fetch_optional(pool)
+        .await?;
+
+    Ok(user)
+}
+
 pub async fn get_user_by_id(pool: &PgPool, id: Uuid) -> Result<Option<User>> {
     let user = sqlx::query_as::<_, User>("SELECT * FROM users WHERE id = $1")
         .bind(id)
@@ -55,3 +65,11 @@ pub async fn update_last_login(pool: &PgPool, user_id: Uuid) -> Result<()> {
 
     Ok(())
 }
+
+pub async fn get_active_users(pool: &PgPool) -> Result<Vec<User>> {
+    let users = sqlx::query_as::<_, User>("SELECT * FROM users WHERE is_active = true ORDER BY name")
+        .fetch_all(pool)
+        .await?;
+
+    Ok(users)
+}
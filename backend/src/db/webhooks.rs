@@ -0,0 +1,314 @@
+use crate::models::{CreateWebhookRequest, UpdateWebhookRequest, WebhookDelivery, WebhookSubscription};
+use anyhow::Result;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+pub async fn create_webhook_subscription(
+    pool: &PgPool,
+    req: &CreateWebhookRequest,
+    secret: &str,
+    created_by: Uuid,
+) -> Result<WebhookSubscription> {
+    let subscription = sqlx::query_as::<_, WebhookSubscription>(
+        r#"
+        INSERT INTO webhook_subscriptions (name, url, secret, event_types, created_by)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING *
+        "#,
+    )
+    .bind(&req.name)
+    .bind(&req.url)
+    .bind(secret)
+    .bind(&req.event_types)
+    .bind(created_by)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(subscription)
+}
+
+pub async fn get_webhook_subscriptions(pool: &PgPool) -> Result<Vec<WebhookSubscription>> {
+    let subscriptions = sqlx::query_as::<_, WebhookSubscription>(
+        "SELECT * FROM webhook_subscriptions ORDER BY created_at DESC"
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(subscriptions)
+}
+
+pub async fn get_webhook_subscription_by_id(pool: &PgPool, id: Uuid) -> Result<Option<WebhookSubscription>> {
+    let subscription = sqlx::query_as::<_, WebhookSubscription>(
+        "SELECT * FROM webhook_subscriptions WHERE id = $1"
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(subscription)
+}
+
+pub async fn update_webhook_subscription(
+    pool: &PgPool,
+    id: Uuid,
+    req: &UpdateWebhookRequest,
+) -> Result<Option<WebhookSubscription>> {
+    let subscription = sqlx::query_as::<_, WebhookSubscription>(
+        r#"
+        UPDATE webhook_subscriptions
+        SET name = COALESCE($2, name),
+            url = COALESCE($3, url),
+            event_types = COALESCE($4, event_types),
+            is_active = COALESCE($5, is_active),
+            consecutive_failures = CASE WHEN $5 THEN 0 ELSE consecutive_failures END,
+            disabled_at = CASE
+                WHEN $5 THEN NULL
+                WHEN NOT $5 AND is_active THEN CURRENT_TIMESTAMP
+                ELSE disabled_at
+            END,
+            disabled_reason = CASE
+                WHEN $5 THEN NULL
+                WHEN NOT $5 AND is_active THEN 'Disabled by an admin'
+                ELSE disabled_reason
+            END
+        WHERE id = $1
+        RETURNING *
+        "#,
+    )
+    .bind(id)
+    .bind(&req.name)
+    .bind(&req.url)
+    .bind(&req.event_types)
+    .bind(req.is_active)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(subscription)
+}
+
+pub async fn delete_webhook_subscription(pool: &PgPool, id: Uuid) -> Result<bool> {
+    let result = sqlx::query("DELETE FROM webhook_subscriptions WHERE id = $1")
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Creates a delivery already claimed for an immediate attempt, for "send
+/// test event".
+pub async fn create_webhook_test_delivery(
+    pool: &PgPool,
+    subscription_id: Uuid,
+    event_type: &str,
+    payload: &serde_json::Value,
+    lock_seconds: i64,
+) -> Result<WebhookDelivery> {
+    let delivery = sqlx::query_as::<_, WebhookDelivery>(
+        r#"
+        INSERT INTO webhook_deliveries (subscription_id, event_type, payload, status, attempts, locked_until)
+        VALUES ($1, $2, $3, 'sending', 1, CURRENT_TIMESTAMP + make_interval(secs => $4))
+        RETURNING *
+        "#,
+    )
+    .bind(subscription_id)
+    .bind(event_type)
+    .bind(payload)
+    .bind(lock_seconds as f64)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(delivery)
+}
+
+pub async fn get_webhook_deliveries(
+    pool: &PgPool,
+    subscription_id: Uuid,
+    status: Option<&str>,
+    limit: i64,
+) -> Result<Vec<WebhookDelivery>> {
+    let deliveries = sqlx::query_as::<_, WebhookDelivery>(
+        r#"
+        SELECT * FROM webhook_deliveries
+        WHERE subscription_id = $1 AND ($2::VARCHAR IS NULL OR status = $2)
+        ORDER BY created_at DESC
+        LIMIT $3
+        "#,
+    )
+    .bind(subscription_id)
+    .bind(status)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(deliveries)
+}
+
+/// Claims due deliveries of active subscriptions; see `claim_due_emails` for
+/// the locking scheme.
+pub async fn claim_due_webhook_deliveries(pool: &PgPool, limit: i64, lock_seconds: i64) -> Result<Vec<WebhookDelivery>> {
+    let deliveries = sqlx::query_as::<_, WebhookDelivery>(
+        r#"
+        UPDATE webhook_deliveries
+        SET status = 'sending',
+            attempts = attempts + 1,
+            locked_until = CURRENT_TIMESTAMP + make_interval(secs => $2)
+        WHERE id IN (
+            SELECT d.id FROM webhook_deliveries d
+            JOIN webhook_subscriptions s ON s.id = d.subscription_id
+            WHERE s.is_active
+              AND ((d.status = 'pending' AND d.next_attempt_at <= CURRENT_TIMESTAMP)
+                OR (d.status = 'sending' AND d.locked_until < CURRENT_TIMESTAMP))
+            ORDER BY d.next_attempt_at ASC
+            LIMIT $1
+            FOR UPDATE OF d SKIP LOCKED
+        )
+        RETURNING *
+        "#,
+    )
+    .bind(limit)
+    .bind(lock_seconds as f64)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(deliveries)
+}
+
+/// Records a 2xx response and resets the subscription's failure count.
+pub async fn mark_webhook_delivered(
+    pool: &PgPool,
+    delivery: &WebhookDelivery,
+    response_status: i32,
+    response_body: Option<&str>,
+) -> Result<WebhookDelivery> {
+    let delivered = sqlx::query_as::<_, WebhookDelivery>(
+        r#"
+        UPDATE webhook_deliveries
+        SET status = 'delivered', delivered_at = CURRENT_TIMESTAMP, locked_until = NULL,
+            response_status = $2, response_body = $3, last_error = NULL
+        WHERE id = $1
+        RETURNING *
+        "#,
+    )
+    .bind(delivery.id)
+    .bind(response_status)
+    .bind(response_body)
+    .fetch_one(pool)
+    .await?;
+
+    sqlx::query(
+        r#"
+        UPDATE webhook_subscriptions
+        SET consecutive_failures = 0, last_success_at = CURRENT_TIMESTAMP
+        WHERE id = $1
+        "#,
+    )
+    .bind(delivery.subscription_id)
+    .execute(pool)
+    .await?;
+
+    Ok(delivered)
+}
+
+/// Records a failed attempt, scheduling a retry after `retry_in_seconds` or
+/// giving up when `None`. Returns the delivery and the subscription's number
+/// of consecutive failures.
+pub async fn mark_webhook_failed(
+    pool: &PgPool,
+    delivery: &WebhookDelivery,
+    response_status: Option<i32>,
+    response_body: Option<&str>,
+    error: &str,
+    retry_in_seconds: Option<i64>,
+) -> Result<(WebhookDelivery, i32)> {
+    let failed = sqlx::query_as::<_, WebhookDelivery>(
+        r#"
+        UPDATE webhook_deliveries
+        SET status = CASE WHEN $1::BIGINT IS NULL THEN 'dead' ELSE 'pending' END,
+            next_attempt_at = CURRENT_TIMESTAMP + make_interval(secs => COALESCE($1, 0)::DOUBLE PRECISION),
+            locked_until = NULL,
+            response_status = $2,
+            response_body = $3,
+            last_error = $4
+        WHERE id = $5
+        RETURNING *
+        "#,
+    )
+    .bind(retry_in_seconds)
+    .bind(response_status)
+    .bind(response_body)
+    .bind(error)
+    .bind(delivery.id)
+    .fetch_one(pool)
+    .await?;
+
+    let failures: i32 = sqlx::query_scalar(
+        r#"
+        UPDATE webhook_subscriptions
+        SET consecutive_failures = consecutive_failures + 1
+        WHERE id = $1
+        RETURNING consecutive_failures
+        "#,
+    )
+    .bind(delivery.subscription_id)
+    .fetch_one(pool)
+    .await?;
+
+    Ok((failed, failures))
+}
+
+/// Turns a failing subscription off and gives up on its queued deliveries.
+pub async fn disable_webhook_subscription(pool: &PgPool, id: Uuid, reason: &str) -> Result<()> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query(
+        r#"
+        UPDATE webhook_subscriptions
+        SET is_active = false, disabled_at = CURRENT_TIMESTAMP, disabled_reason = $2
+        WHERE id = $1 AND is_active
+        "#,
+    )
+    .bind(id)
+    .bind(reason)
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query(
+        r#"
+        UPDATE webhook_deliveries
+        SET status = 'dead', locked_until = NULL, last_error = $2
+        WHERE subscription_id = $1 AND status = 'pending'
+        "#,
+    )
+    .bind(id)
+    .bind(format!("Subscription disabled: {}", reason))
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(())
+}
+
+/// Puts a dead delivery back in the queue with a fresh retry budget.
+pub async fn requeue_webhook_delivery(
+    pool: &PgPool,
+    subscription_id: Uuid,
+    id: Uuid,
+) -> Result<Option<WebhookDelivery>> {
+    let delivery = sqlx::query_as::<_, WebhookDelivery>(
+        r#"
+        UPDATE webhook_deliveries
+        SET status = 'pending', attempts = 0, next_attempt_at = CURRENT_TIMESTAMP,
+            locked_until = NULL
+        WHERE id = $1 AND subscription_id = $2 AND status = 'dead'
+        RETURNING *
+        "#,
+    )
+    .bind(id)
+    .bind(subscription_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(delivery)
+}
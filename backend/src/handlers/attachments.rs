@@ -0,0 +1,58 @@
+use axum::{
+    extract::{Path, State},
+    http::header::{CONTENT_DISPOSITION, CONTENT_TYPE},
+    response::IntoResponse,
+    Json,
+};
+use uuid::Uuid;
+
+use crate::{db, models::Attachment, utils::AppError, AppState};
+
+pub async fn get_task_attachments(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<Attachment>>, AppError> {
+    db::get_task_by_id(&state.pool, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Task with id {} not found", id)))?;
+
+    let attachments = db::get_attachments_by_task(&state.pool, id).await?;
+
+    Ok(Json(attachments))
+}
+
+/// Downloads a file stored by the API, e.g. one received by email.
+pub async fn download_attachment(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let attachment = db::get_attachment_content(&state.pool, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Attachment with id {} not found", id)))?;
+
+    let content_type = attachment
+        .mime_type
+        .unwrap_or_else(|| "application/octet-stream".to_string());
+    // RFC 6266: file names are often Hebrew, so send them percent-encoded
+    let disposition = format!(
+        "attachment; filename*=UTF-8''{}",
+        percent_encode(&attachment.file_name)
+    );
+
+    Ok((
+        [(CONTENT_TYPE, content_type), (CONTENT_DISPOSITION, disposition)],
+        attachment.content,
+    ))
+}
+
+pub(crate) fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
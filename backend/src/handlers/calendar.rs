@@ -0,0 +1,106 @@
+use axum::{
+    extract::{Path, State},
+    http::header::{CACHE_CONTROL, CONTENT_TYPE},
+    response::IntoResponse,
+    Json,
+};
+use chrono::{Duration, Utc};
+use rand::RngCore;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    db,
+    models::{CalendarFeedCreated, CalendarFeedStatus},
+    services::{hearing_calendar_event, render_calendar, task_calendar_event, CalendarEvent},
+    utils::{AppError, AuthUser},
+    AppState,
+};
+
+/// How far back the feed keeps past hearings.
+const FEED_HEARING_HISTORY_DAYS: i64 = 30;
+
+pub async fn get_calendar_feed(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<Json<CalendarFeedStatus>, AppError> {
+    let feed = db::get_calendar_feed(&state.pool, auth.id).await?;
+
+    Ok(Json(feed.into()))
+}
+
+/// Creates the caller's feed URL, or rotates it if one exists. The token is
+/// only returned here; the database keeps its hash.
+pub async fn create_calendar_feed(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<Json<CalendarFeedCreated>, AppError> {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    let token: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+
+    let feed = db::upsert_calendar_feed(&state.pool, auth.id, &hash_token(&token)).await?;
+
+    Ok(Json(CalendarFeedCreated {
+        url: format!(
+            "{}/api/calendar/{}.ics",
+            state.config.api_base_url.trim_end_matches('/'),
+            token
+        ),
+        created_at: feed.created_at,
+    }))
+}
+
+pub async fn delete_calendar_feed(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<Json<Value>, AppError> {
+    if !db::delete_calendar_feed(&state.pool, auth.id).await? {
+        return Err(AppError::NotFound("No calendar feed to delete".to_string()));
+    }
+
+    Ok(Json(json!({
+        "message": "Calendar feed deleted successfully"
+    })))
+}
+
+/// The RFC 5545 feed behind `/api/calendar/:token.ics`: the user's open
+/// tasks on their due dates and their hearings. The token in the URL is the
+/// only credential, since calendar apps cannot send a bearer token.
+pub async fn get_calendar_ics(
+    State(state): State<AppState>,
+    Path(token): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let token = token.strip_suffix(".ics").unwrap_or(&token);
+
+    let user = db::get_calendar_feed_user(&state.pool, &hash_token(token))
+        .await?
+        .ok_or_else(|| AppError::NotFound("Calendar feed not found".to_string()))?;
+
+    let tasks = db::get_calendar_tasks(&state.pool, &user.email).await?;
+    let since = Utc::now() - Duration::days(FEED_HEARING_HISTORY_DAYS);
+    let hearings = db::get_calendar_hearings(&state.pool, user.id, &user.email, since).await?;
+
+    let mut events: Vec<CalendarEvent> = tasks
+        .iter()
+        .filter_map(|task| task_calendar_event(task, &state.email_service.task_url(task.id)))
+        .collect();
+    events.extend(hearings.iter().map(hearing_calendar_event));
+
+    let body = render_calendar(&format!("משימות – {}", user.name), &events);
+
+    Ok((
+        [
+            (CONTENT_TYPE, "text/calendar; charset=utf-8"),
+            (CACHE_CONTROL, "no-cache"),
+        ],
+        body,
+    ))
+}
+
+fn hash_token(token: &str) -> String {
+    Sha256::digest(token.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
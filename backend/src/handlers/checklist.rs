@@ -0,0 +1,81 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use serde_json::{json, Value};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    db,
+    models::{ChecklistItem, CreateChecklistItemRequest, UpdateChecklistItemRequest},
+    utils::{AppError, AuthUser},
+    AppState,
+};
+
+pub async fn get_checklist(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<ChecklistItem>>, AppError> {
+    ensure_task_exists(&state, id).await?;
+
+    let items = db::get_checklist_items(&state.pool, id).await?;
+
+    Ok(Json(items))
+}
+
+pub async fn create_checklist_item(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<CreateChecklistItemRequest>,
+) -> Result<Json<ChecklistItem>, AppError> {
+    // Validate request
+    payload.validate()?;
+
+    ensure_task_exists(&state, id).await?;
+
+    let item = db::create_checklist_item(&state.pool, id, &payload).await?;
+
+    Ok(Json(item))
+}
+
+pub async fn update_checklist_item(
+    State(state): State<AppState>,
+    auth: Option<AuthUser>,
+    Path((id, item_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateChecklistItemRequest>,
+) -> Result<Json<ChecklistItem>, AppError> {
+    // Validate request
+    payload.validate()?;
+
+    let user_id = auth.map(|a| a.id);
+    let item = db::update_checklist_item(&state.pool, id, item_id, &payload, user_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Checklist item with id {} not found", item_id)))?;
+
+    Ok(Json(item))
+}
+
+pub async fn delete_checklist_item(
+    State(state): State<AppState>,
+    Path((id, item_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<Value>, AppError> {
+    let deleted = db::delete_checklist_item(&state.pool, id, item_id).await?;
+
+    if !deleted {
+        return Err(AppError::NotFound(format!("Checklist item with id {} not found", item_id)));
+    }
+
+    Ok(Json(json!({
+        "message": "Checklist item deleted successfully",
+        "id": item_id
+    })))
+}
+
+async fn ensure_task_exists(state: &AppState, id: Uuid) -> Result<(), AppError> {
+    db::get_task_by_id(&state.pool, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Task with id {} not found", id)))?;
+
+    Ok(())
+}
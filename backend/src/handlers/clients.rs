@@ -0,0 +1,133 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use serde_json::{json, Value};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    db,
+    models::{Client, ClientsQuery, CreateClientRequest, Matter, MattersQuery, UpdateClientRequest},
+    utils::{AppError, AuthUser},
+    AppState,
+};
+
+pub async fn create_client(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(payload): Json<CreateClientRequest>,
+) -> Result<Json<Client>, AppError> {
+    // Validate request
+    payload.validate()?;
+
+    if let Some(id_number) = &payload.id_number {
+        if db::get_client_by_id_number(&state.pool, id_number).await?.is_some() {
+            return Err(AppError::BadRequest(format!(
+                "A client with ID number {} already exists",
+                id_number
+            )));
+        }
+    }
+
+    let client = db::create_client(&state.pool, &payload, auth.id).await?;
+
+    Ok(Json(client))
+}
+
+pub async fn get_clients(
+    State(state): State<AppState>,
+    _auth: AuthUser,
+    Query(params): Query<ClientsQuery>,
+) -> Result<Json<Vec<Client>>, AppError> {
+    let clients = db::get_clients(&state.pool, &params).await?;
+
+    Ok(Json(clients))
+}
+
+pub async fn get_client(
+    State(state): State<AppState>,
+    _auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Client>, AppError> {
+    let client = find_client(&state, id).await?;
+
+    Ok(Json(client))
+}
+
+pub async fn update_client(
+    State(state): State<AppState>,
+    _auth: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateClientRequest>,
+) -> Result<Json<Client>, AppError> {
+    // Validate request
+    payload.validate()?;
+
+    if let Some(id_number) = &payload.id_number {
+        if let Some(existing) = db::get_client_by_id_number(&state.pool, id_number).await? {
+            if existing.id != id {
+                return Err(AppError::BadRequest(format!(
+                    "A client with ID number {} already exists",
+                    id_number
+                )));
+            }
+        }
+    }
+
+    let client = db::update_client(&state.pool, id, &payload)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Client with id {} not found", id)))?;
+
+    Ok(Json(client))
+}
+
+/// Only a client without matters can be deleted; others can be deactivated.
+pub async fn delete_client(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Value>, AppError> {
+    auth.require_admin()?;
+
+    find_client(&state, id).await?;
+
+    let matters = db::count_matters_by_client(&state.pool, id).await?;
+    if matters > 0 {
+        return Err(AppError::BadRequest(format!(
+            "Client has {} matter(s) and cannot be deleted; deactivate it instead",
+            matters
+        )));
+    }
+
+    if !db::delete_client(&state.pool, id).await? {
+        return Err(AppError::NotFound(format!("Client with id {} not found", id)));
+    }
+
+    Ok(Json(json!({
+        "message": "Client deleted successfully",
+        "id": id
+    })))
+}
+
+pub async fn get_client_matters(
+    State(state): State<AppState>,
+    _auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<Matter>>, AppError> {
+    find_client(&state, id).await?;
+
+    let params = MattersQuery {
+        client_id: Some(id),
+        ..Default::default()
+    };
+    let matters = db::get_matters(&state.pool, &params).await?;
+
+    Ok(Json(matters))
+}
+
+async fn find_client(state: &AppState, id: Uuid) -> Result<Client, AppError> {
+    db::get_client_by_id(&state.pool, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Client with id {} not found", id)))
+}
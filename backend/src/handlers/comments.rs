@@ -0,0 +1,290 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use serde_json::{json, Value};
+use sqlx::PgConnection;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    db,
+    models::{
+        ActivityItem, CommentResponse, CreateAuditLogRequest, CreateCommentRequest, NewTaskEvent,
+        NotificationEvent, Task, TaskComment, TaskCommentEdit, TaskEventType,
+        UpdateCommentRequest, User,
+    },
+    services::{dispatch_notification, resolve_mentions},
+    utils::{AppError, AuthUser},
+    AppState,
+};
+
+pub async fn get_task_comments(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<CommentResponse>>, AppError> {
+    find_task(&state, id).await?;
+
+    let comments = db::get_comments_by_task(&state.pool, id).await?;
+    let responses: Vec<CommentResponse> = comments.into_iter().map(|c| c.into()).collect();
+
+    Ok(Json(responses))
+}
+
+pub async fn create_task_comment(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<CreateCommentRequest>,
+) -> Result<Json<CommentResponse>, AppError> {
+    // Validate request
+    payload.validate()?;
+
+    let task = find_task(&state, id).await?;
+    let author = find_author(&state, &auth).await?;
+
+    let mut tx = state.pool.begin().await?;
+    let comment = post_task_comment(&state, &mut tx, &task, &author, &payload.body).await?;
+    tx.commit().await?;
+
+    record_comment_audit(&state, auth.id, &comment, "comment_created", None).await;
+
+    Ok(Json(comment.into()))
+}
+
+pub async fn update_task_comment(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((id, comment_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateCommentRequest>,
+) -> Result<Json<CommentResponse>, AppError> {
+    // Validate request
+    payload.validate()?;
+
+    let task = find_task(&state, id).await?;
+    let comment = find_comment(&state, id, comment_id).await?;
+
+    // Only the author may edit a comment
+    if comment.author_id != Some(auth.id) {
+        return Err(AppError::Forbidden(
+            "Only the author can edit this comment".to_string(),
+        ));
+    }
+
+    if comment.body == payload.body {
+        return Ok(Json(comment.into()));
+    }
+
+    let users = db::get_active_users(&state.pool).await?;
+    let mentioned: Vec<User> = resolve_mentions(&payload.body, &users)
+        .into_iter()
+        .filter(|u| Some(u.id) != comment.author_id)
+        .cloned()
+        .collect();
+    let mentioned_ids: Vec<Uuid> = mentioned.iter().map(|u| u.id).collect();
+
+    // Only people who were not mentioned before get a new notification
+    let newly_mentioned: Vec<User> = mentioned
+        .into_iter()
+        .filter(|u| !comment.mentioned_user_ids.contains(&u.id))
+        .collect();
+
+    let previous_body = comment.body.clone();
+    let mut tx = state.pool.begin().await?;
+
+    let updated = db::update_comment(
+        &mut tx,
+        &comment,
+        Some(auth.id),
+        &payload.body,
+        &mentioned_ids,
+    )
+    .await?;
+    let event = NewTaskEvent::for_comment(TaskEventType::CommentUpdated, &task, &updated);
+    db::record_task_event(&mut *tx, &event).await?;
+    queue_mention_notifications(&state, &mut tx, &task, &updated, &newly_mentioned).await?;
+
+    tx.commit().await?;
+
+    record_comment_audit(
+        &state,
+        auth.id,
+        &updated,
+        "comment_edited",
+        Some(json!({ "previous_body": previous_body, "body": updated.body })),
+    )
+    .await;
+
+    Ok(Json(updated.into()))
+}
+
+pub async fn delete_task_comment(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((id, comment_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<Value>, AppError> {
+    let task = find_task(&state, id).await?;
+    let comment = find_comment(&state, id, comment_id).await?;
+
+    // Authors can delete their own comments, admins can delete any
+    if comment.author_id != Some(auth.id) && !auth.is_admin() {
+        return Err(AppError::Forbidden(
+            "Only the author or an admin can delete this comment".to_string(),
+        ));
+    }
+
+    let mut tx = state.pool.begin().await?;
+
+    if !db::soft_delete_comment(&mut *tx, comment.id).await? {
+        return Err(AppError::NotFound(format!(
+            "Comment with id {} not found",
+            comment_id
+        )));
+    }
+    let event = NewTaskEvent::for_comment(TaskEventType::CommentDeleted, &task, &comment);
+    db::record_task_event(&mut *tx, &event).await?;
+
+    tx.commit().await?;
+
+    record_comment_audit(&state, auth.id, &comment, "comment_deleted", None).await;
+
+    Ok(Json(json!({
+        "message": "Comment deleted successfully",
+        "id": comment_id
+    })))
+}
+
+pub async fn get_task_comment_history(
+    State(state): State<AppState>,
+    Path((id, comment_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<Vec<TaskCommentEdit>>, AppError> {
+    let comment = find_comment(&state, id, comment_id).await?;
+    let edits = db::get_comment_edits(&state.pool, comment.id).await?;
+
+    Ok(Json(edits))
+}
+
+pub async fn get_task_activity(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<ActivityItem>>, AppError> {
+    find_task(&state, id).await?;
+
+    let comments = db::get_comments_by_task(&state.pool, id).await?;
+    let logs = db::get_task_audit_logs(&state.pool, id).await?;
+
+    let mut feed: Vec<ActivityItem> = comments
+        .into_iter()
+        .map(ActivityItem::from)
+        .chain(logs.into_iter().map(ActivityItem::from))
+        .collect();
+    feed.sort_by_key(|item| item.occurred_at);
+
+    Ok(Json(feed))
+}
+
+async fn find_task(state: &AppState, id: Uuid) -> Result<Task, AppError> {
+    db::get_task_by_id(&state.pool, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Task with id {} not found", id)))
+}
+
+async fn find_comment(state: &AppState, task_id: Uuid, comment_id: Uuid) -> Result<TaskComment, AppError> {
+    db::get_comment_by_id(&state.pool, task_id, comment_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Comment with id {} not found", comment_id)))
+}
+
+async fn find_author(state: &AppState, auth: &AuthUser) -> Result<User, AppError> {
+    let user = db::get_user_by_id(&state.pool, auth.id)
+        .await?
+        .ok_or_else(|| AppError::Unauthorized("User not found".to_string()))?;
+
+    if !user.is_active {
+        return Err(AppError::Forbidden("User account is disabled".to_string()));
+    }
+
+    Ok(user)
+}
+
+pub(crate) async fn record_comment_audit(
+    state: &AppState,
+    actor_id: Uuid,
+    comment: &TaskComment,
+    action: &str,
+    changes: Option<Value>,
+) {
+    let req = CreateAuditLogRequest {
+        user_id: Some(actor_id),
+        action: action.to_string(),
+        entity_type: "comment".to_string(),
+        entity_id: comment.id,
+        // Every record names the comment's task
+        changes: Some(match changes {
+            Some(Value::Object(mut changes)) => {
+                changes.insert("task_id".to_string(), json!(comment.task_id));
+                Value::Object(changes)
+            }
+            _ => json!({ "task_id": comment.task_id }),
+        }),
+        ip_address: None,
+        user_agent: None,
+    };
+
+    if let Err(e) = db::create_audit_log(&state.pool, &req).await {
+        tracing::error!("Failed to write audit log: {:?}", e);
+    }
+}
+
+/// Adds `author`'s comment to the task in the caller's transaction, with its
+/// event and the notifications for everyone it @mentions.
+pub(crate) async fn post_task_comment(
+    state: &AppState,
+    conn: &mut PgConnection,
+    task: &Task,
+    author: &User,
+    body: &str,
+) -> Result<TaskComment, AppError> {
+    // Resolve @mentions against the users table
+    let users = db::get_active_users(&state.pool).await?;
+    let mentioned: Vec<User> = resolve_mentions(body, &users)
+        .into_iter()
+        .filter(|u| u.id != author.id)
+        .cloned()
+        .collect();
+    let mentioned_ids: Vec<Uuid> = mentioned.iter().map(|u| u.id).collect();
+
+    let comment = db::create_comment(
+        &mut *conn,
+        task.id,
+        Some(author.id),
+        &author.name,
+        &author.email,
+        body,
+        &mentioned_ids,
+    )
+    .await?;
+    let event = NewTaskEvent::for_comment(TaskEventType::CommentCreated, task, &comment);
+    db::record_task_event(&mut *conn, &event).await?;
+    queue_mention_notifications(state, conn, task, &comment, &mentioned).await?;
+
+    Ok(comment)
+}
+
+async fn queue_mention_notifications(
+    state: &AppState,
+    conn: &mut PgConnection,
+    task: &Task,
+    comment: &TaskComment,
+    users: &[User],
+) -> Result<(), AppError> {
+    for user in users {
+        let email = state
+            .email_service
+            .build_comment_mention_notification(task, comment, &user.email)?;
+        dispatch_notification(state, conn, NotificationEvent::Comment, &task.priority, email)
+            .await?;
+    }
+
+    Ok(())
+}
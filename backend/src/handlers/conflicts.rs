@@ -0,0 +1,79 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use serde_json::json;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    db,
+    models::{AuditLog, ConflictCheckRequest, ConflictCheckResponse, CreateAuditLogRequest},
+    services::rank_conflict_hits,
+    utils::{AppError, AuthUser},
+    AppState,
+};
+
+const CONFLICT_CHECK_ENTITY: &str = "conflict_check";
+
+/// Fuzzy-matches a name (in Hebrew or Latin letters) and/or an ID number
+/// against every client, opposing party, matter party and matter contact. The check and its
+/// hits are written to the audit log, as evidence that it was run; the
+/// returned `check_id` refers to that record.
+pub async fn check_conflicts(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(payload): Json<ConflictCheckRequest>,
+) -> Result<Json<ConflictCheckResponse>, AppError> {
+    // Validate request
+    payload.validate()?;
+
+    if payload.name.as_deref().map_or(true, |n| n.trim().is_empty()) && payload.id_number.is_none() {
+        return Err(AppError::BadRequest("Give a name or an ID number to check".to_string()));
+    }
+
+    let candidates = db::get_conflict_candidates(&state.pool).await?;
+    let hits = rank_conflict_hits(payload.name.as_deref(), payload.id_number.as_deref(), &candidates);
+
+    let check_id = Uuid::new_v4();
+    let log = CreateAuditLogRequest {
+        user_id: Some(auth.id),
+        action: "conflict_check".to_string(),
+        entity_type: CONFLICT_CHECK_ENTITY.to_string(),
+        entity_id: check_id,
+        changes: Some(json!({
+            "request": payload,
+            "checked_by": auth.email,
+            "hits": hits,
+        })),
+        ip_address: None,
+        user_agent: None,
+    };
+    // Unlike other audit records, a check that cannot be recorded fails
+    let log = db::create_audit_log(&state.pool, &log).await?;
+
+    Ok(Json(ConflictCheckResponse {
+        check_id,
+        checked_at: log.created_at,
+        name: payload.name,
+        id_number: payload.id_number,
+        hits,
+    }))
+}
+
+/// The recorded check; users see their own checks, admins everyone's.
+pub async fn get_conflict_check(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<AuditLog>, AppError> {
+    let log = db::get_audit_log_for_entity(&state.pool, CONFLICT_CHECK_ENTITY, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Conflict check with id {} not found", id)))?;
+
+    if log.user_id != Some(auth.id) && !auth.is_admin() {
+        return Err(AppError::Forbidden("You can only view your own conflict checks".to_string()));
+    }
+
+    Ok(Json(log))
+}
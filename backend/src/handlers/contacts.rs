@@ -0,0 +1,291 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::header::{CONTENT_DISPOSITION, CONTENT_TYPE},
+    response::IntoResponse,
+    Json,
+};
+use serde_json::{json, Value};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    db,
+    models::{
+        Contact, ContactImportResult, ContactPoint, ContactsQuery, CreateContactRequest,
+        LinkContactRequest, LinkedContact, UpdateContactRequest,
+    },
+    services::{parse_vcards, render_vcards},
+    utils::{AppError, AuthUser},
+    AppState,
+};
+
+/// Largest vCard file accepted for import.
+pub const CONTACTS_IMPORT_MAX_BYTES: usize = 5 * 1024 * 1024;
+
+pub async fn create_contact(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(payload): Json<CreateContactRequest>,
+) -> Result<Json<Contact>, AppError> {
+    // Validate request
+    payload.validate()?;
+
+    let contact = db::create_contact(&state.pool, &payload, auth.id).await?;
+
+    Ok(Json(contact))
+}
+
+pub async fn get_contacts(
+    State(state): State<AppState>,
+    _auth: AuthUser,
+    Query(params): Query<ContactsQuery>,
+) -> Result<Json<Vec<Contact>>, AppError> {
+    let contacts = db::get_contacts(&state.pool, &params).await?;
+
+    Ok(Json(contacts))
+}
+
+pub async fn get_contact(
+    State(state): State<AppState>,
+    _auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Contact>, AppError> {
+    let contact = find_contact(&state, id).await?;
+
+    Ok(Json(contact))
+}
+
+pub async fn update_contact(
+    State(state): State<AppState>,
+    _auth: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateContactRequest>,
+) -> Result<Json<Contact>, AppError> {
+    // Validate request
+    payload.validate()?;
+
+    let contact = db::update_contact(&state.pool, id, &payload)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Contact with id {} not found", id)))?;
+
+    Ok(Json(contact))
+}
+
+/// Removes the contact from every task and matter it is linked to.
+pub async fn delete_contact(
+    State(state): State<AppState>,
+    _auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Value>, AppError> {
+    if !db::delete_contact(&state.pool, id).await? {
+        return Err(AppError::NotFound(format!("Contact with id {} not found", id)));
+    }
+
+    Ok(Json(json!({
+        "message": "Contact deleted successfully",
+        "id": id
+    })))
+}
+
+/// The contacts matching the query, as a `.vcf` file.
+pub async fn export_contacts(
+    State(state): State<AppState>,
+    _auth: AuthUser,
+    Query(params): Query<ContactsQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let contacts = db::get_contacts(&state.pool, &params).await?;
+
+    Ok(vcard_response("contacts.vcf", render_vcards(&contacts)))
+}
+
+pub async fn export_contact(
+    State(state): State<AppState>,
+    _auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let contact = find_contact(&state, id).await?;
+
+    Ok(vcard_response(
+        &format!("contact-{}.vcf", contact.id),
+        render_vcards(std::slice::from_ref(&contact)),
+    ))
+}
+
+/// Creates a contact for every card in the posted vCard file. Cards that
+/// cannot be read are reported and skipped, as are contacts already in the
+/// directory under the same name with a matching phone or email.
+pub async fn import_contacts(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    body: String,
+) -> Result<Json<ContactImportResult>, AppError> {
+    let mut result = ContactImportResult {
+        imported: Vec::new(),
+        duplicates: Vec::new(),
+        errors: Vec::new(),
+    };
+
+    for card in parse_vcards(&body) {
+        let request = match card {
+            Ok(request) => request,
+            Err(e) => {
+                result.errors.push(e);
+                continue;
+            }
+        };
+        if let Err(e) = request.validate() {
+            result.errors.push(format!("{}: {}", request.name, e));
+            continue;
+        }
+
+        let existing = db::get_contacts_by_name(&state.pool, &request.name).await?;
+        if existing.iter().any(|contact| is_duplicate(contact, &request)) {
+            result.duplicates.push(request.name);
+            continue;
+        }
+
+        result.imported.push(db::create_contact(&state.pool, &request, auth.id).await?);
+    }
+
+    if result.imported.is_empty() && result.duplicates.is_empty() && result.errors.is_empty() {
+        return Err(AppError::BadRequest("No vCards found in the request body".to_string()));
+    }
+
+    Ok(Json(result))
+}
+
+pub async fn get_task_contacts(
+    State(state): State<AppState>,
+    _auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<LinkedContact>>, AppError> {
+    db::get_task_by_id(&state.pool, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Task with id {} not found", id)))?;
+
+    let contacts = db::get_task_contacts(&state.pool, id).await?;
+
+    Ok(Json(contacts))
+}
+
+pub async fn link_task_contact(
+    State(state): State<AppState>,
+    _auth: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<LinkContactRequest>,
+) -> Result<Json<Vec<LinkedContact>>, AppError> {
+    // Validate request
+    payload.validate()?;
+
+    db::get_task_by_id(&state.pool, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Task with id {} not found", id)))?;
+    check_contact(&state, payload.contact_id).await?;
+
+    db::link_task_contact(&state.pool, id, payload.contact_id, &payload.role).await?;
+    let contacts = db::get_task_contacts(&state.pool, id).await?;
+
+    Ok(Json(contacts))
+}
+
+pub async fn unlink_task_contact(
+    State(state): State<AppState>,
+    _auth: AuthUser,
+    Path((id, link_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<Value>, AppError> {
+    if !db::unlink_task_contact(&state.pool, id, link_id).await? {
+        return Err(AppError::NotFound(format!("Contact link with id {} not found", link_id)));
+    }
+
+    Ok(Json(json!({
+        "message": "Contact unlinked successfully",
+        "id": link_id
+    })))
+}
+
+pub async fn get_matter_contacts(
+    State(state): State<AppState>,
+    _auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<LinkedContact>>, AppError> {
+    db::get_matter_by_id(&state.pool, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Matter with id {} not found", id)))?;
+
+    let contacts = db::get_matter_contacts(&state.pool, id).await?;
+
+    Ok(Json(contacts))
+}
+
+pub async fn link_matter_contact(
+    State(state): State<AppState>,
+    _auth: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<LinkContactRequest>,
+) -> Result<Json<Vec<LinkedContact>>, AppError> {
+    // Validate request
+    payload.validate()?;
+
+    db::get_matter_by_id(&state.pool, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Matter with id {} not found", id)))?;
+    check_contact(&state, payload.contact_id).await?;
+
+    db::link_matter_contact(&state.pool, id, payload.contact_id, &payload.role).await?;
+    let contacts = db::get_matter_contacts(&state.pool, id).await?;
+
+    Ok(Json(contacts))
+}
+
+pub async fn unlink_matter_contact(
+    State(state): State<AppState>,
+    _auth: AuthUser,
+    Path((id, link_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<Value>, AppError> {
+    if !db::unlink_matter_contact(&state.pool, id, link_id).await? {
+        return Err(AppError::NotFound(format!("Contact link with id {} not found", link_id)));
+    }
+
+    Ok(Json(json!({
+        "message": "Contact unlinked successfully",
+        "id": link_id
+    })))
+}
+
+/// Same name, and a phone or email in common, or neither has any.
+fn is_duplicate(contact: &Contact, request: &CreateContactRequest) -> bool {
+    let shares = |a: &[ContactPoint], b: &[ContactPoint]| {
+        a.iter().any(|x| b.iter().any(|y| x.value.eq_ignore_ascii_case(&y.value)))
+    };
+
+    let no_details = contact.phones.is_empty()
+        && contact.emails.is_empty()
+        && request.phones.is_empty()
+        && request.emails.is_empty();
+
+    no_details || shares(&contact.phones, &request.phones) || shares(&contact.emails, &request.emails)
+}
+
+fn vcard_response(filename: &str, body: String) -> impl IntoResponse {
+    (
+        [
+            (CONTENT_TYPE, "text/vcard; charset=utf-8".to_string()),
+            (CONTENT_DISPOSITION, format!("attachment; filename=\"{}\"", filename)),
+        ],
+        body,
+    )
+}
+
+async fn check_contact(state: &AppState, id: Uuid) -> Result<(), AppError> {
+    db::get_contact_by_id(&state.pool, id)
+        .await?
+        .ok_or_else(|| AppError::BadRequest(format!("Contact {} not found", id)))?;
+
+    Ok(())
+}
+
+async fn find_contact(state: &AppState, id: Uuid) -> Result<Contact, AppError> {
+    db::get_contact_by_id(&state.pool, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Contact with id {} not found", id)))
+}
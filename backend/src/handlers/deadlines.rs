@@ -0,0 +1,94 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    db,
+    handlers::tasks::{record_task_update, to_responses},
+    models::{
+        CalculateDeadlineRequest, CourtCalendarYear, DeadlineCalculation, SetTaskDeadlineRequest,
+        SetTaskDeadlineResponse, TaskDeadline, COURT_CALENDAR_YEARS,
+    },
+    services::{court_calendar_year, DeadlineRule},
+    utils::{AppError, AuthUser},
+    AppState,
+};
+
+/// Previews the due date a rule gives, without changing anything.
+pub async fn calculate_deadline(
+    Json(payload): Json<CalculateDeadlineRequest>,
+) -> Result<Json<DeadlineCalculation>, AppError> {
+    // Validate request
+    payload.validate()?;
+
+    let rule = parse_rule(&payload.rule)?;
+
+    Ok(Json(rule.compute(payload.trigger_date)))
+}
+
+/// Holidays and recess periods the calculator uses for a year.
+pub async fn get_court_calendar(Path(year): Path<i32>) -> Result<Json<CourtCalendarYear>, AppError> {
+    if !COURT_CALENDAR_YEARS.contains(&year) {
+        return Err(AppError::BadRequest(format!("Year out of range: {}", year)));
+    }
+
+    Ok(Json(court_calendar_year(year)))
+}
+
+/// Computes a deadline and makes it the task's due date, recording the
+/// trigger date, rule and skipped days alongside it.
+pub async fn set_task_deadline(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<SetTaskDeadlineRequest>,
+) -> Result<Json<SetTaskDeadlineResponse>, AppError> {
+    // Validate request
+    payload.validate()?;
+
+    let existing = db::get_task_by_id(&state.pool, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Task with id {} not found", id)))?;
+
+    let calculation = parse_rule(&payload.rule)?.compute(payload.trigger_date);
+
+    let mut tx = state.pool.begin().await?;
+
+    let task = db::set_task_due_date(&mut *tx, id, calculation.due_date).await?;
+    let deadline = db::create_task_deadline(
+        &mut *tx,
+        id,
+        &calculation,
+        existing.due_date,
+        payload.note.as_deref(),
+        auth.id,
+    )
+    .await?;
+    record_task_update(&state, &mut tx, &existing, &task, Some(&auth.email)).await?;
+
+    tx.commit().await?;
+
+    let task = to_responses(&state, vec![task]).await?.remove(0);
+
+    Ok(Json(SetTaskDeadlineResponse { task, deadline }))
+}
+
+pub async fn get_task_deadlines(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<TaskDeadline>>, AppError> {
+    db::get_task_by_id(&state.pool, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Task with id {} not found", id)))?;
+
+    let deadlines = db::get_task_deadlines(&state.pool, id).await?;
+
+    Ok(Json(deadlines))
+}
+
+fn parse_rule(rule: &str) -> Result<DeadlineRule, AppError> {
+    DeadlineRule::parse(rule).map_err(|e| AppError::BadRequest(e.to_string()))
+}
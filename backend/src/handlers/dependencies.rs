@@ -0,0 +1,95 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    db::{self, AddDependencyResult},
+    handlers::tasks::to_responses,
+    models::{CreateDependencyRequest, TaskDependenciesResponse, TaskDependency},
+    utils::{AppError, AuthUser},
+    AppState,
+};
+
+pub async fn get_task_dependencies(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<TaskDependenciesResponse>, AppError> {
+    ensure_task_exists(&state, id).await?;
+
+    let blocked_by = db::get_blocking_tasks(&state.pool, id).await?;
+    let blocking = db::get_dependent_tasks(&state.pool, id).await?;
+
+    let blocked_by = to_responses(&state, blocked_by).await?;
+    let blocking = to_responses(&state, blocking).await?;
+    let is_blocked = blocked_by
+        .iter()
+        .any(|t| t.status == "חדשה" || t.status == "בטיפול");
+
+    Ok(Json(TaskDependenciesResponse {
+        task_id: id,
+        is_blocked,
+        blocked_by,
+        blocking,
+    }))
+}
+
+pub async fn add_task_dependency(
+    State(state): State<AppState>,
+    auth: Option<AuthUser>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<CreateDependencyRequest>,
+) -> Result<Json<TaskDependency>, AppError> {
+    if payload.blocked_by_id == id {
+        return Err(AppError::BadRequest("A task cannot block itself".to_string()));
+    }
+
+    ensure_task_exists(&state, id).await?;
+    ensure_task_exists(&state, payload.blocked_by_id).await?;
+
+    let created_by = auth.map(|a| a.id);
+    let mut tx = state.pool.begin().await?;
+    let result = db::add_task_dependency(&mut tx, id, payload.blocked_by_id, created_by).await?;
+    tx.commit().await?;
+
+    match result {
+        AddDependencyResult::Added(dependency) => Ok(Json(dependency)),
+        AddDependencyResult::AlreadyExists => Err(AppError::BadRequest(
+            "Dependency already exists".to_string(),
+        )),
+        AddDependencyResult::WouldCreateCycle => Err(AppError::BadRequest(format!(
+            "Task {} already depends on task {}; adding this dependency would create a cycle",
+            payload.blocked_by_id, id
+        ))),
+    }
+}
+
+pub async fn remove_task_dependency(
+    State(state): State<AppState>,
+    Path((id, blocked_by_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<Value>, AppError> {
+    let removed = db::remove_task_dependency(&state.pool, id, blocked_by_id).await?;
+
+    if !removed {
+        return Err(AppError::NotFound(format!(
+            "Task {} is not blocked by task {}",
+            id, blocked_by_id
+        )));
+    }
+
+    Ok(Json(json!({
+        "message": "Dependency removed successfully",
+        "task_id": id,
+        "blocked_by_id": blocked_by_id
+    })))
+}
+
+async fn ensure_task_exists(state: &AppState, id: Uuid) -> Result<(), AppError> {
+    db::get_task_by_id(&state.pool, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Task with id {} not found", id)))?;
+
+    Ok(())
+}
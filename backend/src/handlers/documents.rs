@@ -0,0 +1,294 @@
+use axum::{
+    extract::{Path, State},
+    http::header::{CONTENT_DISPOSITION, CONTENT_TYPE},
+    response::IntoResponse,
+    Json,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::Utc;
+use serde_json::{json, Value};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    db,
+    handlers::attachments::percent_encode,
+    models::{
+        Attachment, CreateDocumentTemplateRequest, DocumentTemplate, GenerateDocumentRequest,
+        NewAttachment, UpdateDocumentTemplateRequest, DEFAULT_TIMEZONE, DOCUMENT_FORMAT_DOCX,
+    },
+    services::{
+        document_values, fill_placeholders, render_document, template_placeholders,
+        DocumentContext, DOCX_MIME_TYPE, HTML_MIME_TYPE,
+    },
+    utils::{AppError, AuthUser},
+    AppState,
+};
+
+/// Largest template upload; DOCX files come base64-encoded in JSON.
+pub const DOCUMENT_TEMPLATE_MAX_BYTES: usize = 10 * 1024 * 1024;
+
+pub async fn create_document_template(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(payload): Json<CreateDocumentTemplateRequest>,
+) -> Result<Json<DocumentTemplate>, AppError> {
+    auth.require_admin()?;
+
+    // Validate request
+    payload.validate()?;
+
+    if db::get_document_template_by_name(&state.pool, &payload.name).await?.is_some() {
+        return Err(AppError::BadRequest("Template name already exists".to_string()));
+    }
+
+    let content = template_content(&payload.format, &payload.content)?;
+    let placeholders = read_placeholders(&payload.format, &content)?;
+
+    let template =
+        db::create_document_template(&state.pool, &payload, &content, &placeholders, auth.id).await?;
+
+    Ok(Json(template))
+}
+
+pub async fn get_document_templates(
+    State(state): State<AppState>,
+    _auth: AuthUser,
+) -> Result<Json<Vec<DocumentTemplate>>, AppError> {
+    let templates = db::get_document_templates(&state.pool).await?;
+
+    Ok(Json(templates))
+}
+
+pub async fn get_document_template(
+    State(state): State<AppState>,
+    _auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<DocumentTemplate>, AppError> {
+    let template = find_document_template(&state, id).await?;
+
+    Ok(Json(template))
+}
+
+/// Downloads the template file itself, e.g. to edit it in Word.
+pub async fn download_document_template(
+    State(state): State<AppState>,
+    _auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let template = db::get_document_template_content(&state.pool, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Document template with id {} not found", id)))?;
+
+    let file_name = with_extension(&template.name, &template.format);
+    let disposition = format!("attachment; filename*=UTF-8''{}", percent_encode(&file_name));
+
+    Ok((
+        [(CONTENT_TYPE, mime_type(&template.format).to_string()), (CONTENT_DISPOSITION, disposition)],
+        template.content,
+    ))
+}
+
+pub async fn update_document_template(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateDocumentTemplateRequest>,
+) -> Result<Json<DocumentTemplate>, AppError> {
+    auth.require_admin()?;
+
+    // Validate request
+    payload.validate()?;
+
+    let existing = find_document_template(&state, id).await?;
+
+    if let Some(name) = &payload.name {
+        if let Some(other) = db::get_document_template_by_name(&state.pool, name).await? {
+            if other.id != id {
+                return Err(AppError::BadRequest("Template name already exists".to_string()));
+            }
+        }
+    }
+
+    let (content, placeholders) = match &payload.content {
+        Some(content) => {
+            let content = template_content(&existing.format, content)?;
+            let placeholders = read_placeholders(&existing.format, &content)?;
+            (Some(content), Some(placeholders))
+        }
+        None => (None, None),
+    };
+
+    let template = db::update_document_template(
+        &state.pool,
+        id,
+        &payload,
+        content.as_deref(),
+        placeholders.as_deref(),
+    )
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Document template with id {} not found", id)))?;
+
+    Ok(Json(template))
+}
+
+/// Documents already generated from the template are kept.
+pub async fn delete_document_template(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Value>, AppError> {
+    auth.require_admin()?;
+
+    if !db::deactivate_document_template(&state.pool, id).await? {
+        return Err(AppError::NotFound(format!("Document template with id {} not found", id)));
+    }
+
+    Ok(Json(json!({
+        "message": "Document template deleted successfully",
+        "id": id
+    })))
+}
+
+/// Fills a template from the task, its matter and client, the matter's
+/// lawyer and the requesting user, and saves the result as an attachment
+/// on the task. Placeholders that neither the records nor the request's
+/// variables cover are reported before anything is saved.
+pub async fn generate_task_document(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<GenerateDocumentRequest>,
+) -> Result<Json<Attachment>, AppError> {
+    // Validate request
+    payload.validate()?;
+
+    let task = db::get_task_by_id(&state.pool, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Task with id {} not found", id)))?;
+    let template = db::get_document_template_content(&state.pool, payload.template_id)
+        .await?
+        .ok_or_else(|| {
+            AppError::BadRequest(format!("Document template {} not found", payload.template_id))
+        })?;
+    let user = db::get_user_by_id(&state.pool, auth.id)
+        .await?
+        .ok_or_else(|| AppError::Unauthorized("User no longer exists".to_string()))?;
+
+    let matter = match task.matter_id {
+        Some(matter_id) => db::get_matter_by_id(&state.pool, matter_id).await?,
+        None => None,
+    };
+    let client = match &matter {
+        Some(matter) => db::get_client_by_id(&state.pool, matter.client_id).await?,
+        None => None,
+    };
+    let lawyer = match matter.as_ref().and_then(|m| m.responsible_lawyer_id) {
+        Some(lawyer_id) => db::get_user_by_id(&state.pool, lawyer_id).await?,
+        None => None,
+    };
+
+    let mut values = document_values(&DocumentContext {
+        task: &task,
+        matter: matter.as_ref(),
+        client: client.as_ref(),
+        lawyer: lawyer.as_ref(),
+        user: &user,
+        office_name: &state.config.invoice_issuer_name,
+        office_tax_id: &state.config.invoice_issuer_tax_id,
+        today: Utc::now().with_timezone(&DEFAULT_TIMEZONE).date_naive(),
+    });
+    values.extend(payload.variables.clone());
+
+    let missing: Vec<&str> = template
+        .placeholders
+        .iter()
+        .filter(|name| !values.contains_key(*name))
+        .map(String::as_str)
+        .collect();
+    if !missing.is_empty() {
+        return Err(AppError::BadRequest(format!(
+            "Missing template variables: {}",
+            missing.join(", ")
+        )));
+    }
+
+    let file_name = payload.file_name.as_deref().unwrap_or(&template.output_file_name);
+    let file_name = fill_placeholders(file_name, &values).map_err(|missing| {
+        AppError::BadRequest(format!("Missing template variables: {}", missing.join(", ")))
+    })?;
+    let file_name = with_extension(&safe_file_name(&file_name), &template.format);
+
+    let content = render_document(&template.format, &template.content, &file_name, &values)?;
+    let document = NewAttachment {
+        file_name,
+        mime_type: Some(mime_type(&template.format).to_string()),
+        content,
+    };
+
+    let mut tx = state.pool.begin().await?;
+    let attachment =
+        db::create_attachment(&mut tx, task.id, &document, Some(auth.id), &state.config.api_base_url)
+            .await?;
+    tx.commit().await?;
+
+    Ok(Json(attachment))
+}
+
+/// The template as stored: HTML as is, DOCX decoded from base64.
+fn template_content(format: &str, content: &str) -> Result<Vec<u8>, AppError> {
+    if format != DOCUMENT_FORMAT_DOCX {
+        return Ok(content.as_bytes().to_vec());
+    }
+
+    STANDARD
+        .decode(content.trim())
+        .map_err(|_| AppError::BadRequest("DOCX content must be base64-encoded".to_string()))
+}
+
+fn read_placeholders(format: &str, content: &[u8]) -> Result<Vec<String>, AppError> {
+    template_placeholders(format, content)
+        .map_err(|e| AppError::BadRequest(format!("Invalid {} template: {}", format, e)))
+}
+
+fn mime_type(format: &str) -> &'static str {
+    if format == DOCUMENT_FORMAT_DOCX {
+        DOCX_MIME_TYPE
+    } else {
+        HTML_MIME_TYPE
+    }
+}
+
+fn with_extension(name: &str, format: &str) -> String {
+    let extension = format!(".{}", format);
+    if name.to_lowercase().ends_with(&extension) {
+        name.to_string()
+    } else {
+        format!("{}{}", name, extension)
+    }
+}
+
+/// Client names and case numbers often have slashes, e.g. `ת"א 1234/05`.
+fn safe_file_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '-',
+            c if c.is_control() => ' ',
+            c => c,
+        })
+        .collect();
+    let name = name.trim().trim_matches('.').trim();
+
+    if name.is_empty() {
+        "document".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+async fn find_document_template(state: &AppState, id: Uuid) -> Result<DocumentTemplate, AppError> {
+    db::get_document_template_by_id(&state.pool, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Document template with id {} not found", id)))
+}
@@ -0,0 +1,89 @@
+use axum::{
+    extract::{Query, State},
+    http::HeaderMap,
+    response::sse::{Event, KeepAlive, Sse},
+};
+use futures_util::{future, stream, Stream, StreamExt};
+use std::{collections::HashSet, sync::Arc};
+use tokio_stream::wrappers::BroadcastStream;
+
+use crate::{
+    db,
+    models::{EventsQuery, TaskEvent},
+    utils::{verify_jwt, AppError, AuthUser},
+    AppState,
+};
+
+/// Most missed events replayed on resume; further behind, the client is told
+/// to reload instead.
+const REPLAY_LIMIT: i64 = 500;
+
+/// Server-Sent Events stream of task and comment changes the caller may see.
+/// Admins see everything, other users the tasks assigned to or created by
+/// them. A `resync` event means the client missed too much and should reload.
+pub async fn get_task_events(
+    State(state): State<AppState>,
+    auth: Option<AuthUser>,
+    headers: HeaderMap,
+    Query(params): Query<EventsQuery>,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, AppError> {
+    // EventSource cannot send headers, so the token may come in the query string
+    let auth = match auth {
+        Some(auth) => auth,
+        None => {
+            let token = params
+                .token
+                .as_deref()
+                .ok_or_else(|| AppError::Unauthorized("Missing authentication token".to_string()))?;
+            AuthUser::try_from(verify_jwt(token, &state.config.jwt_secret)?)?
+        }
+    };
+
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<i64>().ok())
+        .or(params.last_event_id);
+
+    // Subscribe before replaying so nothing committed in between is lost
+    let receiver = state.task_events.subscribe();
+
+    let mut replayed = match last_event_id {
+        Some(id) => db::get_task_events_after(&state.pool, id, REPLAY_LIMIT + 1).await?,
+        None => Vec::new(),
+    };
+    let resync = replayed.len() as i64 > REPLAY_LIMIT;
+    if resync {
+        replayed.clear();
+    }
+    let replayed_ids: HashSet<i64> = replayed.iter().map(|e| e.id).collect();
+
+    let is_admin = auth.is_admin();
+    let email = auth.email;
+    let visible = move |event: &Arc<TaskEvent>| is_admin || event.is_visible_to(&email);
+
+    // A client that lags behind the buffer is disconnected and resumes from
+    // its Last-Event-ID
+    let live = BroadcastStream::new(receiver)
+        .take_while(|received| future::ready(received.is_ok()))
+        .filter_map(move |received| {
+            future::ready(received.ok().filter(|event| !replayed_ids.contains(&event.id)))
+        });
+
+    let events = stream::iter(replayed.into_iter().map(Arc::new))
+        .chain(live)
+        .filter(move |event| future::ready(visible(event)))
+        .map(|event| to_sse_event(&event));
+
+    let resync_event = resync.then(|| Ok(Event::default().event("resync").data("{}")));
+    let stream = stream::iter(resync_event).chain(events);
+
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
+fn to_sse_event(event: &TaskEvent) -> Result<Event, axum::Error> {
+    Event::default()
+        .id(event.id.to_string())
+        .event(&event.event_type)
+        .json_data(event)
+}
@@ -0,0 +1,190 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use chrono::{Duration, Utc};
+use serde_json::{json, Value};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    db,
+    models::{
+        CreateAuditLogRequest, CreateMatterExpiryRequest, ExpiryReportQuery, MatterExpiry,
+        ResolveMatterExpiryRequest, UpcomingExpiry, UpdateMatterExpiryRequest, DEFAULT_TIMEZONE,
+    },
+    utils::{AppError, AuthUser},
+    AppState,
+};
+
+/// Audit log entity type of changes to expiry dates.
+const MATTER_EXPIRY_ENTITY: &str = "matter_expiry";
+
+/// Default look-ahead of the expiry report, in days.
+const EXPIRY_REPORT_DAYS: i64 = 90;
+
+pub async fn get_matter_expiries(
+    State(state): State<AppState>,
+    _auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<MatterExpiry>>, AppError> {
+    check_matter(&state, id).await?;
+
+    let expiries = db::get_matter_expiries(&state.pool, id).await?;
+
+    Ok(Json(expiries))
+}
+
+pub async fn create_matter_expiry(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<CreateMatterExpiryRequest>,
+) -> Result<Json<MatterExpiry>, AppError> {
+    // Validate request
+    payload.validate()?;
+
+    check_matter(&state, id).await?;
+
+    let expiry = db::create_matter_expiry(&state.pool, id, &payload, auth.id).await?;
+    record_expiry_audit(&state, auth.id, &expiry, "create", None).await;
+
+    Ok(Json(expiry))
+}
+
+/// Changing the date starts its reminders over.
+pub async fn update_matter_expiry(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((id, expiry_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateMatterExpiryRequest>,
+) -> Result<Json<MatterExpiry>, AppError> {
+    // Validate request
+    payload.validate()?;
+
+    let before = find_expiry(&state, id, expiry_id).await?;
+    let expiry = db::update_matter_expiry(&state.pool, id, expiry_id, &payload)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Expiry with id {} not found", expiry_id)))?;
+
+    let changes = json!({
+        "matter_id": id,
+        "before": {
+            "expiry_type": before.expiry_type,
+            "expires_on": before.expires_on,
+            "basis": before.basis,
+        },
+    });
+    record_expiry_audit(&state, auth.id, &expiry, "update", Some(changes)).await;
+
+    Ok(Json(expiry))
+}
+
+/// Marks the expiry as dealt with, e.g. the claim was filed, which stops
+/// its reminders and takes it off the report.
+pub async fn resolve_matter_expiry(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((id, expiry_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<ResolveMatterExpiryRequest>,
+) -> Result<Json<MatterExpiry>, AppError> {
+    // Validate request
+    payload.validate()?;
+
+    let existing = find_expiry(&state, id, expiry_id).await?;
+    if existing.resolved_at.is_some() {
+        return Err(AppError::BadRequest("Expiry is already resolved".to_string()));
+    }
+
+    let expiry =
+        db::resolve_matter_expiry(&state.pool, id, expiry_id, &payload.resolution, auth.id)
+            .await?
+            .ok_or_else(|| AppError::BadRequest("Expiry is already resolved".to_string()))?;
+    record_expiry_audit(&state, auth.id, &expiry, "resolve", None).await;
+
+    Ok(Json(expiry))
+}
+
+pub async fn delete_matter_expiry(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((id, expiry_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<Value>, AppError> {
+    let expiry = find_expiry(&state, id, expiry_id).await?;
+
+    if !db::delete_matter_expiry(&state.pool, id, expiry_id).await? {
+        return Err(AppError::NotFound(format!("Expiry with id {} not found", expiry_id)));
+    }
+    record_expiry_audit(&state, auth.id, &expiry, "delete", None).await;
+
+    Ok(Json(json!({
+        "message": "Expiry deleted successfully",
+        "id": expiry_id
+    })))
+}
+
+/// Every unresolved limitation, renewal and expiry date across the office
+/// that falls within `days` (90 by default), soonest first, together with
+/// those already past.
+pub async fn get_expiry_report(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Query(params): Query<ExpiryReportQuery>,
+) -> Result<Json<Vec<UpcomingExpiry>>, AppError> {
+    auth.require_admin()?;
+
+    let days = params.days.unwrap_or(EXPIRY_REPORT_DAYS);
+    if !(0..=3650).contains(&days) {
+        return Err(AppError::BadRequest("days must be between 0 and 3650".to_string()));
+    }
+
+    let today = Utc::now().with_timezone(&DEFAULT_TIMEZONE).date_naive();
+    let expiries = db::get_upcoming_expiries(
+        &state.pool,
+        today,
+        today + Duration::days(days),
+        params.expiry_type.as_deref(),
+        params.lawyer_id,
+    )
+    .await?;
+
+    Ok(Json(expiries))
+}
+
+/// Dates on which rights are lost are kept track of for the insurer, so
+/// every change is written to the audit log.
+async fn record_expiry_audit(
+    state: &AppState,
+    actor_id: Uuid,
+    expiry: &MatterExpiry,
+    action: &str,
+    changes: Option<Value>,
+) {
+    let req = CreateAuditLogRequest {
+        user_id: Some(actor_id),
+        action: action.to_string(),
+        entity_type: MATTER_EXPIRY_ENTITY.to_string(),
+        entity_id: expiry.id,
+        changes: changes.or_else(|| Some(json!({ "matter_id": expiry.matter_id, "expiry": expiry }))),
+        ip_address: None,
+        user_agent: None,
+    };
+
+    if let Err(e) = db::create_audit_log(&state.pool, &req).await {
+        tracing::error!("Failed to write audit log: {:?}", e);
+    }
+}
+
+async fn check_matter(state: &AppState, id: Uuid) -> Result<(), AppError> {
+    db::get_matter_by_id(&state.pool, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Matter with id {} not found", id)))?;
+
+    Ok(())
+}
+
+async fn find_expiry(state: &AppState, matter_id: Uuid, id: Uuid) -> Result<MatterExpiry, AppError> {
+    db::get_matter_expiry(&state.pool, matter_id, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Expiry with id {} not found", id)))
+}
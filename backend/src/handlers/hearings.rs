@@ -0,0 +1,393 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde_json::{json, Value};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    db,
+    handlers::tasks::{insert_task, record_task_update, to_responses},
+    models::{
+        CancelHearingRequest, CancelHearingResponse, CreateHearingRequest,
+        CreatePreparationStepRequest, CreateTaskRequest, Hearing, HearingPreparationStep,
+        HearingResponse, HearingsQuery, Matter, Task, UpdateHearingRequest, UpdateTaskRequest, User,
+        DEFAULT_TIMEZONE, HEARING_CANCELLED, HEARING_SCHEDULED, MATTER_CLOSED,
+    },
+    services::CourtCalendar,
+    utils::{AppError, AuthUser},
+    AppState,
+};
+
+/// Schedules a hearing and creates its standard preparation tasks, each due
+/// the step's number of days before the hearing.
+pub async fn create_hearing(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(payload): Json<CreateHearingRequest>,
+) -> Result<Json<HearingResponse>, AppError> {
+    // Validate request
+    payload.validate()?;
+
+    let matter = db::get_matter_by_id(&state.pool, payload.matter_id)
+        .await?
+        .ok_or_else(|| AppError::BadRequest(format!("Matter {} not found", payload.matter_id)))?;
+    if matter.status == MATTER_CLOSED {
+        return Err(AppError::BadRequest(format!(
+            "Matter {} is closed",
+            matter.matter_number
+        )));
+    }
+
+    let creator = db::get_user_by_id(&state.pool, auth.id)
+        .await?
+        .ok_or_else(|| AppError::Unauthorized("User no longer exists".to_string()))?;
+    let (assigned_to, assigned_to_email) = preparation_assignee(&state, &payload, &matter, &creator).await?;
+
+    let steps = if payload.create_tasks.unwrap_or(true) {
+        db::get_preparation_steps_for(&state.pool, &payload.hearing_type).await?
+    } else {
+        Vec::new()
+    };
+
+    let court = payload.court.as_deref().or(matter.court.as_deref());
+
+    let mut tx = state.pool.begin().await?;
+
+    let hearing = db::create_hearing(&mut *tx, &payload, court, auth.id).await?;
+
+    let mut calendar = CourtCalendar::default();
+    let mut tasks: Vec<Task> = Vec::new();
+    for step in steps.iter() {
+        let request = CreateTaskRequest {
+            title: format!("{} – {} ({})", step.title, hearing.hearing_type, matter.matter_number),
+            description: Some(hearing_description(&hearing)),
+            category: step.category.clone(),
+            assigned_to: assigned_to.clone(),
+            assigned_to_email: assigned_to_email.clone(),
+            created_by: creator.name.clone(),
+            created_by_email: creator.email.clone(),
+            due_date: Some(preparation_due_date(&mut calendar, hearing.scheduled_at, step.days_before)),
+            priority: Some(step.priority.clone()),
+            attachments_folder_url: None,
+            notes: None,
+            parent_id: None,
+            matter_id: Some(matter.id),
+            series_id: None,
+            occurrence_date: None,
+        };
+        request.validate()?;
+
+        let task = insert_task(&state, &mut tx, &request).await?;
+        db::link_hearing_task(&mut *tx, hearing.id, task.id, step.days_before).await?;
+        tasks.push(task);
+    }
+
+    tx.commit().await?;
+
+    let today = Utc::now().with_timezone(&DEFAULT_TIMEZONE).date_naive();
+    let overdue = tasks
+        .iter()
+        .filter(|t| t.due_date.map_or(false, |d| d < today))
+        .count();
+
+    let mut warnings = Vec::new();
+    if overdue > 0 {
+        warnings.push(format!(
+            "{} preparation task(s) are already past their due date",
+            overdue
+        ));
+    }
+
+    Ok(Json(HearingResponse {
+        hearing,
+        tasks: to_responses(&state, tasks).await?,
+        warnings,
+    }))
+}
+
+pub async fn get_hearings(
+    State(state): State<AppState>,
+    _auth: AuthUser,
+    Query(params): Query<HearingsQuery>,
+) -> Result<Json<Vec<Hearing>>, AppError> {
+    let hearings = db::get_hearings(&state.pool, &params).await?;
+
+    Ok(Json(hearings))
+}
+
+pub async fn get_matter_hearings(
+    State(state): State<AppState>,
+    _auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<Hearing>>, AppError> {
+    db::get_matter_by_id(&state.pool, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Matter with id {} not found", id)))?;
+
+    let params = HearingsQuery {
+        matter_id: Some(id),
+        include_cancelled: true,
+        ..Default::default()
+    };
+    let hearings = db::get_hearings(&state.pool, &params).await?;
+
+    Ok(Json(hearings))
+}
+
+pub async fn get_hearing(
+    State(state): State<AppState>,
+    _auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<HearingResponse>, AppError> {
+    let hearing = find_hearing(&state, id).await?;
+    let tasks = db::get_hearing_tasks(&state.pool, id).await?;
+
+    Ok(Json(HearingResponse {
+        hearing,
+        tasks: to_responses(&state, tasks).await?,
+        warnings: Vec::new(),
+    }))
+}
+
+/// Updates a hearing. Moving it to another day moves its open preparation
+/// tasks by the same offsets; finished tasks are left alone.
+pub async fn update_hearing(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateHearingRequest>,
+) -> Result<Json<HearingResponse>, AppError> {
+    // Validate request
+    payload.validate()?;
+
+    let existing = find_hearing(&state, id).await?;
+    if existing.status == HEARING_CANCELLED {
+        return Err(AppError::BadRequest("Hearing is cancelled".to_string()));
+    }
+
+    let mut tx = state.pool.begin().await?;
+
+    let hearing = db::update_hearing(&mut *tx, id, &payload)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Hearing with id {} not found", id)))?;
+
+    if hearing.scheduled_at != existing.scheduled_at && hearing.status == HEARING_SCHEDULED {
+        let mut calendar = CourtCalendar::default();
+        for link in db::get_open_hearing_task_links(&mut *tx, id).await? {
+            let task = match db::get_task_by_id(&state.pool, link.task_id).await? {
+                Some(task) => task,
+                None => continue,
+            };
+
+            let due_date = preparation_due_date(&mut calendar, hearing.scheduled_at, link.days_before);
+            if task.due_date == Some(due_date) {
+                continue;
+            }
+
+            let moved = db::set_task_due_date(&mut *tx, task.id, due_date).await?;
+            record_task_update(&state, &mut tx, &task, &moved, Some(&auth.email)).await?;
+        }
+    }
+
+    tx.commit().await?;
+
+    let tasks = db::get_hearing_tasks(&state.pool, id).await?;
+
+    Ok(Json(HearingResponse {
+        hearing,
+        tasks: to_responses(&state, tasks).await?,
+        warnings: Vec::new(),
+    }))
+}
+
+/// Cancels a hearing. Its open preparation tasks are cancelled too when
+/// asked; otherwise they are returned so the caller can offer to.
+pub async fn cancel_hearing(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<CancelHearingRequest>,
+) -> Result<Json<CancelHearingResponse>, AppError> {
+    let existing = find_hearing(&state, id).await?;
+    if existing.status == HEARING_CANCELLED && !payload.cancel_tasks {
+        return Err(AppError::BadRequest("Hearing is already cancelled".to_string()));
+    }
+
+    let mut tx = state.pool.begin().await?;
+
+    // Cancelling again only cancels the tasks that were left open
+    let hearing = if existing.status == HEARING_CANCELLED {
+        existing
+    } else {
+        db::cancel_hearing(&mut *tx, id).await?
+    };
+
+    let mut cancelled_tasks = Vec::new();
+    if payload.cancel_tasks {
+        let cancel = UpdateTaskRequest {
+            status: Some("בוטלה".to_string()),
+            ..Default::default()
+        };
+
+        for link in db::get_open_hearing_task_links(&mut *tx, id).await? {
+            let task = match db::get_task_by_id(&state.pool, link.task_id).await? {
+                Some(task) => task,
+                None => continue,
+            };
+
+            let cancelled = db::update_task(&mut *tx, task.id, &cancel).await?;
+            record_task_update(&state, &mut tx, &task, &cancelled, Some(&auth.email)).await?;
+            cancelled_tasks.push(cancelled);
+        }
+    }
+
+    tx.commit().await?;
+
+    let open_tasks = db::get_hearing_tasks(&state.pool, id)
+        .await?
+        .into_iter()
+        .filter(|t| matches!(t.status.as_str(), "חדשה" | "בטיפול"))
+        .collect();
+
+    Ok(Json(CancelHearingResponse {
+        hearing,
+        cancelled_tasks: to_responses(&state, cancelled_tasks).await?,
+        open_tasks: to_responses(&state, open_tasks).await?,
+    }))
+}
+
+/// Deletes a hearing entered by mistake; its tasks are kept.
+pub async fn delete_hearing(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Value>, AppError> {
+    auth.require_admin()?;
+
+    if !db::delete_hearing(&state.pool, id).await? {
+        return Err(AppError::NotFound(format!("Hearing with id {} not found", id)));
+    }
+
+    Ok(Json(json!({
+        "message": "Hearing deleted successfully",
+        "id": id
+    })))
+}
+
+pub async fn get_preparation_steps(
+    State(state): State<AppState>,
+    _auth: AuthUser,
+) -> Result<Json<Vec<HearingPreparationStep>>, AppError> {
+    let steps = db::get_all_preparation_steps(&state.pool).await?;
+
+    Ok(Json(steps))
+}
+
+pub async fn create_preparation_step(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(payload): Json<CreatePreparationStepRequest>,
+) -> Result<Json<HearingPreparationStep>, AppError> {
+    auth.require_admin()?;
+
+    // Validate request
+    payload.validate()?;
+
+    let step = db::create_preparation_step(&state.pool, &payload).await?;
+
+    Ok(Json(step))
+}
+
+pub async fn delete_preparation_step(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Value>, AppError> {
+    auth.require_admin()?;
+
+    if !db::delete_preparation_step(&state.pool, id).await? {
+        return Err(AppError::NotFound(format!("Preparation step with id {} not found", id)));
+    }
+
+    Ok(Json(json!({
+        "message": "Preparation step deleted successfully",
+        "id": id
+    })))
+}
+
+/// The assignee given in the request, else the matter's responsible lawyer,
+/// else the caller.
+async fn preparation_assignee(
+    state: &AppState,
+    payload: &CreateHearingRequest,
+    matter: &Matter,
+    creator: &User,
+) -> Result<(String, String), AppError> {
+    match (&payload.assigned_to, &payload.assigned_to_email) {
+        (Some(name), Some(email)) => return Ok((name.clone(), email.clone())),
+        (None, None) => {}
+        _ => {
+            return Err(AppError::BadRequest(
+                "assigned_to and assigned_to_email must be given together".to_string(),
+            ))
+        }
+    }
+
+    if let Some(lawyer_id) = matter.responsible_lawyer_id {
+        if let Some(lawyer) = db::get_user_by_id(&state.pool, lawyer_id).await? {
+            if lawyer.is_active {
+                return Ok((lawyer.name, lawyer.email));
+            }
+        }
+    }
+
+    Ok((creator.name.clone(), creator.email.clone()))
+}
+
+/// `days_before` the hearing's date in Israel, brought forward off weekends
+/// and holidays so the work is due on a day the office is open.
+fn preparation_due_date(
+    calendar: &mut CourtCalendar,
+    scheduled_at: DateTime<Utc>,
+    days_before: i32,
+) -> NaiveDate {
+    let hearing_date = scheduled_at.with_timezone(&DEFAULT_TIMEZONE).date_naive();
+    let mut date = hearing_date - Duration::days(days_before as i64);
+
+    while calendar.closure(date).is_some() {
+        date -= Duration::days(1);
+    }
+
+    date
+}
+
+fn hearing_description(hearing: &Hearing) -> String {
+    let local = hearing.scheduled_at.with_timezone(&DEFAULT_TIMEZONE);
+    let mut description = format!(
+        "הכנה לדיון {} בתאריך {} בשעה {}",
+        hearing.hearing_type,
+        local.format("%d/%m/%Y"),
+        local.format("%H:%M")
+    );
+
+    if let Some(court) = &hearing.court {
+        description.push_str(&format!(", {}", court));
+    }
+    if let Some(judge) = &hearing.judge {
+        description.push_str(&format!(", בפני {}", judge));
+    }
+    if let Some(courtroom) = &hearing.courtroom {
+        description.push_str(&format!(", אולם {}", courtroom));
+    }
+
+    description
+}
+
+async fn find_hearing(state: &AppState, id: Uuid) -> Result<Hearing, AppError> {
+    db::get_hearing_by_id(&state.pool, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Hearing with id {} not found", id)))
+}
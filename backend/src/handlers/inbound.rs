@@ -0,0 +1,293 @@
+use axum::{
+    body::Bytes,
+    extract::{Query, State},
+    http::HeaderMap,
+    Json,
+};
+use serde_json::json;
+use sqlx::PgConnection;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    db,
+    handlers::{comments::{post_task_comment, record_comment_audit}, tasks::insert_task},
+    models::{
+        CreateTaskRequest, InboundEmail, InboundEmailResponse, InboundEmailsQuery, InboundOutcome,
+        NewAttachment, NewInboundEmail, TaskComment,
+    },
+    services::{
+        constant_time_eq, parse_email, sender_authenticated, strip_quoted_reply,
+        task_id_from_reply_address,
+        ParsedEmail, MAX_REPLY_CHARS,
+    },
+    utils::{AppError, AuthUser},
+    AppState,
+};
+
+/// Largest raw message accepted, attachments included.
+pub const INBOUND_EMAIL_MAX_BYTES: usize = 30 * 1024 * 1024;
+
+const INBOUND_OUTCOMES: [&str; 3] = ["comment", "task", "rejected"];
+
+/// Subject prefixes dropped from the title of tasks created by email.
+const FORWARD_PREFIXES: [&str; 5] = ["fwd:", "fw:", "re:", "הועבר:", "השב:"];
+
+/// Accepts a raw RFC 822 message from the local MTA or a mail-forwarding
+/// hook, authenticated by the `X-Inbound-Secret` header.
+///
+/// A reply to a notification (sent to its `reply+...` address) becomes a
+/// comment on the task, without the quoted history and signature. A message
+/// to the intake address becomes a new task assigned to its sender. Either
+/// way the sender must be an active user, vouched for by the MTA's
+/// `Authentication-Results`, and attachments are saved on the task. Anything
+/// else is logged as rejected; the response is still 200 so that the MTA
+/// does not retry it.
+pub async fn receive_inbound_email(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<InboundEmailResponse>, AppError> {
+    let secret = &state.config.inbound_email_secret;
+    if secret.is_empty() {
+        return Err(AppError::NotFound("Inbound email is not configured".to_string()));
+    }
+
+    let provided = headers
+        .get("x-inbound-secret")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("");
+    if !constant_time_eq(provided, secret) {
+        return Err(AppError::Unauthorized("Invalid inbound email secret".to_string()));
+    }
+
+    let email = parse_email(&body).map_err(|e| AppError::BadRequest(e.to_string()))?;
+
+    // The MTA may deliver the same message more than once
+    if let Some(existing) = db::get_inbound_email_by_message_id(&state.pool, &email.message_id).await? {
+        return Ok(Json(InboundEmailResponse {
+            email: existing,
+            duplicate: true,
+        }));
+    }
+
+    let mut record = NewInboundEmail {
+        message_id: email.message_id.clone(),
+        sender: email.sender.clone(),
+        subject: email.subject.clone(),
+        outcome: InboundOutcome::Rejected,
+        reason: None,
+        task_id: None,
+        comment_id: None,
+        attachments_saved: 0,
+    };
+
+    let mut tx = state.pool.begin().await?;
+
+    let comment = process_email(&state, &mut tx, &email, &mut record).await?;
+
+    let recorded = match db::record_inbound_email(&mut *tx, &record).await? {
+        Some(recorded) => recorded,
+        None => {
+            // A concurrent delivery of the same message got there first
+            tx.rollback().await?;
+            let existing = db::get_inbound_email_by_message_id(&state.pool, &email.message_id)
+                .await?
+                .ok_or_else(|| AppError::InternalServerError("Inbound email vanished".to_string()))?;
+            return Ok(Json(InboundEmailResponse {
+                email: existing,
+                duplicate: true,
+            }));
+        }
+    };
+
+    tx.commit().await?;
+
+    match &comment {
+        Some((author_id, comment)) => {
+            let changes = json!({
+                "task_id": comment.task_id,
+                "source": "email",
+                "message_id": email.message_id,
+            });
+            record_comment_audit(&state, *author_id, comment, "comment_created", Some(changes)).await;
+        }
+        None if record.outcome == InboundOutcome::Rejected => {
+            tracing::info!(
+                "Rejected inbound email {} from {:?}: {}",
+                email.message_id,
+                email.sender,
+                record.reason.as_deref().unwrap_or("")
+            );
+        }
+        None => {}
+    }
+
+    Ok(Json(InboundEmailResponse {
+        email: recorded,
+        duplicate: false,
+    }))
+}
+
+pub async fn get_inbound_emails(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Query(params): Query<InboundEmailsQuery>,
+) -> Result<Json<Vec<InboundEmail>>, AppError> {
+    auth.require_admin()?;
+
+    if let Some(outcome) = &params.outcome {
+        if !INBOUND_OUTCOMES.contains(&outcome.as_str()) {
+            return Err(AppError::BadRequest(format!("Invalid outcome: {}", outcome)));
+        }
+    }
+    let limit = params.limit.unwrap_or(50).clamp(1, 500);
+
+    let emails = db::get_inbound_emails(&state.pool, params.outcome.as_deref(), limit).await?;
+
+    Ok(Json(emails))
+}
+
+/// Turns the message into a comment or a task, filling in `record`; a
+/// rejected message only gets a reason. Returns the comment's author and
+/// the comment, for the audit log.
+async fn process_email(
+    state: &AppState,
+    conn: &mut PgConnection,
+    email: &ParsedEmail,
+    record: &mut NewInboundEmail,
+) -> Result<Option<(Uuid, TaskComment)>, AppError> {
+    // Out-of-office replies to notifications must not pile up as comments
+    if email.is_auto_reply {
+        return reject(record, "Automatic reply");
+    }
+
+    let sender = match &email.sender {
+        Some(sender) => sender,
+        None => return reject(record, "No sender address"),
+    };
+    if sender.eq_ignore_ascii_case(&state.config.smtp_username) {
+        return reject(record, "Sent by this system");
+    }
+
+    // The sender becomes the author, so the From: address must be genuine
+    if !sender_authenticated(&email.authentication_results, &state.config.inbound_authserv_id, sender) {
+        return reject(record, "Sender could not be authenticated");
+    }
+
+    let author = match db::get_user_by_email_ignore_case(&state.pool, sender).await? {
+        Some(user) if user.is_active => user,
+        _ => return reject(record, "Sender is not an active user"),
+    };
+
+    // A reply to a task notification
+    let domain = &state.config.inbound_email_domain;
+    let reply_task = (!domain.is_empty())
+        .then(|| {
+            email.recipients.iter().find_map(|r| {
+                task_id_from_reply_address(&state.config.inbound_email_secret, domain, r)
+            })
+        })
+        .flatten();
+
+    if let Some(task_id) = reply_task {
+        let task = match db::get_task_by_id(&state.pool, task_id).await? {
+            Some(task) => task,
+            None => return reject(record, "Task no longer exists"),
+        };
+
+        let mut body: String = strip_quoted_reply(&email.text)
+            .chars()
+            .take(MAX_REPLY_CHARS)
+            .collect();
+        if body.is_empty() {
+            if email.attachments.is_empty() {
+                return reject(record, "Reply is empty");
+            }
+            body = "(קבצים צורפו בדוא\"ל)".to_string();
+        }
+
+        let comment = post_task_comment(state, conn, &task, &author, &body).await?;
+        record.attachments_saved =
+            save_attachments(state, conn, task.id, &email.attachments, author.id).await?;
+        record.outcome = InboundOutcome::Comment;
+        record.task_id = Some(task.id);
+        record.comment_id = Some(comment.id);
+
+        return Ok(Some((author.id, comment)));
+    }
+
+    // A new task sent to the intake address
+    let intake = &state.config.inbound_intake_address;
+    if !intake.is_empty() && email.recipients.iter().any(|r| r.eq_ignore_ascii_case(intake)) {
+        let title: String = task_title(email.subject.as_deref()).chars().take(500).collect();
+        let description = Some(email.text.trim().to_string()).filter(|d| !d.is_empty());
+
+        let payload = CreateTaskRequest {
+            title,
+            description,
+            category: state.config.inbound_intake_category.clone(),
+            assigned_to: author.name.clone(),
+            assigned_to_email: author.email.clone(),
+            created_by: author.name.clone(),
+            created_by_email: author.email.clone(),
+            due_date: None,
+            priority: None,
+            attachments_folder_url: None,
+            notes: None,
+            parent_id: None,
+            matter_id: None,
+            series_id: None,
+            occurrence_date: None,
+        };
+        payload.validate()?;
+
+        let task = insert_task(state, conn, &payload).await?;
+        record.attachments_saved =
+            save_attachments(state, conn, task.id, &email.attachments, author.id).await?;
+        record.outcome = InboundOutcome::Task;
+        record.task_id = Some(task.id);
+
+        return Ok(None);
+    }
+
+    reject(record, "Not addressed to a reply address or the intake address")
+}
+
+fn reject(record: &mut NewInboundEmail, reason: &str) -> Result<Option<(Uuid, TaskComment)>, AppError> {
+    record.reason = Some(reason.to_string());
+    Ok(None)
+}
+
+async fn save_attachments(
+    state: &AppState,
+    conn: &mut PgConnection,
+    task_id: Uuid,
+    attachments: &[NewAttachment],
+    uploaded_by: Uuid,
+) -> Result<i32, AppError> {
+    for attachment in attachments {
+        db::create_attachment(conn, task_id, attachment, Some(uploaded_by), &state.config.api_base_url)
+            .await?;
+    }
+
+    Ok(attachments.len() as i32)
+}
+
+/// The subject without forwarding prefixes, or a placeholder title.
+fn task_title(subject: Option<&str>) -> String {
+    let mut title = subject.unwrap_or("").trim();
+
+    while let Some(prefix) = FORWARD_PREFIXES
+        .iter()
+        .find(|p| title.to_lowercase().starts_with(*p))
+    {
+        title = title[prefix.len()..].trim_start();
+    }
+
+    if title.is_empty() {
+        "משימה מדוא\"ל".to_string()
+    } else {
+        title.to_string()
+    }
+}
@@ -0,0 +1,235 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::header::CONTENT_TYPE,
+    response::IntoResponse,
+    Json,
+};
+use chrono::{Datelike, Utc};
+use serde_json::{json, Value};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    db,
+    models::{
+        CreateInvoiceRequest, Invoice, InvoiceLine, InvoiceResponse, InvoicesQuery,
+        DEFAULT_TIMEZONE, INVOICE_DRAFT, INVOICE_ISSUED,
+    },
+    services::{build_invoice_lines, render_invoice_html, vat_amount, InvoiceIssuer},
+    utils::{AppError, AuthUser},
+    AppState,
+};
+
+/// Drafts an invoice from the billable time in the period that is not yet
+/// invoiced, with one line per matter, task and rate. The entries are locked
+/// from here on; deleting the draft releases them.
+pub async fn create_invoice(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(payload): Json<CreateInvoiceRequest>,
+) -> Result<Json<InvoiceResponse>, AppError> {
+    auth.require_admin()?;
+
+    // Validate request
+    payload.validate()?;
+
+    if payload.period_from > payload.period_to {
+        return Err(AppError::BadRequest("period_from must not be after period_to".to_string()));
+    }
+
+    let client_id = match (payload.client_id, payload.matter_id) {
+        (client_id, Some(matter_id)) => {
+            let matter = db::get_matter_by_id(&state.pool, matter_id)
+                .await?
+                .ok_or_else(|| AppError::BadRequest(format!("Matter {} not found", matter_id)))?;
+            if let Some(client_id) = client_id {
+                if client_id != matter.client_id {
+                    return Err(AppError::BadRequest(format!(
+                        "Matter {} does not belong to client {}",
+                        matter.matter_number, client_id
+                    )));
+                }
+            }
+            matter.client_id
+        }
+        (Some(client_id), None) => {
+            db::get_client_by_id(&state.pool, client_id)
+                .await?
+                .ok_or_else(|| AppError::BadRequest(format!("Client {} not found", client_id)))?;
+            client_id
+        }
+        (None, None) => {
+            return Err(AppError::BadRequest("Give a client_id or a matter_id".to_string()));
+        }
+    };
+
+    let vat_rate = payload
+        .vat_rate_basis_points
+        .unwrap_or_else(|| state.config.vat_rate_basis_points());
+
+    let mut tx = state.pool.begin().await?;
+
+    let entries = db::lock_invoiceable_entries(&mut *tx, client_id, &payload).await?;
+    if entries.is_empty() {
+        return Err(AppError::BadRequest(
+            "No uninvoiced billable time in this period".to_string(),
+        ));
+    }
+
+    let new_lines = build_invoice_lines(&entries);
+    let subtotal: i64 = new_lines.iter().map(|l| l.amount_agorot).sum();
+
+    let invoice = db::create_invoice(
+        &mut *tx,
+        client_id,
+        &payload,
+        subtotal,
+        vat_rate,
+        vat_amount(subtotal, vat_rate),
+        auth.id,
+    )
+    .await?;
+
+    let mut lines = Vec::with_capacity(new_lines.len());
+    for (position, line) in new_lines.iter().enumerate() {
+        lines.push(db::create_invoice_line(&mut *tx, invoice.id, position as i32 + 1, line).await?);
+    }
+
+    let entry_ids: Vec<Uuid> = entries.iter().map(|e| e.entry.id).collect();
+    db::assign_time_entries_to_invoice(&mut *tx, invoice.id, &entry_ids).await?;
+
+    tx.commit().await?;
+
+    let mut warnings = Vec::new();
+    let unrated = entries.iter().filter(|e| e.entry.hourly_rate_agorot.is_none()).count();
+    if unrated > 0 {
+        warnings.push(format!(
+            "{} time entr{} had no hourly rate and were billed at zero",
+            unrated,
+            if unrated == 1 { "y" } else { "ies" }
+        ));
+    }
+
+    Ok(Json(InvoiceResponse { invoice, lines, warnings }))
+}
+
+pub async fn get_invoices(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Query(params): Query<InvoicesQuery>,
+) -> Result<Json<Vec<Invoice>>, AppError> {
+    auth.require_admin()?;
+
+    if let Some(status) = &params.status {
+        if ![INVOICE_DRAFT, INVOICE_ISSUED].contains(&status.as_str()) {
+            return Err(AppError::BadRequest(format!("Invalid invoice status: {}", status)));
+        }
+    }
+
+    let invoices = db::get_invoices(&state.pool, &params).await?;
+
+    Ok(Json(invoices))
+}
+
+pub async fn get_invoice(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<InvoiceResponse>, AppError> {
+    auth.require_admin()?;
+
+    let (invoice, lines) = find_invoice(&state, id).await?;
+
+    Ok(Json(InvoiceResponse { invoice, lines, warnings: Vec::new() }))
+}
+
+/// The invoice as a printable Hebrew HTML page; drafts are marked as such.
+pub async fn get_invoice_document(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    auth.require_admin()?;
+
+    let (invoice, lines) = find_invoice(&state, id).await?;
+    let client = db::get_client_by_id(&state.pool, invoice.client_id)
+        .await?
+        .ok_or_else(|| AppError::InternalServerError("Invoice client is missing".to_string()))?;
+    let matter = match invoice.matter_id {
+        Some(matter_id) => db::get_matter_by_id(&state.pool, matter_id).await?,
+        None => None,
+    };
+
+    let issuer = InvoiceIssuer {
+        name: &state.config.invoice_issuer_name,
+        tax_id: &state.config.invoice_issuer_tax_id,
+    };
+    let body = render_invoice_html(&issuer, &invoice, &lines, &client, matter.as_ref())?;
+
+    Ok(([(CONTENT_TYPE, "text/html; charset=utf-8")], body))
+}
+
+/// Gives a draft the next number of the current year. Issued invoices cannot
+/// be changed or deleted.
+pub async fn issue_invoice(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<InvoiceResponse>, AppError> {
+    auth.require_admin()?;
+
+    let mut tx = state.pool.begin().await?;
+
+    let existing = db::get_invoice_for_update(&mut *tx, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Invoice with id {} not found", id)))?;
+    if existing.status != INVOICE_DRAFT {
+        return Err(AppError::BadRequest(format!(
+            "Invoice {} is already issued",
+            existing.invoice_number.unwrap_or_default()
+        )));
+    }
+
+    // Numbered by the year the invoice shows, in office time
+    let year = Utc::now().with_timezone(&DEFAULT_TIMEZONE).year();
+    let number = db::next_invoice_number(&mut *tx, year).await?;
+    let invoice = db::issue_invoice(&mut *tx, id, year, number).await?;
+
+    tx.commit().await?;
+
+    let lines = db::get_invoice_lines(&state.pool, id).await?;
+
+    Ok(Json(InvoiceResponse { invoice, lines, warnings: Vec::new() }))
+}
+
+/// Only drafts can be deleted; their time entries become billable again.
+pub async fn delete_invoice(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Value>, AppError> {
+    auth.require_admin()?;
+
+    let (invoice, _) = find_invoice(&state, id).await?;
+    if invoice.status != INVOICE_DRAFT {
+        return Err(AppError::BadRequest("Issued invoices cannot be deleted".to_string()));
+    }
+
+    if !db::delete_draft_invoice(&state.pool, id).await? {
+        return Err(AppError::BadRequest("Issued invoices cannot be deleted".to_string()));
+    }
+
+    Ok(Json(json!({
+        "message": "Invoice deleted successfully",
+        "id": id
+    })))
+}
+
+async fn find_invoice(state: &AppState, id: Uuid) -> Result<(Invoice, Vec<InvoiceLine>), AppError> {
+    let invoice = db::get_invoice_by_id(&state.pool, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Invoice with id {} not found", id)))?;
+    let lines = db::get_invoice_lines(&state.pool, id).await?;
+
+    Ok((invoice, lines))
+}
@@ -0,0 +1,233 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use serde_json::{json, Value};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    db,
+    handlers::tasks::to_responses,
+    models::{
+        CreateMatterPartyRequest, CreateMatterRequest, Matter, MatterParty, MatterResponse,
+        MatterStats, MattersQuery, TaskResponse, UpdateMatterRequest, MATTER_CLOSED, MATTER_OPEN,
+    },
+    utils::{AppError, AuthUser},
+    AppState,
+};
+
+pub async fn create_matter(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(payload): Json<CreateMatterRequest>,
+) -> Result<Json<Matter>, AppError> {
+    // Validate request
+    payload.validate()?;
+
+    check_matter_number(&state, &payload.matter_number, None).await?;
+    check_references(&state, Some(payload.client_id), payload.responsible_lawyer_id).await?;
+
+    let matter = db::create_matter(&state.pool, &payload, auth.id).await?;
+
+    Ok(Json(matter))
+}
+
+pub async fn get_matters(
+    State(state): State<AppState>,
+    _auth: AuthUser,
+    Query(params): Query<MattersQuery>,
+) -> Result<Json<Vec<Matter>>, AppError> {
+    if let Some(status) = &params.status {
+        if ![MATTER_OPEN, MATTER_CLOSED].contains(&status.as_str()) {
+            return Err(AppError::BadRequest(format!("Invalid matter status: {}", status)));
+        }
+    }
+
+    let matters = db::get_matters(&state.pool, &params).await?;
+
+    Ok(Json(matters))
+}
+
+pub async fn get_matter(
+    State(state): State<AppState>,
+    _auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Matter>, AppError> {
+    let matter = find_matter(&state, id).await?;
+
+    Ok(Json(matter))
+}
+
+/// Closing a matter that still has open tasks goes through, with a warning
+/// listing how many are left.
+pub async fn update_matter(
+    State(state): State<AppState>,
+    _auth: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateMatterRequest>,
+) -> Result<Json<MatterResponse>, AppError> {
+    // Validate request
+    payload.validate()?;
+
+    let existing = find_matter(&state, id).await?;
+
+    if let Some(matter_number) = &payload.matter_number {
+        check_matter_number(&state, matter_number, Some(id)).await?;
+    }
+    check_references(&state, payload.client_id, payload.responsible_lawyer_id).await?;
+
+    let matter = db::update_matter(&state.pool, id, &payload)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Matter with id {} not found", id)))?;
+
+    let mut response = MatterResponse::from(matter);
+
+    if existing.status != MATTER_CLOSED && response.matter.status == MATTER_CLOSED {
+        let stats = db::get_matter_stats(&state.pool, id).await?;
+        let open_tasks = stats.new + stats.in_progress;
+        if open_tasks > 0 {
+            response.warnings.push(format!(
+                "Matter was closed with {} open task(s)",
+                open_tasks
+            ));
+        }
+    }
+
+    Ok(Json(response))
+}
+
+/// Tasks filed under the matter are kept, without a matter.
+pub async fn delete_matter(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Value>, AppError> {
+    auth.require_admin()?;
+
+    if !db::delete_matter(&state.pool, id).await? {
+        return Err(AppError::NotFound(format!("Matter with id {} not found", id)));
+    }
+
+    Ok(Json(json!({
+        "message": "Matter deleted successfully",
+        "id": id
+    })))
+}
+
+pub async fn get_matter_tasks(
+    State(state): State<AppState>,
+    _auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<TaskResponse>>, AppError> {
+    find_matter(&state, id).await?;
+
+    let tasks = db::get_tasks_by_matter(&state.pool, id).await?;
+    let responses = to_responses(&state, tasks).await?;
+
+    Ok(Json(responses))
+}
+
+pub async fn get_matter_stats(
+    State(state): State<AppState>,
+    _auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<MatterStats>, AppError> {
+    find_matter(&state, id).await?;
+
+    let stats = db::get_matter_stats(&state.pool, id).await?;
+
+    Ok(Json(stats))
+}
+
+pub async fn get_matter_parties(
+    State(state): State<AppState>,
+    _auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<MatterParty>>, AppError> {
+    find_matter(&state, id).await?;
+
+    let parties = db::get_matter_parties(&state.pool, id).await?;
+
+    Ok(Json(parties))
+}
+
+pub async fn create_matter_party(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<CreateMatterPartyRequest>,
+) -> Result<Json<MatterParty>, AppError> {
+    // Validate request
+    payload.validate()?;
+
+    find_matter(&state, id).await?;
+
+    let party = db::create_matter_party(&state.pool, id, &payload, auth.id).await?;
+
+    Ok(Json(party))
+}
+
+pub async fn delete_matter_party(
+    State(state): State<AppState>,
+    _auth: AuthUser,
+    Path((id, party_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<Value>, AppError> {
+    if !db::delete_matter_party(&state.pool, id, party_id).await? {
+        return Err(AppError::NotFound(format!("Party with id {} not found", party_id)));
+    }
+
+    Ok(Json(json!({
+        "message": "Party deleted successfully",
+        "id": party_id
+    })))
+}
+
+async fn check_matter_number(
+    state: &AppState,
+    matter_number: &str,
+    matter_id: Option<Uuid>,
+) -> Result<(), AppError> {
+    if let Some(existing) = db::get_matter_by_number(&state.pool, matter_number).await? {
+        if Some(existing.id) != matter_id {
+            return Err(AppError::BadRequest(format!(
+                "Matter number {} already exists",
+                matter_number
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+async fn check_references(
+    state: &AppState,
+    client_id: Option<Uuid>,
+    responsible_lawyer_id: Option<Uuid>,
+) -> Result<(), AppError> {
+    if let Some(client_id) = client_id {
+        db::get_client_by_id(&state.pool, client_id)
+            .await?
+            .ok_or_else(|| AppError::BadRequest(format!("Client {} not found", client_id)))?;
+    }
+
+    if let Some(lawyer_id) = responsible_lawyer_id {
+        match db::get_user_by_id(&state.pool, lawyer_id).await? {
+            Some(user) if user.is_active => {}
+            _ => {
+                return Err(AppError::BadRequest(format!(
+                    "Responsible lawyer {} is not an active user",
+                    lawyer_id
+                )))
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn find_matter(state: &AppState, id: Uuid) -> Result<Matter, AppError> {
+    db::get_matter_by_id(&state.pool, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Matter with id {} not found", id)))
+}
@@ -2,8 +2,54 @@ pub mod tasks;
 pub mod users;
 pub mod health;
 pub mod stats;
+pub mod comments;
+pub mod checklist;
+pub mod dependencies;
+pub mod recurring;
+pub mod templates;
+pub mod outbox;
+pub mod notifications;
+pub mod push;
+pub mod events;
+pub mod webhooks;
+pub mod attachments;
+pub mod inbound;
+pub mod clients;
+pub mod matters;
+pub mod deadlines;
+pub mod hearings;
+pub mod calendar;
+pub mod time_entries;
+pub mod invoices;
+pub mod conflicts;
+pub mod contacts;
+pub mod documents;
+pub mod expiries;
 
 pub use tasks::*;
 pub use users::*;
 pub use health::*;
 pub use stats::*;
+pub use comments::*;
+pub use checklist::*;
+pub use dependencies::*;
+pub use recurring::*;
+pub use templates::*;
+pub use outbox::*;
+pub use notifications::*;
+pub use push::*;
+pub use events::*;
+pub use webhooks::*;
+pub use attachments::*;
+pub use inbound::*;
+pub use clients::*;
+pub use matters::*;
+pub use deadlines::*;
+pub use hearings::*;
+pub use calendar::*;
+pub use time_entries::*;
+pub use invoices::*;
+pub use conflicts::*;
+pub use contacts::*;
+pub use documents::*;
+pub use expiries::*;
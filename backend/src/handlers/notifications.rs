@@ -0,0 +1,123 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use chrono_tz::Tz;
+use serde_json::{json, Value};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    db,
+    models::{
+        InAppNotification, NotificationEvent, NotificationPreference,
+        NotificationPreferencesResponse, NotificationSettings, NotificationsQuery,
+        UpdateNotificationPreferencesRequest, DEFAULT_TIMEZONE,
+    },
+    utils::{AppError, AuthUser},
+    AppState,
+};
+
+pub async fn get_notification_preferences(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<Json<NotificationPreferencesResponse>, AppError> {
+    let response = load_preferences(&state, auth.id).await?;
+
+    Ok(Json(response))
+}
+
+pub async fn update_notification_preferences(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(payload): Json<UpdateNotificationPreferencesRequest>,
+) -> Result<Json<NotificationPreferencesResponse>, AppError> {
+    // Validate request
+    payload.validate()?;
+
+    let timezone = match &payload.timezone {
+        Some(name) => name
+            .parse::<Tz>()
+            .map_err(|_| AppError::ValidationError(format!("Unknown time zone: {}", name)))?
+            .name()
+            .to_string(),
+        None => DEFAULT_TIMEZONE.name().to_string(),
+    };
+
+    if payload.quiet_hours_start.is_some() != payload.quiet_hours_end.is_some() {
+        return Err(AppError::ValidationError(
+            "Quiet hours need both a start and an end".to_string(),
+        ));
+    }
+
+    let mut tx = state.pool.begin().await?;
+
+    db::upsert_notification_settings(&mut *tx, auth.id, &payload, &timezone).await?;
+    for preference in &payload.preferences {
+        db::upsert_notification_preference(&mut *tx, auth.id, preference).await?;
+    }
+
+    tx.commit().await?;
+
+    let response = load_preferences(&state, auth.id).await?;
+
+    Ok(Json(response))
+}
+
+pub async fn get_notifications(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Query(params): Query<NotificationsQuery>,
+) -> Result<Json<Vec<InAppNotification>>, AppError> {
+    let limit = params.limit.unwrap_or(50).clamp(1, 200);
+    let notifications = db::get_in_app_notifications(&state.pool, auth.id, params.unread, limit).await?;
+
+    Ok(Json(notifications))
+}
+
+pub async fn mark_notification_read(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<InAppNotification>, AppError> {
+    let notification = db::mark_notification_read(&state.pool, auth.id, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Notification with id {} not found", id)))?;
+
+    Ok(Json(notification))
+}
+
+pub async fn mark_all_notifications_read(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<Json<Value>, AppError> {
+    let updated = db::mark_all_notifications_read(&state.pool, auth.id).await?;
+
+    Ok(Json(json!({
+        "message": "Notifications marked as read",
+        "updated": updated
+    })))
+}
+
+async fn load_preferences(state: &AppState, user_id: Uuid) -> Result<NotificationPreferencesResponse, AppError> {
+    let settings = db::get_notification_settings(&state.pool, user_id)
+        .await?
+        .unwrap_or_else(|| NotificationSettings::default_for(user_id));
+    let stored = db::get_notification_preferences(&state.pool, user_id).await?;
+
+    let preferences = NotificationEvent::ALL
+        .iter()
+        .map(|event| {
+            stored
+                .iter()
+                .find(|p| p.event_type == event.as_str())
+                .cloned()
+                .unwrap_or_else(|| NotificationPreference::default_for(user_id, *event))
+        })
+        .collect();
+
+    Ok(NotificationPreferencesResponse {
+        settings,
+        preferences,
+    })
+}
@@ -0,0 +1,47 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use uuid::Uuid;
+
+use crate::{
+    db,
+    models::{OutboxEmailSummary, OutboxQuery},
+    utils::{AppError, AuthUser},
+    AppState,
+};
+
+const OUTBOX_STATUSES: [&str; 4] = ["pending", "sending", "sent", "dead"];
+
+pub async fn get_email_outbox(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Query(params): Query<OutboxQuery>,
+) -> Result<Json<Vec<OutboxEmailSummary>>, AppError> {
+    auth.require_admin()?;
+
+    let status = params.status.unwrap_or_else(|| "dead".to_string());
+    if !OUTBOX_STATUSES.contains(&status.as_str()) {
+        return Err(AppError::BadRequest(format!("Invalid outbox status: {}", status)));
+    }
+    let limit = params.limit.unwrap_or(50).clamp(1, 500);
+
+    let emails = db::get_outbox_emails_by_status(&state.pool, &status, limit).await?;
+    let summaries: Vec<OutboxEmailSummary> = emails.into_iter().map(|e| e.into()).collect();
+
+    Ok(Json(summaries))
+}
+
+pub async fn resend_outbox_email(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<OutboxEmailSummary>, AppError> {
+    auth.require_admin()?;
+
+    let email = db::requeue_email(&state.pool, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Dead-lettered email with id {} not found", id)))?;
+
+    Ok(Json(email.into()))
+}
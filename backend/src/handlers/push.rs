@@ -0,0 +1,71 @@
+use axum::{
+    extract::State,
+    http::{header::USER_AGENT, HeaderMap},
+    Json,
+};
+use serde_json::{json, Value};
+use validator::Validate;
+
+use crate::{
+    db,
+    models::{CreatePushSubscriptionRequest, DeletePushSubscriptionRequest, PushSubscription},
+    services::{validate_push_endpoint, validate_push_keys},
+    utils::{AppError, AuthUser},
+    AppState,
+};
+
+pub async fn get_vapid_public_key(
+    State(state): State<AppState>,
+    _auth: AuthUser,
+) -> Result<Json<Value>, AppError> {
+    let public_key = state
+        .push_service
+        .public_key()
+        .ok_or_else(|| AppError::NotFound("Web Push is not configured".to_string()))?;
+
+    Ok(Json(json!({ "public_key": public_key })))
+}
+
+pub async fn get_push_subscriptions(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<Json<Vec<PushSubscription>>, AppError> {
+    let subscriptions = db::get_push_subscriptions_by_user(&state.pool, auth.id).await?;
+
+    Ok(Json(subscriptions))
+}
+
+pub async fn create_push_subscription(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    headers: HeaderMap,
+    Json(payload): Json<CreatePushSubscriptionRequest>,
+) -> Result<Json<PushSubscription>, AppError> {
+    // Validate request
+    payload.validate()?;
+    validate_push_endpoint(&payload.endpoint)
+        .map_err(|e| AppError::ValidationError(format!("Invalid subscription endpoint: {}", e)))?;
+    validate_push_keys(&payload.keys.p256dh, &payload.keys.auth)
+        .map_err(|e| AppError::ValidationError(format!("Invalid subscription keys: {}", e)))?;
+
+    let user_agent = headers.get(USER_AGENT).and_then(|v| v.to_str().ok());
+    let subscription = db::upsert_push_subscription(&state.pool, auth.id, &payload, user_agent).await?;
+
+    Ok(Json(subscription))
+}
+
+pub async fn delete_push_subscription(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(payload): Json<DeletePushSubscriptionRequest>,
+) -> Result<Json<Value>, AppError> {
+    let deleted = db::delete_push_subscription(&state.pool, auth.id, &payload.endpoint).await?;
+
+    if !deleted {
+        return Err(AppError::NotFound("Push subscription not found".to_string()));
+    }
+
+    Ok(Json(json!({
+        "message": "Push subscription deleted successfully"
+    })))
+}
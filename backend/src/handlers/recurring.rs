@@ -0,0 +1,264 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use chrono::{NaiveDate, Utc};
+use serde_json::{json, Value};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    db,
+    handlers::tasks::{record_task_update, to_responses},
+    models::{
+        CreateRecurringTaskRequest, OccurrencesQuery, RecurringTaskSeries,
+        RecurringTaskUpdateResponse, SeriesEditScope, TaskResponse, UpdateRecurringTaskRequest,
+        DEFAULT_TIMEZONE,
+    },
+    services::{self, RecurrenceRule},
+    utils::{AppError, AuthUser},
+    AppState,
+};
+
+const DEFAULT_PREVIEW_OCCURRENCES: usize = 10;
+const MAX_PREVIEW_OCCURRENCES: usize = 100;
+
+pub async fn create_recurring_task(
+    State(state): State<AppState>,
+    Json(payload): Json<CreateRecurringTaskRequest>,
+) -> Result<Json<RecurringTaskSeries>, AppError> {
+    // Validate request
+    payload.validate()?;
+
+    let rule = parse_rule(&payload.rrule)?;
+    let first = rule.occurrences(payload.start_date).next();
+
+    if first.is_none() {
+        return Err(AppError::BadRequest(
+            "Recurrence rule has no occurrences".to_string(),
+        ));
+    }
+
+    let series = db::create_recurring_series(&state.pool, &payload, first).await?;
+
+    // Create anything already due instead of waiting for the next scheduler tick
+    let today = Utc::now().with_timezone(&DEFAULT_TIMEZONE).date_naive();
+    if let Err(e) = services::materialize_series(&state, &series, today).await {
+        tracing::error!("Failed to materialize series {}: {:?}", series.id, e);
+    }
+
+    let series = find_series(&state, series.id).await?;
+
+    Ok(Json(series))
+}
+
+pub async fn get_all_recurring_tasks(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<RecurringTaskSeries>>, AppError> {
+    let series = db::get_all_recurring_series(&state.pool).await?;
+
+    Ok(Json(series))
+}
+
+pub async fn get_recurring_task(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<RecurringTaskSeries>, AppError> {
+    let series = find_series(&state, id).await?;
+
+    Ok(Json(series))
+}
+
+/// Upcoming occurrence dates of a series, for previewing the schedule.
+pub async fn get_recurring_task_occurrences(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<OccurrencesQuery>,
+) -> Result<Json<Vec<NaiveDate>>, AppError> {
+    let series = find_series(&state, id).await?;
+    let rule = parse_rule(&series.rrule)?;
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_PREVIEW_OCCURRENCES)
+        .min(MAX_PREVIEW_OCCURRENCES);
+
+    let occurrences: Vec<NaiveDate> = match series.next_occurrence {
+        Some(next) => rule
+            .occurrences(series.start_date)
+            .skip_while(|d| *d < next)
+            .take(limit)
+            .collect(),
+        None => Vec::new(),
+    };
+
+    Ok(Json(occurrences))
+}
+
+pub async fn get_recurring_task_tasks(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<TaskResponse>>, AppError> {
+    find_series(&state, id).await?;
+
+    let tasks = db::get_series_tasks(&state.pool, id).await?;
+    let responses = to_responses(&state, tasks).await?;
+
+    Ok(Json(responses))
+}
+
+pub async fn update_recurring_task(
+    State(state): State<AppState>,
+    auth: Option<AuthUser>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateRecurringTaskRequest>,
+) -> Result<Json<RecurringTaskUpdateResponse>, AppError> {
+    // Validate request
+    payload.validate()?;
+
+    let series = find_series(&state, id).await?;
+    let actor_email = auth.as_ref().map(|a| a.email.as_str());
+
+    match payload.scope {
+        SeriesEditScope::ThisOccurrence => {
+            update_single_occurrence(&state, series, &payload, actor_email).await
+        }
+        SeriesEditScope::AllFuture => {
+            update_future_occurrences(&state, series, &payload, actor_email).await
+        }
+    }
+}
+
+pub async fn delete_recurring_task(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Value>, AppError> {
+    // Stops future occurrences; tasks already created are kept
+    let deactivated = db::deactivate_recurring_series(&state.pool, id).await?;
+
+    if !deactivated {
+        return Err(AppError::NotFound(format!("Recurring task with id {} not found", id)));
+    }
+
+    Ok(Json(json!({
+        "message": "Recurring task stopped successfully",
+        "id": id
+    })))
+}
+
+/// Edits one occurrence only. If its task has not been created yet it is
+/// created now, so the scheduler will later skip it as a duplicate.
+async fn update_single_occurrence(
+    state: &AppState,
+    series: RecurringTaskSeries,
+    payload: &UpdateRecurringTaskRequest,
+    actor_email: Option<&str>,
+) -> Result<Json<RecurringTaskUpdateResponse>, AppError> {
+    let date = payload.occurrence_date.ok_or_else(|| {
+        AppError::BadRequest("occurrence_date is required for this_occurrence".to_string())
+    })?;
+
+    if payload.rrule.is_some() || payload.lead_days.is_some() {
+        return Err(AppError::BadRequest(
+            "The schedule can only be changed for all future occurrences".to_string(),
+        ));
+    }
+
+    let rule = parse_rule(&series.rrule)?;
+    let is_occurrence = rule
+        .occurrences(series.start_date)
+        .take_while(|d| *d <= date)
+        .any(|d| d == date);
+    if !is_occurrence {
+        return Err(AppError::BadRequest(format!(
+            "{} is not an occurrence of this series",
+            date
+        )));
+    }
+
+    let task = match db::get_series_occurrence_task(&state.pool, series.id, date).await? {
+        Some(task) => task,
+        None => {
+            services::create_occurrence(state, &series, date).await?;
+            db::get_series_occurrence_task(&state.pool, series.id, date)
+                .await?
+                .ok_or_else(|| {
+                    AppError::InternalServerError("Failed to create occurrence".to_string())
+                })?
+        }
+    };
+
+    let task = if payload.has_task_changes() {
+        let mut tx = state.pool.begin().await?;
+
+        let updated = db::update_task(&mut *tx, task.id, &payload.task_update()).await?;
+        record_task_update(state, &mut tx, &task, &updated, actor_email).await?;
+
+        tx.commit().await?;
+        updated
+    } else {
+        task
+    };
+
+    let updated_tasks = to_responses(state, vec![task]).await?;
+
+    Ok(Json(RecurringTaskUpdateResponse {
+        series,
+        updated_tasks,
+    }))
+}
+
+/// Edits the series template and every open occurrence from the given date on.
+async fn update_future_occurrences(
+    state: &AppState,
+    series: RecurringTaskSeries,
+    payload: &UpdateRecurringTaskRequest,
+    actor_email: Option<&str>,
+) -> Result<Json<RecurringTaskUpdateResponse>, AppError> {
+    let today = Utc::now().with_timezone(&DEFAULT_TIMEZONE).date_naive();
+    let from = payload.occurrence_date.unwrap_or(today);
+
+    // A new schedule takes effect from the first date not yet materialized
+    let next_occurrence = match &payload.rrule {
+        Some(rrule) => {
+            let rule = parse_rule(rrule)?;
+            let resume_from = series.next_occurrence.unwrap_or(today).max(from).max(today);
+            rule.next_on_or_after(series.start_date, resume_from)
+        }
+        None => series.next_occurrence,
+    };
+
+    // Update the series and its tasks, and queue their notifications, together
+    let mut tx = state.pool.begin().await?;
+
+    let updated_series =
+        db::update_recurring_series(&mut *tx, series.id, payload, next_occurrence).await?;
+
+    let mut updated = Vec::new();
+    if payload.has_task_changes() {
+        let update = payload.task_update();
+        for task in db::get_open_series_tasks_from(&state.pool, series.id, from).await? {
+            let task_updated = db::update_task(&mut *tx, task.id, &update).await?;
+            record_task_update(state, &mut tx, &task, &task_updated, actor_email).await?;
+            updated.push(task_updated);
+        }
+    }
+
+    tx.commit().await?;
+
+    let updated_tasks = to_responses(state, updated).await?;
+
+    Ok(Json(RecurringTaskUpdateResponse {
+        series: updated_series,
+        updated_tasks,
+    }))
+}
+
+async fn find_series(state: &AppState, id: Uuid) -> Result<RecurringTaskSeries, AppError> {
+    db::get_recurring_series_by_id(&state.pool, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Recurring task with id {} not found", id)))
+}
+
+fn parse_rule(rrule: &str) -> Result<RecurrenceRule, AppError> {
+    RecurrenceRule::parse(rrule).map_err(|e| AppError::BadRequest(e.to_string()))
+}
@@ -1,16 +1,22 @@
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     Json,
 };
 use serde_json::{json, Value};
+use sqlx::PgConnection;
 use uuid::Uuid;
 use validator::Validate;
 
 use crate::{
     db,
-    models::{CreateTaskRequest, TaskResponse, UpdateTaskRequest},
-    services::EmailService,
-    utils::AppError,
+    models::{
+        CreateTaskRequest, NewTaskEvent, NotificationEvent, Task, TaskEventType, TaskResponse,
+        UpdateTaskParams, UpdateTaskRequest, MATTER_CLOSED,
+    },
+    services::{
+        dispatch_notification, update_event_for, update_notification_recipients, EmailService,
+    },
+    utils::{AppError, AuthUser},
     AppState,
 };
 
@@ -21,29 +27,49 @@ pub async fn create_task(
     // Validate request
     payload.validate()?;
 
-    // Generate task ID
-    let task_id = payload.generate_task_id();
+    // A subtask must point at an existing parent
+    if let Some(parent_id) = payload.parent_id {
+        db::get_task_by_id(&state.pool, parent_id)
+            .await?
+            .ok_or_else(|| AppError::BadRequest(format!("Parent task {} not found", parent_id)))?;
+    }
+    let matter_warning = check_matter(&state, payload.matter_id).await?;
 
-    // Create task in database
-    let task = db::create_task(&state.pool, &payload, &task_id).await?;
+    // Create task and queue its email notification together
+    let mut tx = state.pool.begin().await?;
+    let task = insert_task(&state, &mut tx, &payload).await?;
+    tx.commit().await?;
 
-    // Send email notification (async, non-blocking)
-    let email_service = state.email_service.clone();
-    let task_clone = task.clone();
-    tokio::spawn(async move {
-        if let Err(e) = email_service.send_task_notification(&task_clone).await {
-            tracing::error!("Failed to send email: {:?}", e);
-        }
-    });
+    let mut response = TaskResponse::from(task);
+    if let Some(warning) = matter_warning {
+        response = response.with_warning(warning);
+    }
+
+    Ok(Json(response))
+}
+
+/// Creates a task in the caller's transaction, with its event and the
+/// notification to the assignee.
+pub(crate) async fn insert_task(
+    state: &AppState,
+    conn: &mut PgConnection,
+    payload: &CreateTaskRequest,
+) -> Result<Task, AppError> {
+    let task = db::create_task(&mut *conn, payload, &payload.generate_task_id()).await?;
+    db::record_task_event(&mut *conn, &NewTaskEvent::for_task(TaskEventType::TaskCreated, &task))
+        .await?;
+    let email = state.email_service.build_task_notification(&task)?;
+    dispatch_notification(state, conn, NotificationEvent::TaskCreated, &task.priority, email)
+        .await?;
 
-    Ok(Json(task.into()))
+    Ok(task)
 }
 
 pub async fn get_all_tasks(
     State(state): State<AppState>,
 ) -> Result<Json<Vec<TaskResponse>>, AppError> {
     let tasks = db::get_all_tasks(&state.pool).await?;
-    let responses: Vec<TaskResponse> = tasks.into_iter().map(|t| t.into()).collect();
+    let responses = to_responses(&state, tasks).await?;
 
     Ok(Json(responses))
 }
@@ -56,46 +82,134 @@ pub async fn get_task(
         .await?
         .ok_or_else(|| AppError::NotFound(format!("Task with id {} not found", id)))?;
 
-    Ok(Json(task.into()))
+    let mut responses = to_responses(&state, vec![task]).await?;
+
+    Ok(Json(responses.remove(0)))
 }
 
 pub async fn update_task(
     State(state): State<AppState>,
+    auth: Option<AuthUser>,
     Path(id): Path<Uuid>,
+    Query(params): Query<UpdateTaskParams>,
     Json(payload): Json<UpdateTaskRequest>,
 ) -> Result<Json<TaskResponse>, AppError> {
     // Validate request
     payload.validate()?;
 
     // Check if task exists
-    db::get_task_by_id(&state.pool, id)
+    let existing = db::get_task_by_id(&state.pool, id)
         .await?
         .ok_or_else(|| AppError::NotFound(format!("Task with id {} not found", id)))?;
 
-    // Update task
-    let task = db::update_task(&state.pool, id, &payload).await?;
+    let matter_warning = match payload.matter_id {
+        Some(matter_id) if existing.matter_id != Some(matter_id) => {
+            check_matter(&state, Some(matter_id)).await?
+        }
+        _ => None,
+    };
+
+    // A parent cannot be completed while subtasks are open, unless an admin forces it
+    if payload.status.as_deref() == Some("הושלמה") {
+        let open_subtasks = db::get_task_progress(&state.pool, &[id])
+            .await?
+            .first()
+            .map_or(0, |p| p.subtasks_open);
 
-    // Send update notification (async, non-blocking)
-    let email_service = state.email_service.clone();
-    let task_clone = task.clone();
-    tokio::spawn(async move {
-        if let Err(e) = email_service.send_task_update_notification(&task_clone).await {
-            tracing::error!("Failed to send update email: {:?}", e);
+        if open_subtasks > 0 {
+            if !params.force {
+                return Err(AppError::BadRequest(format!(
+                    "Task has {} open subtasks and cannot be completed",
+                    open_subtasks
+                )));
+            }
+
+            if !auth.as_ref().map_or(false, |a| a.is_admin()) {
+                return Err(AppError::Forbidden(
+                    "Only an admin can complete a task with open subtasks".to_string(),
+                ));
+            }
         }
-    });
+    }
 
-    Ok(Json(task.into()))
+    // Update task and queue its notifications together
+    let mut tx = state.pool.begin().await?;
+
+    let task = db::update_task(&mut *tx, id, &payload).await?;
+    let actor_email = auth.as_ref().map(|a| a.email.as_str());
+    record_task_update(&state, &mut tx, &existing, &task, actor_email).await?;
+
+    // Closing a blocker may free up the tasks that were waiting on it
+    let was_open = is_open_status(&existing.status);
+    if was_open && !is_open_status(&task.status) {
+        queue_unblocked_notifications(&state, &mut tx, &task).await?;
+    }
+
+    tx.commit().await?;
+
+    let mut response = to_responses(&state, vec![task]).await?.remove(0);
+
+    // Starting work while blockers are still open is allowed, but flagged
+    if existing.status != "בטיפול" && response.status == "בטיפול" && response.is_blocked {
+        let warning = format!(
+            "Task is still blocked by {} open task(s)",
+            response.blocked_by.len()
+        );
+        response = response.with_warning(warning);
+    }
+    if let Some(warning) = matter_warning {
+        response = response.with_warning(warning);
+    }
+
+    Ok(Json(response))
+}
+
+/// Records the update event and queues the notifications the changes from
+/// `existing` call for, in the caller's transaction.
+pub(crate) async fn record_task_update(
+    state: &AppState,
+    conn: &mut PgConnection,
+    existing: &Task,
+    task: &Task,
+    actor_email: Option<&str>,
+) -> Result<(), AppError> {
+    // A previous assignee sees the task leave their list
+    let event = NewTaskEvent::for_task(TaskEventType::TaskUpdated, task)
+        .also_visible_to(&existing.assigned_to_email);
+    db::record_task_event(&mut *conn, &event).await?;
+
+    // Only changes the notification rules care about are emailed
+    let changes = task.changes_from(existing);
+    for recipient in
+        update_notification_recipients(&state.config, existing, task, &changes, actor_email)
+    {
+        let event = update_event_for(&recipient, task, &changes);
+        let email = state
+            .email_service
+            .build_task_update_notification(task, &changes, &recipient)?;
+        dispatch_notification(state, conn, event, &task.priority, email).await?;
+    }
+
+    Ok(())
 }
 
 pub async fn delete_task(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
 ) -> Result<Json<Value>, AppError> {
-    let deleted = db::delete_task(&state.pool, id).await?;
+    let task = db::get_task_by_id(&state.pool, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Task with id {} not found", id)))?;
 
-    if !deleted {
+    let mut tx = state.pool.begin().await?;
+
+    if !db::delete_task(&mut *tx, id).await? {
         return Err(AppError::NotFound(format!("Task with id {} not found", id)));
     }
+    db::record_task_event(&mut *tx, &NewTaskEvent::for_task(TaskEventType::TaskDeleted, &task))
+        .await?;
+
+    tx.commit().await?;
 
     Ok(Json(json!({
         "message": "Task deleted successfully",
@@ -108,7 +222,7 @@ pub async fn get_tasks_by_assignee(
     Path(assignee): Path<String>,
 ) -> Result<Json<Vec<TaskResponse>>, AppError> {
     let tasks = db::get_tasks_by_assignee(&state.pool, &assignee).await?;
-    let responses: Vec<TaskResponse> = tasks.into_iter().map(|t| t.into()).collect();
+    let responses = to_responses(&state, tasks).await?;
 
     Ok(Json(responses))
 }
@@ -118,7 +232,92 @@ pub async fn get_tasks_by_status(
     Path(status): Path<String>,
 ) -> Result<Json<Vec<TaskResponse>>, AppError> {
     let tasks = db::get_tasks_by_status(&state.pool, &status).await?;
-    let responses: Vec<TaskResponse> = tasks.into_iter().map(|t| t.into()).collect();
+    let responses = to_responses(&state, tasks).await?;
 
     Ok(Json(responses))
 }
+
+pub async fn get_subtasks(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<TaskResponse>>, AppError> {
+    db::get_task_by_id(&state.pool, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Task with id {} not found", id)))?;
+
+    let tasks = db::get_subtasks(&state.pool, id).await?;
+    let responses = to_responses(&state, tasks).await?;
+
+    Ok(Json(responses))
+}
+
+/// Converts tasks to responses, attaching the rolled-up subtask/checklist
+/// progress, the blockers that are still open and the time logged.
+pub(crate) async fn to_responses(state: &AppState, tasks: Vec<Task>) -> Result<Vec<TaskResponse>, AppError> {
+    let ids: Vec<Uuid> = tasks.iter().map(|t| t.id).collect();
+    let mut progress = db::get_task_progress(&state.pool, &ids).await?;
+    let blockers = db::get_open_blockers(&state.pool, &ids).await?;
+    let time_totals = db::get_task_time_totals(&state.pool, &ids).await?;
+
+    let responses = tasks
+        .into_iter()
+        .map(|task| {
+            let task_progress = progress
+                .iter()
+                .position(|p| p.task_id == task.id)
+                .map(|i| progress.swap_remove(i));
+            let task_blockers: Vec<Uuid> = blockers
+                .iter()
+                .filter(|(task_id, _)| *task_id == task.id)
+                .map(|(_, blocked_by_id)| *blocked_by_id)
+                .collect();
+            let time_spent = time_totals
+                .iter()
+                .find(|t| t.task_id == task.id)
+                .map(|t| t.total_minutes)
+                .unwrap_or(0);
+            TaskResponse::from(task)
+                .with_progress(task_progress)
+                .with_open_blockers(task_blockers)
+                .with_time_spent(time_spent)
+        })
+        .collect();
+
+    Ok(responses)
+}
+
+/// Tasks can only be filed under an existing matter. Filing one under a
+/// closed matter is allowed, but returns a warning.
+pub(crate) async fn check_matter(state: &AppState, matter_id: Option<Uuid>) -> Result<Option<String>, AppError> {
+    let matter_id = match matter_id {
+        Some(matter_id) => matter_id,
+        None => return Ok(None),
+    };
+
+    let matter = db::get_matter_by_id(&state.pool, matter_id)
+        .await?
+        .ok_or_else(|| AppError::BadRequest(format!("Matter {} not found", matter_id)))?;
+
+    Ok((matter.status == MATTER_CLOSED)
+        .then(|| format!("Matter {} is closed", matter.matter_number)))
+}
+
+fn is_open_status(status: &str) -> bool {
+    matches!(status, "חדשה" | "בטיפול")
+}
+
+async fn queue_unblocked_notifications(
+    state: &AppState,
+    conn: &mut PgConnection,
+    blocker: &Task,
+) -> Result<(), AppError> {
+    let unblocked = db::get_unblocked_tasks(&mut *conn, blocker.id).await?;
+
+    for task in unblocked {
+        let email = state.email_service.build_task_unblocked_notification(&task, blocker)?;
+        dispatch_notification(state, conn, NotificationEvent::TaskUpdated, &task.priority, email)
+            .await?;
+    }
+
+    Ok(())
+}
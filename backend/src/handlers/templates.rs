@@ -0,0 +1,357 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use chrono::{Duration, NaiveDate, Utc};
+use serde_json::{json, Value};
+use sqlx::PgConnection;
+use std::collections::HashMap;
+use uuid::Uuid;
+use validator::{Validate, ValidateEmail};
+
+use crate::{
+    db,
+    handlers::tasks::{check_matter, to_responses},
+    models::{
+        validate_priority, CreateChecklistItemRequest, CreateTaskRequest,
+        CreateTaskTemplateRequest, InstantiateTemplateRequest, InstantiateTemplateResponse,
+        NewTaskEvent, NotificationEvent, Task, TaskEventType, TaskTemplate, TemplateSubtask,
+        UpdateTaskTemplateRequest, DEFAULT_TIMEZONE, MAX_DUE_OFFSET_DAYS,
+    },
+    services::{dispatch_notification, fill_placeholders, find_placeholders},
+    utils::{AppError, AuthUser},
+    AppState,
+};
+
+pub async fn create_task_template(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(payload): Json<CreateTaskTemplateRequest>,
+) -> Result<Json<TaskTemplate>, AppError> {
+    auth.require_admin()?;
+
+    // Validate request
+    payload.validate()?;
+    validate_subtasks(&payload.subtasks)?;
+
+    if db::get_task_template_by_name(&state.pool, &payload.name).await?.is_some() {
+        return Err(AppError::BadRequest("Template name already exists".to_string()));
+    }
+
+    let template = db::create_task_template(&state.pool, &payload, auth.id).await?;
+
+    Ok(Json(template))
+}
+
+pub async fn get_all_task_templates(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<TaskTemplate>>, AppError> {
+    let templates = db::get_all_task_templates(&state.pool).await?;
+
+    Ok(Json(templates))
+}
+
+pub async fn get_task_template(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<TaskTemplate>, AppError> {
+    let template = find_template(&state, id).await?;
+
+    Ok(Json(template))
+}
+
+pub async fn update_task_template(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateTaskTemplateRequest>,
+) -> Result<Json<TaskTemplate>, AppError> {
+    auth.require_admin()?;
+
+    // Validate request
+    payload.validate()?;
+    if let Some(subtasks) = &payload.subtasks {
+        validate_subtasks(subtasks)?;
+    }
+
+    if let Some(name) = &payload.name {
+        if let Some(existing) = db::get_task_template_by_name(&state.pool, name).await? {
+            if existing.id != id {
+                return Err(AppError::BadRequest("Template name already exists".to_string()));
+            }
+        }
+    }
+
+    let template = db::update_task_template(&state.pool, id, &payload)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Template with id {} not found", id)))?;
+
+    Ok(Json(template))
+}
+
+pub async fn delete_task_template(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Value>, AppError> {
+    auth.require_admin()?;
+
+    let deleted = db::deactivate_task_template(&state.pool, id).await?;
+
+    if !deleted {
+        return Err(AppError::NotFound(format!("Template with id {} not found", id)));
+    }
+
+    Ok(Json(json!({
+        "message": "Template deleted successfully",
+        "id": id
+    })))
+}
+
+pub async fn create_task_from_template(
+    State(state): State<AppState>,
+    auth: Option<AuthUser>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<InstantiateTemplateRequest>,
+) -> Result<Json<InstantiateTemplateResponse>, AppError> {
+    // Validate request
+    payload.validate()?;
+
+    let template = find_template(&state, id).await?;
+    let matter_warning = check_matter(&state, payload.matter_id).await?;
+    let include_subtasks = payload.include_subtasks.unwrap_or(true);
+    let today = Utc::now().with_timezone(&DEFAULT_TIMEZONE).date_naive();
+    let start_date = payload.start_date.unwrap_or(today);
+
+    // Built-in variables, which the request may override
+    let mut variables: HashMap<String, String> = HashMap::from([
+        ("today".to_string(), today.to_string()),
+        ("start_date".to_string(), start_date.to_string()),
+        ("assigned_to".to_string(), payload.assigned_to.clone()),
+        ("created_by".to_string(), payload.created_by.clone()),
+    ]);
+    variables.extend(payload.variables.clone());
+
+    // Fail before creating anything if a placeholder has no value
+    let missing: Vec<String> = template_texts(&template, include_subtasks)
+        .into_iter()
+        .flat_map(find_placeholders)
+        .filter(|name| !variables.contains_key(name))
+        .fold(Vec::new(), |mut acc, name| {
+            if !acc.contains(&name) {
+                acc.push(name);
+            }
+            acc
+        });
+    if !missing.is_empty() {
+        return Err(AppError::BadRequest(format!(
+            "Missing template variables: {}",
+            missing.join(", ")
+        )));
+    }
+
+    let fill = |text: &str| -> Result<String, AppError> {
+        fill_placeholders(text, &variables).map_err(|missing| {
+            AppError::BadRequest(format!("Missing template variables: {}", missing.join(", ")))
+        })
+    };
+    let fill_opt = |text: &Option<String>| -> Result<Option<String>, AppError> {
+        text.as_deref().map(fill).transpose()
+    };
+
+    let task_request = CreateTaskRequest {
+        title: fill(&template.title)?,
+        description: fill_opt(&template.task_description)?,
+        category: template.category.clone(),
+        assigned_to: payload.assigned_to.clone(),
+        assigned_to_email: payload.assigned_to_email.clone(),
+        created_by: payload.created_by.clone(),
+        created_by_email: payload.created_by_email.clone(),
+        due_date: offset_date(start_date, template.due_offset_days)?,
+        priority: Some(template.priority.clone()),
+        attachments_folder_url: payload.attachments_folder_url.clone(),
+        notes: fill_opt(&template.notes)?,
+        parent_id: None,
+        matter_id: payload.matter_id,
+        series_id: None,
+        occurrence_date: None,
+    };
+    // The whole bundle, with its notifications, is created in one transaction
+    let mut tx = state.pool.begin().await?;
+
+    let task = db::create_task(&mut *tx, &task_request, &task_request.generate_task_id()).await?;
+    add_checklist(&mut tx, task.id, &template.checklist, &fill).await?;
+
+    let mut subtasks: Vec<Task> = Vec::new();
+    if include_subtasks {
+        for definition in template.subtasks.iter() {
+            let request = CreateTaskRequest {
+                title: fill(&definition.title)?,
+                description: fill_opt(&definition.description)?,
+                category: definition
+                    .category
+                    .clone()
+                    .unwrap_or_else(|| template.category.clone()),
+                assigned_to: definition
+                    .assigned_to
+                    .clone()
+                    .unwrap_or_else(|| payload.assigned_to.clone()),
+                assigned_to_email: definition
+                    .assigned_to_email
+                    .clone()
+                    .unwrap_or_else(|| payload.assigned_to_email.clone()),
+                created_by: payload.created_by.clone(),
+                created_by_email: payload.created_by_email.clone(),
+                due_date: offset_date(
+                    start_date,
+                    definition.due_offset_days.or(template.due_offset_days),
+                )?,
+                priority: Some(
+                    definition
+                        .priority
+                        .clone()
+                        .unwrap_or_else(|| template.priority.clone()),
+                ),
+                attachments_folder_url: None,
+                notes: None,
+                parent_id: Some(task.id),
+                matter_id: payload.matter_id,
+                series_id: None,
+                occurrence_date: None,
+            };
+            let subtask = db::create_task(&mut *tx, &request, &request.generate_task_id()).await?;
+            add_checklist(&mut tx, subtask.id, &definition.checklist, &fill).await?;
+
+            // Each step waits for the one before it
+            if template.sequential_subtasks {
+                if let Some(previous) = subtasks.last() {
+                    db::add_task_dependency(
+                        &mut tx,
+                        subtask.id,
+                        previous.id,
+                        auth.as_ref().map(|a| a.id),
+                    )
+                    .await?;
+                }
+            }
+
+            subtasks.push(subtask);
+        }
+    }
+
+    for created in std::iter::once(&task).chain(subtasks.iter()) {
+        let event = NewTaskEvent::for_task(TaskEventType::TaskCreated, created);
+        db::record_task_event(&mut *tx, &event).await?;
+        let email = state.email_service.build_task_notification(created)?;
+        dispatch_notification(
+            &state,
+            &mut tx,
+            NotificationEvent::TaskCreated,
+            &created.priority,
+            email,
+        )
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    let mut task_response = to_responses(&state, vec![task]).await?.remove(0);
+    if let Some(warning) = matter_warning {
+        task_response = task_response.with_warning(warning);
+    }
+    let subtask_responses = to_responses(&state, subtasks).await?;
+
+    Ok(Json(InstantiateTemplateResponse {
+        task: task_response,
+        subtasks: subtask_responses,
+    }))
+}
+
+async fn add_checklist(
+    conn: &mut PgConnection,
+    task_id: Uuid,
+    titles: &[String],
+    fill: &impl Fn(&str) -> Result<String, AppError>,
+) -> Result<(), AppError> {
+    for (position, title) in titles.iter().enumerate() {
+        let item = CreateChecklistItemRequest {
+            title: fill(title)?,
+            position: Some(position as i32),
+        };
+        db::create_checklist_item(&mut *conn, task_id, &item).await?;
+    }
+
+    Ok(())
+}
+
+/// Every text in the template that may contain placeholders.
+fn template_texts(template: &TaskTemplate, include_subtasks: bool) -> Vec<&str> {
+    let mut texts: Vec<&str> = vec![template.title.as_str()];
+    texts.extend(template.task_description.as_deref());
+    texts.extend(template.notes.as_deref());
+    texts.extend(template.checklist.iter().map(String::as_str));
+
+    if include_subtasks {
+        for subtask in template.subtasks.iter() {
+            texts.push(subtask.title.as_str());
+            texts.extend(subtask.description.as_deref());
+            texts.extend(subtask.checklist.iter().map(String::as_str));
+        }
+    }
+
+    texts
+}
+
+fn offset_date(start: NaiveDate, offset_days: Option<i32>) -> Result<Option<NaiveDate>, AppError> {
+    offset_days
+        .map(|days| {
+            start
+                .checked_add_signed(Duration::days(days as i64))
+                .ok_or_else(|| AppError::BadRequest("Due date out of range".to_string()))
+        })
+        .transpose()
+}
+
+fn validate_subtasks(subtasks: &[TemplateSubtask]) -> Result<(), AppError> {
+    for subtask in subtasks {
+        subtask.validate()?;
+
+        if subtask.title.trim().is_empty() {
+            return Err(AppError::ValidationError(
+                "Subtask title cannot be empty".to_string(),
+            ));
+        }
+
+        if let Some(priority) = &subtask.priority {
+            validate_priority(priority).map_err(|_| {
+                AppError::ValidationError(format!("Invalid subtask priority: {}", priority))
+            })?;
+        }
+
+        if let Some(email) = &subtask.assigned_to_email {
+            if !email.validate_email() {
+                return Err(AppError::ValidationError(format!(
+                    "Invalid subtask assignee email: {}",
+                    email
+                )));
+            }
+        }
+
+        if let Some(days) = subtask.due_offset_days {
+            if !(-MAX_DUE_OFFSET_DAYS..=MAX_DUE_OFFSET_DAYS).contains(&days) {
+                return Err(AppError::ValidationError(format!(
+                    "Subtask due offset must be between -{0} and {0} days",
+                    MAX_DUE_OFFSET_DAYS
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn find_template(state: &AppState, id: Uuid) -> Result<TaskTemplate, AppError> {
+    db::get_task_template_by_id(&state.pool, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Template with id {} not found", id)))
+}
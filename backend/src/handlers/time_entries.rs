@@ -0,0 +1,317 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use chrono::Utc;
+use serde_json::{json, Value};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    db,
+    models::{
+        CreateTimeEntryRequest, SetHourlyRateRequest, StartTimerRequest, StopTimerRequest,
+        TimeEntriesQuery, TimeEntry, TimeReport, TimeReportQuery, UpdateTimeEntryRequest, User,
+        DEFAULT_TIMEZONE,
+    },
+    utils::{AppError, AuthUser},
+    AppState,
+};
+
+/// Longest entry accepted from a start and end time.
+const MAX_ENTRY_MINUTES: i64 = 24 * 60;
+
+/// Starts the caller's timer. A user has at most one running timer.
+pub async fn start_timer(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(payload): Json<StartTimerRequest>,
+) -> Result<Json<TimeEntry>, AppError> {
+    // Validate request
+    payload.validate()?;
+
+    if let Some(running) = db::get_running_timer(&state.pool, auth.id).await? {
+        return Err(AppError::BadRequest(format!(
+            "A timer is already running (time entry {})",
+            running.id
+        )));
+    }
+    let matter_id = resolve_matter(&state, payload.task_id, payload.matter_id).await?;
+
+    let entry = db::start_timer(&state.pool, auth.id, &payload, matter_id).await?;
+
+    Ok(Json(entry))
+}
+
+pub async fn stop_timer(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(payload): Json<StopTimerRequest>,
+) -> Result<Json<TimeEntry>, AppError> {
+    // Validate request
+    payload.validate()?;
+
+    let running = db::get_running_timer(&state.pool, auth.id)
+        .await?
+        .ok_or_else(|| AppError::BadRequest("No timer is running".to_string()))?;
+
+    let entry = db::stop_timer(&state.pool, running.id, payload.narrative.as_deref()).await?;
+
+    Ok(Json(entry))
+}
+
+/// The caller's running timer, or `null`.
+pub async fn get_running_timer(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<Json<Option<TimeEntry>>, AppError> {
+    let entry = db::get_running_timer(&state.pool, auth.id).await?;
+
+    Ok(Json(entry))
+}
+
+/// Logs time after the fact, from a duration or a start and end time.
+pub async fn create_time_entry(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(payload): Json<CreateTimeEntryRequest>,
+) -> Result<Json<TimeEntry>, AppError> {
+    // Validate request
+    payload.validate()?;
+
+    let duration_minutes = match (payload.duration_minutes, payload.started_at, payload.ended_at) {
+        (Some(minutes), None, None) => minutes,
+        (None, Some(started_at), Some(ended_at)) => {
+            if ended_at <= started_at {
+                return Err(AppError::BadRequest("ended_at must be after started_at".to_string()));
+            }
+            let seconds = (ended_at - started_at).num_seconds();
+            let minutes = (seconds + 59) / 60;
+            if minutes > MAX_ENTRY_MINUTES {
+                return Err(AppError::BadRequest(format!(
+                    "A time entry cannot be longer than {} minutes",
+                    MAX_ENTRY_MINUTES
+                )));
+            }
+            minutes as i32
+        }
+        _ => {
+            return Err(AppError::BadRequest(
+                "Give either duration_minutes or both started_at and ended_at".to_string(),
+            ))
+        }
+    };
+
+    let entry_date = payload.entry_date.unwrap_or_else(|| match payload.started_at {
+        Some(started_at) => started_at.with_timezone(&DEFAULT_TIMEZONE).date_naive(),
+        None => Utc::now().with_timezone(&DEFAULT_TIMEZONE).date_naive(),
+    });
+    let matter_id = resolve_matter(&state, payload.task_id, payload.matter_id).await?;
+
+    let entry = db::create_time_entry(
+        &state.pool,
+        auth.id,
+        &payload,
+        matter_id,
+        entry_date,
+        duration_minutes,
+    )
+    .await?;
+
+    Ok(Json(entry))
+}
+
+/// Non-admins only see their own time.
+pub async fn get_time_entries(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Query(mut params): Query<TimeEntriesQuery>,
+) -> Result<Json<Vec<TimeEntry>>, AppError> {
+    if !auth.is_admin() {
+        params.user_id = Some(auth.id);
+    }
+
+    let entries = db::get_time_entries(&state.pool, &params).await?;
+
+    Ok(Json(entries))
+}
+
+pub async fn get_time_entry(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<TimeEntry>, AppError> {
+    let entry = find_own_time_entry(&state, &auth, id).await?;
+
+    Ok(Json(entry))
+}
+
+pub async fn update_time_entry(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateTimeEntryRequest>,
+) -> Result<Json<TimeEntry>, AppError> {
+    // Validate request
+    payload.validate()?;
+
+    let existing = find_own_time_entry(&state, &auth, id).await?;
+    check_not_locked(&existing)?;
+
+    if existing.is_running() && payload.duration_minutes.is_some() {
+        return Err(AppError::BadRequest(
+            "Stop the timer before changing its duration".to_string(),
+        ));
+    }
+
+    // Moving the entry to another task moves it to that task's matter too,
+    // so that it is invoiced to the right client
+    let matter_id = if payload.task_id.is_some() || payload.matter_id.is_some() {
+        resolve_matter(&state, payload.task_id, payload.matter_id).await?
+    } else {
+        existing.matter_id
+    };
+
+    let entry = db::update_time_entry(&state.pool, id, &payload, matter_id)
+        .await?
+        .ok_or_else(|| AppError::BadRequest("Time entry was invoiced and is locked".to_string()))?;
+
+    Ok(Json(entry))
+}
+
+pub async fn delete_time_entry(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Value>, AppError> {
+    let existing = find_own_time_entry(&state, &auth, id).await?;
+    check_not_locked(&existing)?;
+
+    if !db::delete_time_entry(&state.pool, id).await? {
+        return Err(AppError::BadRequest("Time entry was invoiced and is locked".to_string()));
+    }
+
+    Ok(Json(json!({
+        "message": "Time entry deleted successfully",
+        "id": id
+    })))
+}
+
+/// Time logged on a task; non-admins only see their own entries.
+pub async fn get_task_time_entries(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<TimeEntry>>, AppError> {
+    db::get_task_by_id(&state.pool, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Task with id {} not found", id)))?;
+
+    let params = TimeEntriesQuery {
+        task_id: Some(id),
+        user_id: if auth.is_admin() { None } else { Some(auth.id) },
+        ..Default::default()
+    };
+    let entries = db::get_time_entries(&state.pool, &params).await?;
+
+    Ok(Json(entries))
+}
+
+/// Finished time broken down per user, matter or period (`day`, `week` or
+/// `month`). Non-admins only get a report of their own time.
+pub async fn get_time_report(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Query(mut params): Query<TimeReportQuery>,
+) -> Result<Json<TimeReport>, AppError> {
+    if !auth.is_admin() {
+        params.user_id = Some(auth.id);
+    }
+
+    let group_by = params.group_by.clone().unwrap_or_else(|| "user".to_string());
+    if !["user", "matter", "period"].contains(&group_by.as_str()) {
+        return Err(AppError::BadRequest(format!("Invalid group_by: {}", group_by)));
+    }
+    let period = params.period.clone().unwrap_or_else(|| "week".to_string());
+    if !["day", "week", "month"].contains(&period.as_str()) {
+        return Err(AppError::BadRequest(format!("Invalid period: {}", period)));
+    }
+
+    let rows = db::get_time_report(&state.pool, &group_by, &period, &params).await?;
+
+    Ok(Json(TimeReport {
+        total_minutes: rows.iter().map(|r| r.total_minutes).sum(),
+        billable_minutes: rows.iter().map(|r| r.billable_minutes).sum(),
+        billable_amount_agorot: rows.iter().map(|r| r.billable_amount_agorot).sum(),
+        group_by,
+        from: params.from,
+        to: params.to,
+        rows,
+    }))
+}
+
+/// Sets the rate new time entries of the user default to.
+pub async fn set_user_hourly_rate(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<SetHourlyRateRequest>,
+) -> Result<Json<User>, AppError> {
+    auth.require_admin()?;
+
+    // Validate request
+    payload.validate()?;
+
+    let user = db::set_user_hourly_rate(&state.pool, id, payload.hourly_rate_agorot)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("User with id {} not found", id)))?;
+
+    Ok(Json(user))
+}
+
+/// Checks the task and matter exist, and returns the matter to file the time
+/// under: the one given, or else the task's.
+async fn resolve_matter(
+    state: &AppState,
+    task_id: Option<Uuid>,
+    matter_id: Option<Uuid>,
+) -> Result<Option<Uuid>, AppError> {
+    let task_matter_id = match task_id {
+        Some(task_id) => db::get_task_by_id(&state.pool, task_id)
+            .await?
+            .ok_or_else(|| AppError::BadRequest(format!("Task {} not found", task_id)))?
+            .matter_id,
+        None => None,
+    };
+
+    if let Some(matter_id) = matter_id {
+        db::get_matter_by_id(&state.pool, matter_id)
+            .await?
+            .ok_or_else(|| AppError::BadRequest(format!("Matter {} not found", matter_id)))?;
+    }
+
+    Ok(matter_id.or(task_matter_id))
+}
+
+fn check_not_locked(entry: &TimeEntry) -> Result<(), AppError> {
+    if entry.is_locked() {
+        return Err(AppError::BadRequest(
+            "Time entry was invoiced and is locked".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Users reach their own entries; admins reach everyone's.
+async fn find_own_time_entry(state: &AppState, auth: &AuthUser, id: Uuid) -> Result<TimeEntry, AppError> {
+    let entry = db::get_time_entry_by_id(&state.pool, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Time entry with id {} not found", id)))?;
+
+    if entry.user_id != auth.id && !auth.is_admin() {
+        return Err(AppError::Forbidden("You can only access your own time entries".to_string()));
+    }
+
+    Ok(entry)
+}
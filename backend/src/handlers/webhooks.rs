@@ -0,0 +1,189 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use serde_json::{json, Value};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    db,
+    models::{
+        CreateWebhookRequest, TaskEventType, UpdateWebhookRequest, WebhookDeliveriesQuery,
+        WebhookDelivery, WebhookSubscription, WebhookSubscriptionCreated, WEBHOOK_TEST_EVENT,
+    },
+    services::{attempt_webhook_delivery, generate_webhook_secret, WEBHOOK_LOCK_SECONDS},
+    utils::{AppError, AuthUser},
+    AppState,
+};
+
+const DELIVERY_STATUSES: [&str; 4] = ["pending", "sending", "delivered", "dead"];
+
+pub async fn get_webhooks(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<Json<Vec<WebhookSubscription>>, AppError> {
+    auth.require_admin()?;
+
+    let subscriptions = db::get_webhook_subscriptions(&state.pool).await?;
+
+    Ok(Json(subscriptions))
+}
+
+pub async fn get_webhook(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<WebhookSubscription>, AppError> {
+    auth.require_admin()?;
+
+    let subscription = find_webhook(&state, id).await?;
+
+    Ok(Json(subscription))
+}
+
+pub async fn create_webhook(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(payload): Json<CreateWebhookRequest>,
+) -> Result<Json<WebhookSubscriptionCreated>, AppError> {
+    auth.require_admin()?;
+
+    // Validate request
+    payload.validate()?;
+    validate_event_types(&payload.event_types)?;
+
+    let secret = generate_webhook_secret();
+    let subscription = db::create_webhook_subscription(&state.pool, &payload, &secret, auth.id).await?;
+
+    Ok(Json(WebhookSubscriptionCreated { subscription, secret }))
+}
+
+pub async fn update_webhook(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateWebhookRequest>,
+) -> Result<Json<WebhookSubscription>, AppError> {
+    auth.require_admin()?;
+
+    // Validate request
+    payload.validate()?;
+    if let Some(event_types) = &payload.event_types {
+        validate_event_types(event_types)?;
+    }
+
+    let subscription = db::update_webhook_subscription(&state.pool, id, &payload)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Webhook with id {} not found", id)))?;
+
+    Ok(Json(subscription))
+}
+
+pub async fn delete_webhook(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Value>, AppError> {
+    auth.require_admin()?;
+
+    if !db::delete_webhook_subscription(&state.pool, id).await? {
+        return Err(AppError::NotFound(format!("Webhook with id {} not found", id)));
+    }
+
+    Ok(Json(json!({
+        "message": "Webhook deleted successfully",
+        "id": id
+    })))
+}
+
+/// Sends a `ping` event right away and returns the logged attempt. A failed
+/// test is retried like any other delivery.
+pub async fn send_test_webhook(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<WebhookDelivery>, AppError> {
+    auth.require_admin()?;
+
+    let subscription = find_webhook(&state, id).await?;
+    if !subscription.is_active {
+        return Err(AppError::BadRequest(
+            "Webhook is disabled; re-enable it before testing".to_string(),
+        ));
+    }
+
+    let payload = json!({
+        "message": "Test event",
+        "webhook_id": subscription.id,
+        "sent_by": auth.email,
+    });
+    let delivery = db::create_webhook_test_delivery(
+        &state.pool,
+        subscription.id,
+        WEBHOOK_TEST_EVENT,
+        &payload,
+        WEBHOOK_LOCK_SECONDS,
+    )
+    .await?;
+    let delivery = attempt_webhook_delivery(&state, &subscription, &delivery).await?;
+
+    Ok(Json(delivery))
+}
+
+pub async fn get_webhook_deliveries(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+    Query(params): Query<WebhookDeliveriesQuery>,
+) -> Result<Json<Vec<WebhookDelivery>>, AppError> {
+    auth.require_admin()?;
+
+    find_webhook(&state, id).await?;
+
+    if let Some(status) = &params.status {
+        if !DELIVERY_STATUSES.contains(&status.as_str()) {
+            return Err(AppError::BadRequest(format!("Invalid delivery status: {}", status)));
+        }
+    }
+    let limit = params.limit.unwrap_or(50).clamp(1, 500);
+
+    let deliveries = db::get_webhook_deliveries(&state.pool, id, params.status.as_deref(), limit).await?;
+
+    Ok(Json(deliveries))
+}
+
+pub async fn redeliver_webhook(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((id, delivery_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<WebhookDelivery>, AppError> {
+    auth.require_admin()?;
+
+    let delivery = db::requeue_webhook_delivery(&state.pool, id, delivery_id)
+        .await?
+        .ok_or_else(|| {
+            AppError::NotFound(format!("Dead webhook delivery with id {} not found", delivery_id))
+        })?;
+
+    Ok(Json(delivery))
+}
+
+async fn find_webhook(state: &AppState, id: Uuid) -> Result<WebhookSubscription, AppError> {
+    db::get_webhook_subscription_by_id(&state.pool, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Webhook with id {} not found", id)))
+}
+
+fn validate_event_types(event_types: &[String]) -> Result<(), AppError> {
+    for event_type in event_types {
+        if !TaskEventType::ALL.iter().any(|e| e.as_str() == event_type) {
+            return Err(AppError::ValidationError(format!(
+                "Unknown event type: {}",
+                event_type
+            )));
+        }
+    }
+
+    Ok(())
+}
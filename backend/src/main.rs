@@ -8,9 +8,13 @@ mod utils;
 
 use axum::http::{
     header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE},
-    HeaderValue, Method,
+    HeaderName, HeaderValue, Method,
+};
+use services::{
+    spawn_email_outbox_worker, spawn_notification_digest_job, spawn_push_worker,
+    spawn_recurring_tasks_job, spawn_task_event_listener, spawn_task_reminder_job,
+    spawn_webhook_worker, EmailService, PushService, TaskEventBroadcaster, WebhookService,
 };
-use services::EmailService;
 use shuttle_runtime::SecretStore;
 use sqlx::PgPool;
 use tower_http::{
@@ -19,12 +23,15 @@ use tower_http::{
     trace::{DefaultMakeSpan, DefaultOnResponse, TraceLayer},
 };
 use tracing::Level;
-use utils::Config;
+use utils::{Config, DEFAULT_NOTIFY_ASSIGNEE_FIELDS, DEFAULT_NOTIFY_CREATOR_FIELDS};
 
 #[derive(Clone)]
 pub struct AppState {
     pub pool: PgPool,
     pub email_service: EmailService,
+    pub push_service: PushService,
+    pub task_events: TaskEventBroadcaster,
+    pub webhook_service: WebhookService,
     pub config: Config,
 }
 
@@ -68,12 +75,78 @@ async fn main(
         api_base_url: secrets
             .get("API_BASE_URL")
             .unwrap_or_else(|| "http://localhost:8000".to_string()),
+        frontend_base_url: secrets
+            .get("FRONTEND_BASE_URL")
+            .unwrap_or_else(|| "http://localhost:8080".to_string()),
         admin_email: secrets
             .get("ADMIN_EMAIL")
             .unwrap_or_else(|| "admin@localhost".to_string()),
         rate_limit_requests_per_minute: secrets
             .get("RATE_LIMIT_REQUESTS_PER_MINUTE")
             .unwrap_or_else(|| "60".to_string()),
+        email_max_attempts: secrets
+            .get("EMAIL_MAX_ATTEMPTS")
+            .unwrap_or_else(|| "8".to_string()),
+        notify_assignee_fields: secrets
+            .get("NOTIFY_ASSIGNEE_FIELDS")
+            .unwrap_or_else(|| DEFAULT_NOTIFY_ASSIGNEE_FIELDS.to_string()),
+        notify_creator_fields: secrets
+            .get("NOTIFY_CREATOR_FIELDS")
+            .unwrap_or_else(|| DEFAULT_NOTIFY_CREATOR_FIELDS.to_string()),
+        reminder_offsets_days: secrets
+            .get("REMINDER_OFFSETS_DAYS")
+            .unwrap_or_else(|| "7,2,0".to_string()),
+        reminder_hour: secrets
+            .get("REMINDER_HOUR")
+            .unwrap_or_else(|| "8".to_string()),
+        escalate_creator_after_days: secrets
+            .get("ESCALATE_CREATOR_AFTER_DAYS")
+            .unwrap_or_else(|| "1".to_string()),
+        escalate_admin_after_days: secrets
+            .get("ESCALATE_ADMIN_AFTER_DAYS")
+            .unwrap_or_else(|| "3".to_string()),
+        vapid_public_key: secrets
+            .get("VAPID_PUBLIC_KEY")
+            .unwrap_or_else(|| "".to_string()),
+        vapid_private_key: secrets
+            .get("VAPID_PRIVATE_KEY")
+            .unwrap_or_else(|| "".to_string()),
+        vapid_subject: secrets
+            .get("VAPID_SUBJECT")
+            .unwrap_or_else(|| "".to_string()),
+        webhook_max_attempts: secrets
+            .get("WEBHOOK_MAX_ATTEMPTS")
+            .unwrap_or_else(|| "8".to_string()),
+        webhook_disable_after_failures: secrets
+            .get("WEBHOOK_DISABLE_AFTER_FAILURES")
+            .unwrap_or_else(|| "25".to_string()),
+        inbound_email_secret: secrets
+            .get("INBOUND_EMAIL_SECRET")
+            .unwrap_or_else(|| "".to_string()),
+        inbound_email_domain: secrets
+            .get("INBOUND_EMAIL_DOMAIN")
+            .unwrap_or_else(|| "".to_string()),
+        inbound_intake_address: secrets
+            .get("INBOUND_INTAKE_ADDRESS")
+            .unwrap_or_else(|| "".to_string()),
+        inbound_intake_category: secrets
+            .get("INBOUND_INTAKE_CATEGORY")
+            .unwrap_or_else(|| "מינהלית".to_string()),
+        inbound_authserv_id: secrets
+            .get("INBOUND_AUTHSERV_ID")
+            .unwrap_or_else(|| "".to_string()),
+        vat_rate_percent: secrets
+            .get("VAT_RATE_PERCENT")
+            .unwrap_or_else(|| "18".to_string()),
+        invoice_issuer_name: secrets
+            .get("INVOICE_ISSUER_NAME")
+            .unwrap_or_else(|| "".to_string()),
+        invoice_issuer_tax_id: secrets
+            .get("INVOICE_ISSUER_TAX_ID")
+            .unwrap_or_else(|| "".to_string()),
+        expiry_reminder_offsets_days: secrets
+            .get("EXPIRY_REMINDER_OFFSETS_DAYS")
+            .unwrap_or_else(|| "90,30,14,7,1".to_string()),
     };
 
     // Initialize email service
@@ -82,20 +155,50 @@ async fn main(
         config.smtp_password.clone(),
         config.smtp_host.clone(),
         config.smtp_port_as_u16(),
-    );
+        config.frontend_base_url.clone(),
+    )
+    .expect("Failed to configure SMTP transport")
+    .with_reply_addresses(&config.inbound_email_domain, &config.inbound_email_secret);
+
+    // Initialize Web Push (disabled without VAPID keys)
+    let push_service = PushService::new(
+        &config.vapid_public_key,
+        &config.vapid_private_key,
+        config.vapid_subject_or_default(),
+    )
+    .expect("Failed to configure Web Push");
+
+    let webhook_service = WebhookService::new().expect("Failed to configure webhook client");
 
     // Create application state
     let state = AppState {
         pool,
         email_service,
+        push_service,
+        task_events: TaskEventBroadcaster::new(),
+        webhook_service,
         config,
     };
 
+    // Start background jobs
+    spawn_email_outbox_worker(state.clone());
+    spawn_recurring_tasks_job(state.clone());
+    spawn_notification_digest_job(state.clone());
+    spawn_task_reminder_job(state.clone());
+    spawn_push_worker(state.clone());
+    spawn_task_event_listener(state.clone());
+    spawn_webhook_worker(state.clone());
+
     // Configure CORS
     let cors = CorsLayer::new()
         .allow_origin("*".parse::<HeaderValue>().unwrap())
         .allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE])
-        .allow_headers([CONTENT_TYPE, AUTHORIZATION, ACCEPT])
+        .allow_headers([
+            CONTENT_TYPE,
+            AUTHORIZATION,
+            ACCEPT,
+            HeaderName::from_static("last-event-id"),
+        ])
         .allow_credentials(false);
 
     // Configure tracing
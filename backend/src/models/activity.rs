@@ -0,0 +1,67 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::models::{AuditLog, TaskComment};
+
+/// A single entry in a task's activity feed: either a comment or an
+/// audit log record of the task or of one of its comments.
+#[derive(Debug, Serialize)]
+pub struct ActivityItem {
+    pub kind: String,
+    pub actor_id: Option<Uuid>,
+    pub actor_name: Option<String>,
+    pub summary: String,
+    pub comment_id: Option<Uuid>,
+    pub details: Option<serde_json::Value>,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl From<TaskComment> for ActivityItem {
+    fn from(comment: TaskComment) -> Self {
+        Self {
+            kind: "comment".to_string(),
+            actor_id: comment.author_id,
+            actor_name: Some(comment.author_name),
+            summary: comment.body,
+            comment_id: Some(comment.id),
+            details: None,
+            occurred_at: comment.created_at,
+        }
+    }
+}
+
+impl From<AuditLog> for ActivityItem {
+    fn from(log: AuditLog) -> Self {
+        // Comment records show as `comment_edited` or `comment_deleted`,
+        // with the new text of an edited comment as the summary
+        if log.entity_type == "comment" {
+            let body = log
+                .changes
+                .as_ref()
+                .and_then(|changes| changes.get("body"))
+                .and_then(|body| body.as_str())
+                .map(str::to_string);
+
+            return Self {
+                kind: log.action.clone(),
+                actor_id: log.user_id,
+                actor_name: None,
+                summary: body.unwrap_or(log.action),
+                comment_id: Some(log.entity_id),
+                details: log.changes,
+                occurred_at: log.created_at,
+            };
+        }
+
+        Self {
+            kind: log.entity_type,
+            actor_id: log.user_id,
+            actor_name: None,
+            summary: log.action,
+            comment_id: None,
+            details: log.changes,
+            occurred_at: log.created_at,
+        }
+    }
+}
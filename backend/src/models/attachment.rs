@@ -11,9 +11,26 @@ pub struct Attachment {
     pub file_url: String,
     pub file_size: Option<i64>,
     pub mime_type: Option<String>,
+    pub uploaded_by: Option<Uuid>,
     pub uploaded_at: DateTime<Utc>,
 }
 
+/// A stored file, for download.
+#[derive(Debug, FromRow)]
+pub struct AttachmentContent {
+    pub file_name: String,
+    pub mime_type: Option<String>,
+    pub content: Vec<u8>,
+}
+
+/// A file to be stored with a task, e.g. one received by email.
+#[derive(Debug, Clone)]
+pub struct NewAttachment {
+    pub file_name: String,
+    pub mime_type: Option<String>,
+    pub content: Vec<u8>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct CreateAttachmentRequest {
     pub task_id: Uuid,
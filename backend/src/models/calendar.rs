@@ -0,0 +1,49 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::models::Hearing;
+
+#[derive(Debug, Clone, FromRow)]
+pub struct CalendarFeed {
+    pub user_id: Uuid,
+    pub token_hash: String,
+    pub last_accessed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Whether the caller has a feed; the URL itself is only shown on creation.
+#[derive(Debug, Serialize)]
+pub struct CalendarFeedStatus {
+    pub active: bool,
+    pub created_at: Option<DateTime<Utc>>,
+    pub last_accessed_at: Option<DateTime<Utc>>,
+}
+
+impl From<Option<CalendarFeed>> for CalendarFeedStatus {
+    fn from(feed: Option<CalendarFeed>) -> Self {
+        Self {
+            active: feed.is_some(),
+            created_at: feed.as_ref().map(|f| f.created_at),
+            last_accessed_at: feed.and_then(|f| f.last_accessed_at),
+        }
+    }
+}
+
+/// Returned when a feed is created or its token rotated.
+#[derive(Debug, Serialize)]
+pub struct CalendarFeedCreated {
+    pub url: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A hearing with the matter details shown in calendar events.
+#[derive(Debug, Clone, FromRow)]
+pub struct FeedHearing {
+    #[sqlx(flatten)]
+    pub hearing: Hearing,
+    pub matter_number: String,
+    pub matter_title: String,
+    pub case_number: Option<String>,
+}
@@ -0,0 +1,35 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ChecklistItem {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub title: String,
+    pub position: i32,
+    pub is_done: bool,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub completed_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateChecklistItemRequest {
+    #[validate(length(min = 1, max = 500))]
+    pub title: String,
+
+    pub position: Option<i32>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateChecklistItemRequest {
+    #[validate(length(min = 1, max = 500))]
+    pub title: Option<String>,
+
+    pub position: Option<i32>,
+    pub is_done: Option<bool>,
+}
@@ -0,0 +1,83 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Client {
+    pub id: Uuid,
+    pub name: String,
+    /// `individual` or `organization`
+    pub client_type: String,
+    /// Israeli ID or company number
+    pub id_number: Option<String>,
+    pub email: Option<String>,
+    pub phone: Option<String>,
+    pub address: Option<String>,
+    pub notes: Option<String>,
+    pub is_active: bool,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateClientRequest {
+    #[validate(length(min = 1, max = 300))]
+    pub name: String,
+
+    #[validate(custom = "validate_client_type")]
+    pub client_type: Option<String>,
+
+    #[validate(length(min = 1, max = 50))]
+    pub id_number: Option<String>,
+
+    #[validate(email)]
+    pub email: Option<String>,
+
+    #[validate(length(max = 50))]
+    pub phone: Option<String>,
+
+    pub address: Option<String>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateClientRequest {
+    #[validate(length(min = 1, max = 300))]
+    pub name: Option<String>,
+
+    #[validate(custom = "validate_client_type")]
+    pub client_type: Option<String>,
+
+    #[validate(length(min = 1, max = 50))]
+    pub id_number: Option<String>,
+
+    #[validate(email)]
+    pub email: Option<String>,
+
+    #[validate(length(max = 50))]
+    pub phone: Option<String>,
+
+    pub address: Option<String>,
+    pub notes: Option<String>,
+    pub is_active: Option<bool>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ClientsQuery {
+    /// Matches name, ID number, email or phone
+    pub q: Option<String>,
+    /// Inactive clients are hidden unless asked for
+    #[serde(default)]
+    pub include_inactive: bool,
+}
+
+fn validate_client_type(client_type: &str) -> Result<(), validator::ValidationError> {
+    if ["individual", "organization"].contains(&client_type) {
+        Ok(())
+    } else {
+        Err(validator::ValidationError::new("invalid_client_type"))
+    }
+}
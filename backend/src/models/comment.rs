@@ -0,0 +1,74 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TaskComment {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub author_id: Option<Uuid>,
+    pub author_name: String,
+    pub author_email: String,
+    pub body: String,
+    pub mentioned_user_ids: Vec<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub edited_at: Option<DateTime<Utc>>,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TaskCommentEdit {
+    pub id: Uuid,
+    pub comment_id: Uuid,
+    pub previous_body: String,
+    pub edited_by: Option<Uuid>,
+    pub edited_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateCommentRequest {
+    #[validate(length(min = 1, max = 10000))]
+    pub body: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateCommentRequest {
+    #[validate(length(min = 1, max = 10000))]
+    pub body: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CommentResponse {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub author_id: Option<Uuid>,
+    pub author_name: String,
+    pub author_email: String,
+    pub body: String,
+    pub mentioned_user_ids: Vec<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub edited_at: Option<DateTime<Utc>>,
+    pub is_edited: bool,
+}
+
+impl From<TaskComment> for CommentResponse {
+    fn from(comment: TaskComment) -> Self {
+        Self {
+            id: comment.id,
+            task_id: comment.task_id,
+            author_id: comment.author_id,
+            author_name: comment.author_name,
+            author_email: comment.author_email,
+            body: comment.body,
+            mentioned_user_ids: comment.mentioned_user_ids,
+            created_at: comment.created_at,
+            updated_at: comment.updated_at,
+            is_edited: comment.edited_at.is_some(),
+            edited_at: comment.edited_at,
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::Validate;
+
+/// A name and/or ID number to check before taking on a matter.
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct ConflictCheckRequest {
+    #[validate(length(min = 1, max = 300))]
+    pub name: Option<String>,
+
+    #[validate(length(min = 1, max = 50))]
+    pub id_number: Option<String>,
+
+    /// What the check is for, e.g. the proposed matter
+    #[validate(length(max = 2000))]
+    pub notes: Option<String>,
+}
+
+/// A client, opposing party, related party or matter contact, once per
+/// matter it appears in.
+#[derive(Debug, Clone, FromRow)]
+pub struct ConflictCandidate {
+    /// `client`, `opposing_party`, `related_party` or `contact`
+    pub source: String,
+    pub name: String,
+    pub id_number: Option<String>,
+    pub role: Option<String>,
+    /// Set for clients
+    pub client_id: Option<Uuid>,
+    pub matter_id: Option<Uuid>,
+    pub matter_number: Option<String>,
+    pub matter_title: Option<String>,
+    pub matter_status: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConflictMatter {
+    pub matter_id: Uuid,
+    pub matter_number: String,
+    pub title: String,
+    pub status: String,
+    /// The party's role in the matter; `None` for the client
+    pub role: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConflictHit {
+    pub source: String,
+    pub name: String,
+    pub id_number: Option<String>,
+    pub client_id: Option<Uuid>,
+    /// 0 to 1; 1 is an exact name or ID number match
+    pub score: f64,
+    /// `id_number`, `exact`, `fuzzy` or `transliteration`
+    pub match_type: String,
+    pub matters: Vec<ConflictMatter>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ConflictCheckResponse {
+    /// Audit log reference for the check
+    pub check_id: Uuid,
+    pub checked_at: DateTime<Utc>,
+    pub name: Option<String>,
+    pub id_number: Option<String>,
+    pub hits: Vec<ConflictHit>,
+}
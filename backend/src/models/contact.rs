@@ -0,0 +1,155 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::{Validate, ValidateEmail};
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Contact {
+    pub id: Uuid,
+    /// `person` or `organization`
+    pub contact_type: String,
+    pub name: String,
+    pub organization: Option<String>,
+    pub job_title: Option<String>,
+    pub id_number: Option<String>,
+    pub roles: Vec<String>,
+    pub phones: sqlx::types::Json<Vec<ContactPoint>>,
+    pub emails: sqlx::types::Json<Vec<ContactPoint>>,
+    pub addresses: sqlx::types::Json<Vec<ContactPoint>>,
+    pub notes: Option<String>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A phone number, email or address, with an optional label such as `נייד`
+/// or `work`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Validate)]
+pub struct ContactPoint {
+    #[validate(length(max = 50))]
+    pub label: Option<String>,
+
+    #[validate(length(min = 1, max = 500))]
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct CreateContactRequest {
+    #[validate(custom = "validate_contact_type")]
+    pub contact_type: Option<String>,
+
+    #[validate(length(min = 1, max = 300))]
+    pub name: String,
+
+    #[validate(length(max = 300))]
+    pub organization: Option<String>,
+
+    #[validate(length(max = 200))]
+    pub job_title: Option<String>,
+
+    #[validate(length(min = 1, max = 50))]
+    pub id_number: Option<String>,
+
+    #[serde(default)]
+    pub roles: Vec<String>,
+
+    #[serde(default)]
+    #[validate(nested)]
+    pub phones: Vec<ContactPoint>,
+
+    #[serde(default)]
+    #[validate(nested, custom = "validate_emails")]
+    pub emails: Vec<ContactPoint>,
+
+    #[serde(default)]
+    #[validate(nested)]
+    pub addresses: Vec<ContactPoint>,
+
+    pub notes: Option<String>,
+}
+
+/// Lists given replace the contact's lists.
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateContactRequest {
+    #[validate(custom = "validate_contact_type")]
+    pub contact_type: Option<String>,
+
+    #[validate(length(min = 1, max = 300))]
+    pub name: Option<String>,
+
+    #[validate(length(max = 300))]
+    pub organization: Option<String>,
+
+    #[validate(length(max = 200))]
+    pub job_title: Option<String>,
+
+    #[validate(length(min = 1, max = 50))]
+    pub id_number: Option<String>,
+
+    pub roles: Option<Vec<String>>,
+
+    #[validate(nested)]
+    pub phones: Option<Vec<ContactPoint>>,
+
+    #[validate(nested, custom = "validate_emails")]
+    pub emails: Option<Vec<ContactPoint>>,
+
+    #[validate(nested)]
+    pub addresses: Option<Vec<ContactPoint>>,
+
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ContactsQuery {
+    /// Matches name, organization, phones and emails
+    pub q: Option<String>,
+    pub role: Option<String>,
+    pub contact_type: Option<String>,
+}
+
+/// Links a contact to a task or matter.
+#[derive(Debug, Deserialize, Validate)]
+pub struct LinkContactRequest {
+    pub contact_id: Uuid,
+
+    /// The contact's part, e.g. `opposing counsel` or `expert witness`
+    #[validate(length(min = 1, max = 100))]
+    pub role: String,
+}
+
+/// A contact as linked to a task or matter.
+#[derive(Debug, Serialize, FromRow)]
+pub struct LinkedContact {
+    pub link_id: Uuid,
+    pub link_role: String,
+    #[sqlx(flatten)]
+    #[serde(flatten)]
+    pub contact: Contact,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ContactImportResult {
+    pub imported: Vec<Contact>,
+    /// Contacts already in the directory
+    pub duplicates: Vec<String>,
+    /// Cards that could not be read, with the reason
+    pub errors: Vec<String>,
+}
+
+fn validate_contact_type(contact_type: &str) -> Result<(), validator::ValidationError> {
+    if ["person", "organization"].contains(&contact_type) {
+        Ok(())
+    } else {
+        Err(validator::ValidationError::new("invalid_contact_type"))
+    }
+}
+
+fn validate_emails(emails: &Vec<ContactPoint>) -> Result<(), validator::ValidationError> {
+    if emails.iter().all(|e| e.value.validate_email()) {
+        Ok(())
+    } else {
+        Err(validator::ValidationError::new("invalid_email"))
+    }
+}
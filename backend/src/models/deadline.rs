@@ -0,0 +1,100 @@
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{types::Json, FromRow};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::models::TaskResponse;
+
+/// Years the court calendar, and so the deadline calculator, covers.
+pub const COURT_CALENDAR_YEARS: std::ops::RangeInclusive<i32> = 1900..=2200;
+
+/// A deadline computed for a task, kept so that it can be checked later.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct TaskDeadline {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub trigger_date: NaiveDate,
+    /// The rule in canonical form, e.g. `30 business days; skip court recess`
+    pub rule: String,
+    pub due_date: NaiveDate,
+    pub previous_due_date: Option<NaiveDate>,
+    pub skipped_days: Json<Vec<SkippedDay>>,
+    pub note: Option<String>,
+    pub computed_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A day the count passed over, or the due date was moved off.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkippedDay {
+    pub date: NaiveDate,
+    /// `שבת`, a holiday or a recess name
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DeadlineCalculation {
+    pub trigger_date: NaiveDate,
+    pub rule: String,
+    pub due_date: NaiveDate,
+    pub skipped_days: Vec<SkippedDay>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CalculateDeadlineRequest {
+    /// E.g. the day the judgment was handed down
+    #[validate(custom = "validate_trigger_date")]
+    pub trigger_date: NaiveDate,
+
+    #[validate(length(min = 1, max = 200))]
+    pub rule: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct SetTaskDeadlineRequest {
+    #[validate(custom = "validate_trigger_date")]
+    pub trigger_date: NaiveDate,
+
+    #[validate(length(min = 1, max = 200))]
+    pub rule: String,
+
+    /// E.g. the judgment or the regulation the rule comes from
+    #[validate(length(max = 1000))]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SetTaskDeadlineResponse {
+    pub task: TaskResponse,
+    pub deadline: TaskDeadline,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CourtHoliday {
+    pub date: NaiveDate,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CourtRecess {
+    pub name: String,
+    /// First and last day of the recess, inclusive
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CourtCalendarYear {
+    pub year: i32,
+    pub holidays: Vec<CourtHoliday>,
+    pub recess: Vec<CourtRecess>,
+}
+
+fn validate_trigger_date(date: &NaiveDate) -> Result<(), validator::ValidationError> {
+    if COURT_CALENDAR_YEARS.contains(&date.year()) {
+        Ok(())
+    } else {
+        Err(validator::ValidationError::new("trigger_date_out_of_range"))
+    }
+}
@@ -0,0 +1,29 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::models::TaskResponse;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TaskDependency {
+    pub task_id: Uuid,
+    pub blocked_by_id: Uuid,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateDependencyRequest {
+    pub blocked_by_id: Uuid,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TaskDependenciesResponse {
+    pub task_id: Uuid,
+    pub is_blocked: bool,
+    /// Tasks that must be finished before this one
+    pub blocked_by: Vec<TaskResponse>,
+    /// Tasks waiting on this one
+    pub blocking: Vec<TaskResponse>,
+}
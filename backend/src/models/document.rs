@@ -0,0 +1,93 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use std::collections::HashMap;
+use uuid::Uuid;
+use validator::Validate;
+
+pub const DOCUMENT_FORMAT_HTML: &str = "html";
+pub const DOCUMENT_FORMAT_DOCX: &str = "docx";
+
+/// A template for routine documents, without the file itself.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DocumentTemplate {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    /// `html` or `docx`
+    pub format: String,
+    /// Name of generated documents, e.g. `ייפוי כוח - {client.name}`
+    pub output_file_name: String,
+    pub placeholders: Vec<String>,
+    pub is_active: bool,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// The template file, for filling in or download.
+#[derive(Debug, FromRow)]
+pub struct DocumentTemplateContent {
+    pub name: String,
+    pub format: String,
+    pub output_file_name: String,
+    pub placeholders: Vec<String>,
+    pub content: Vec<u8>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateDocumentTemplateRequest {
+    #[validate(length(min = 1, max = 200))]
+    pub name: String,
+
+    pub description: Option<String>,
+
+    #[validate(custom = "validate_document_format")]
+    pub format: String,
+
+    /// Defaults to the template name
+    #[validate(length(min = 1, max = 300))]
+    pub output_file_name: Option<String>,
+
+    /// The HTML itself, or the DOCX file in base64
+    #[validate(length(min = 1))]
+    pub content: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateDocumentTemplateRequest {
+    #[validate(length(min = 1, max = 200))]
+    pub name: Option<String>,
+
+    pub description: Option<String>,
+
+    #[validate(length(min = 1, max = 300))]
+    pub output_file_name: Option<String>,
+
+    /// A new version of the template, in its existing format
+    #[validate(length(min = 1))]
+    pub content: Option<String>,
+}
+
+/// Fills a template with the task's details and saves the result as an
+/// attachment on the task.
+#[derive(Debug, Deserialize, Validate)]
+pub struct GenerateDocumentRequest {
+    pub template_id: Uuid,
+
+    /// Values for placeholders the records do not cover, or to override them
+    #[serde(default)]
+    pub variables: HashMap<String, String>,
+
+    /// Overrides the template's output file name
+    #[validate(length(min = 1, max = 300))]
+    pub file_name: Option<String>,
+}
+
+fn validate_document_format(format: &str) -> Result<(), validator::ValidationError> {
+    if [DOCUMENT_FORMAT_HTML, DOCUMENT_FORMAT_DOCX].contains(&format) {
+        Ok(())
+    } else {
+        Err(validator::ValidationError::new("invalid_document_format"))
+    }
+}
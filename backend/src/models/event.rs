@@ -0,0 +1,128 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::models::{CommentResponse, Task, TaskComment};
+
+/// Kinds of change published on the real-time event stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskEventType {
+    TaskCreated,
+    TaskUpdated,
+    TaskDeleted,
+    CommentCreated,
+    CommentUpdated,
+    CommentDeleted,
+}
+
+impl TaskEventType {
+    pub const ALL: [TaskEventType; 6] = [
+        TaskEventType::TaskCreated,
+        TaskEventType::TaskUpdated,
+        TaskEventType::TaskDeleted,
+        TaskEventType::CommentCreated,
+        TaskEventType::CommentUpdated,
+        TaskEventType::CommentDeleted,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskEventType::TaskCreated => "task_created",
+            TaskEventType::TaskUpdated => "task_updated",
+            TaskEventType::TaskDeleted => "task_deleted",
+            TaskEventType::CommentCreated => "comment_created",
+            TaskEventType::CommentUpdated => "comment_updated",
+            TaskEventType::CommentDeleted => "comment_deleted",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct TaskEvent {
+    pub id: i64,
+    pub event_type: String,
+    pub task_id: Uuid,
+    #[serde(skip_serializing)]
+    pub visible_to: Vec<String>,
+    pub payload: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TaskEvent {
+    /// Whether a non-admin user may see the event.
+    pub fn is_visible_to(&self, email: &str) -> bool {
+        let email = email.to_lowercase();
+        self.visible_to.iter().any(|e| *e == email)
+    }
+}
+
+/// An event to be appended to the stream, in the transaction of the change.
+#[derive(Debug, Clone)]
+pub struct NewTaskEvent {
+    pub event_type: TaskEventType,
+    pub task_id: Uuid,
+    pub visible_to: Vec<String>,
+    pub payload: serde_json::Value,
+}
+
+impl NewTaskEvent {
+    /// A task event carrying the task itself, or only its ids once deleted.
+    /// The assignee and creator may see it.
+    pub fn for_task(event_type: TaskEventType, task: &Task) -> Self {
+        let payload = match event_type {
+            TaskEventType::TaskDeleted => json!({ "id": task.id, "task_id": task.task_id }),
+            _ => json!(task),
+        };
+
+        Self {
+            event_type,
+            task_id: task.id,
+            visible_to: audience(task),
+            payload,
+        }
+    }
+
+    /// A comment event, visible to whoever may see the task.
+    pub fn for_comment(event_type: TaskEventType, task: &Task, comment: &TaskComment) -> Self {
+        let payload = match event_type {
+            TaskEventType::CommentDeleted => json!({ "id": comment.id, "task_id": task.id }),
+            _ => json!(CommentResponse::from(comment.clone())),
+        };
+
+        Self {
+            event_type,
+            task_id: task.id,
+            visible_to: audience(task),
+            payload,
+        }
+    }
+
+    /// Also shows the event to `email`, e.g. the previous assignee of a
+    /// reassigned task.
+    pub fn also_visible_to(mut self, email: &str) -> Self {
+        let email = email.to_lowercase();
+        if !self.visible_to.contains(&email) {
+            self.visible_to.push(email);
+        }
+        self
+    }
+}
+
+fn audience(task: &Task) -> Vec<String> {
+    let mut emails = vec![task.assigned_to_email.to_lowercase()];
+    let creator = task.created_by_email.to_lowercase();
+    if !emails.contains(&creator) {
+        emails.push(creator);
+    }
+    emails
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EventsQuery {
+    /// Resume point for clients that cannot send the `Last-Event-ID` header
+    pub last_event_id: Option<i64>,
+    /// JWT for clients that cannot send the `Authorization` header, e.g. `EventSource`
+    pub token: Option<String>,
+}
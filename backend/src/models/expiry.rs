@@ -0,0 +1,111 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::Validate;
+
+pub const EXPIRY_LIMITATION: &str = "limitation";
+pub const EXPIRY_LIEN_RENEWAL: &str = "lien_renewal";
+pub const EXPIRY_LICENSE: &str = "license_expiry";
+pub const EXPIRY_OTHER: &str = "other";
+
+/// A date by which something must be done on a matter before a right is
+/// lost, e.g. filing before the limitation period runs out.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct MatterExpiry {
+    pub id: Uuid,
+    pub matter_id: Uuid,
+    /// `limitation`, `lien_renewal`, `license_expiry` or `other`
+    pub expiry_type: String,
+    pub expires_on: NaiveDate,
+    /// How the date was worked out
+    pub basis: String,
+    pub notes: Option<String>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub resolved_by: Option<Uuid>,
+    pub resolution: Option<String>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl MatterExpiry {
+    /// The type as written in reminders and reports.
+    pub fn type_label(&self) -> &'static str {
+        match self.expiry_type.as_str() {
+            EXPIRY_LIMITATION => "התיישנות",
+            EXPIRY_LIEN_RENEWAL => "חידוש שעבוד",
+            EXPIRY_LICENSE => "פקיעת רישיון",
+            _ => "מועד פקיעה",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateMatterExpiryRequest {
+    #[validate(custom = "validate_expiry_type")]
+    pub expiry_type: String,
+
+    pub expires_on: NaiveDate,
+
+    /// E.g. `7 years from the accident on 01/03/2020`
+    #[validate(length(min = 1, max = 2000))]
+    pub basis: String,
+
+    pub notes: Option<String>,
+}
+
+/// A new date starts the reminders over, e.g. after a lien is renewed.
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateMatterExpiryRequest {
+    #[validate(custom = "validate_expiry_type")]
+    pub expiry_type: Option<String>,
+
+    pub expires_on: Option<NaiveDate>,
+
+    #[validate(length(min = 1, max = 2000))]
+    pub basis: Option<String>,
+
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ResolveMatterExpiryRequest {
+    /// What was done, e.g. `claim filed on 12/05/2025`
+    #[validate(length(min = 1, max = 2000))]
+    pub resolution: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExpiryReportQuery {
+    /// How far ahead to look; defaults to 90 days. Dates that have passed
+    /// without being resolved are always included.
+    pub days: Option<i64>,
+    pub expiry_type: Option<String>,
+    pub lawyer_id: Option<Uuid>,
+}
+
+/// An unresolved expiry with its matter and the lawyer to remind.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct UpcomingExpiry {
+    #[sqlx(flatten)]
+    #[serde(flatten)]
+    pub expiry: MatterExpiry,
+    pub matter_number: String,
+    pub matter_title: String,
+    pub client_name: String,
+    pub lawyer_id: Option<Uuid>,
+    /// Not set when the matter has no lawyer or the lawyer is inactive
+    pub lawyer_name: Option<String>,
+    pub lawyer_email: Option<String>,
+    /// Negative once the date has passed
+    pub days_left: i32,
+}
+
+fn validate_expiry_type(expiry_type: &str) -> Result<(), validator::ValidationError> {
+    if [EXPIRY_LIMITATION, EXPIRY_LIEN_RENEWAL, EXPIRY_LICENSE, EXPIRY_OTHER].contains(&expiry_type) {
+        Ok(())
+    } else {
+        Err(validator::ValidationError::new("invalid_expiry_type"))
+    }
+}
@@ -0,0 +1,165 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::models::{validate_priority, TaskResponse};
+
+pub const HEARING_SCHEDULED: &str = "scheduled";
+pub const HEARING_HELD: &str = "held";
+pub const HEARING_CANCELLED: &str = "cancelled";
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Hearing {
+    pub id: Uuid,
+    pub matter_id: Uuid,
+    pub hearing_type: String,
+    pub scheduled_at: DateTime<Utc>,
+    pub court: Option<String>,
+    pub judge: Option<String>,
+    pub courtroom: Option<String>,
+    pub notes: Option<String>,
+    /// `scheduled`, `held` or `cancelled`
+    pub status: String,
+    pub cancelled_at: Option<DateTime<Utc>>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A task created for every hearing of a type, `days_before` the hearing.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct HearingPreparationStep {
+    pub id: Uuid,
+    /// `None` for steps that apply to every hearing
+    pub hearing_type: Option<String>,
+    pub title: String,
+    pub days_before: i32,
+    pub category: String,
+    pub priority: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A generated task and the offset it was created at.
+#[derive(Debug, Clone, FromRow)]
+pub struct HearingTaskLink {
+    pub task_id: Uuid,
+    pub days_before: i32,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateHearingRequest {
+    pub matter_id: Uuid,
+
+    #[validate(length(min = 1, max = 100))]
+    pub hearing_type: String,
+
+    pub scheduled_at: DateTime<Utc>,
+
+    /// Defaults to the matter's court
+    #[validate(length(max = 200))]
+    pub court: Option<String>,
+
+    #[validate(length(max = 200))]
+    pub judge: Option<String>,
+
+    #[validate(length(max = 50))]
+    pub courtroom: Option<String>,
+
+    pub notes: Option<String>,
+
+    /// Who the preparation tasks go to; defaults to the matter's responsible
+    /// lawyer, or else the caller
+    #[validate(length(min = 1, max = 100))]
+    pub assigned_to: Option<String>,
+
+    #[validate(email)]
+    pub assigned_to_email: Option<String>,
+
+    /// Whether to create the preparation tasks (default: yes)
+    pub create_tasks: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateHearingRequest {
+    #[validate(length(min = 1, max = 100))]
+    pub hearing_type: Option<String>,
+
+    /// Moving the hearing moves its open preparation tasks with it
+    pub scheduled_at: Option<DateTime<Utc>>,
+
+    #[validate(length(max = 200))]
+    pub court: Option<String>,
+
+    #[validate(length(max = 200))]
+    pub judge: Option<String>,
+
+    #[validate(length(max = 50))]
+    pub courtroom: Option<String>,
+
+    pub notes: Option<String>,
+
+    /// `scheduled` or `held`; hearings are cancelled through their own endpoint
+    #[validate(custom = "validate_hearing_status")]
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct CancelHearingRequest {
+    /// Also cancel the hearing's open preparation tasks
+    #[serde(default)]
+    pub cancel_tasks: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct HearingsQuery {
+    pub matter_id: Option<Uuid>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub include_cancelled: bool,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreatePreparationStepRequest {
+    #[validate(length(min = 1, max = 100))]
+    pub hearing_type: Option<String>,
+
+    #[validate(length(min = 1, max = 500))]
+    pub title: String,
+
+    #[validate(range(min = 0, max = 365))]
+    pub days_before: i32,
+
+    #[validate(length(min = 1, max = 100))]
+    pub category: Option<String>,
+
+    #[validate(custom = "validate_priority")]
+    pub priority: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HearingResponse {
+    #[serde(flatten)]
+    pub hearing: Hearing,
+    pub tasks: Vec<TaskResponse>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CancelHearingResponse {
+    pub hearing: Hearing,
+    pub cancelled_tasks: Vec<TaskResponse>,
+    /// Preparation tasks left open, which the caller may still cancel
+    pub open_tasks: Vec<TaskResponse>,
+}
+
+fn validate_hearing_status(status: &str) -> Result<(), validator::ValidationError> {
+    if [HEARING_SCHEDULED, HEARING_HELD].contains(&status) {
+        Ok(())
+    } else {
+        Err(validator::ValidationError::new("invalid_hearing_status"))
+    }
+}
@@ -0,0 +1,62 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// A message received on the inbound endpoint and what became of it.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct InboundEmail {
+    pub id: Uuid,
+    pub message_id: String,
+    pub sender: Option<String>,
+    pub subject: Option<String>,
+    pub outcome: String,
+    pub reason: Option<String>,
+    pub task_id: Option<Uuid>,
+    pub comment_id: Option<Uuid>,
+    pub attachments_saved: i32,
+    pub received_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewInboundEmail {
+    pub message_id: String,
+    pub sender: Option<String>,
+    pub subject: Option<String>,
+    pub outcome: InboundOutcome,
+    pub reason: Option<String>,
+    pub task_id: Option<Uuid>,
+    pub comment_id: Option<Uuid>,
+    pub attachments_saved: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InboundOutcome {
+    Comment,
+    Task,
+    Rejected,
+}
+
+impl InboundOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InboundOutcome::Comment => "comment",
+            InboundOutcome::Task => "task",
+            InboundOutcome::Rejected => "rejected",
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct InboundEmailResponse {
+    #[serde(flatten)]
+    pub email: InboundEmail,
+    /// The message had already been processed
+    pub duplicate: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct InboundEmailsQuery {
+    pub outcome: Option<String>,
+    pub limit: Option<i64>,
+}
@@ -0,0 +1,102 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::models::TimeEntry;
+
+pub const INVOICE_DRAFT: &str = "draft";
+pub const INVOICE_ISSUED: &str = "issued";
+
+/// Money amounts are in agorot; the VAT rate is in basis points.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Invoice {
+    pub id: Uuid,
+    pub invoice_year: Option<i32>,
+    pub sequence_number: Option<i32>,
+    /// `2025-0001`, once issued
+    pub invoice_number: Option<String>,
+    pub client_id: Uuid,
+    pub matter_id: Option<Uuid>,
+    pub period_from: NaiveDate,
+    pub period_to: NaiveDate,
+    /// `draft` or `issued`
+    pub status: String,
+    pub subtotal_agorot: i64,
+    pub vat_rate_basis_points: i32,
+    pub vat_agorot: i64,
+    pub total_agorot: i64,
+    pub notes: Option<String>,
+    pub issued_at: Option<DateTime<Utc>>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct InvoiceLine {
+    pub id: Uuid,
+    pub invoice_id: Uuid,
+    pub position: i32,
+    pub description: String,
+    pub entries: i32,
+    pub minutes: i32,
+    pub hourly_rate_agorot: i64,
+    pub amount_agorot: i64,
+}
+
+/// A line before it is stored.
+#[derive(Debug, Clone)]
+pub struct NewInvoiceLine {
+    pub description: String,
+    pub entries: i32,
+    pub minutes: i32,
+    pub hourly_rate_agorot: i64,
+    pub amount_agorot: i64,
+}
+
+/// A billable entry with what its invoice line is described by.
+#[derive(Debug, Clone, FromRow)]
+pub struct InvoiceableEntry {
+    #[sqlx(flatten)]
+    pub entry: TimeEntry,
+    pub matter_number: String,
+    pub task_title: Option<String>,
+    pub task_code: Option<String>,
+}
+
+/// Drafts an invoice for a client's matters, or for one matter, from the
+/// billable entries in the period that are not yet invoiced.
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateInvoiceRequest {
+    /// Required unless `matter_id` is given
+    pub client_id: Option<Uuid>,
+    pub matter_id: Option<Uuid>,
+    pub period_from: NaiveDate,
+    pub period_to: NaiveDate,
+
+    /// Defaults to the configured rate
+    #[validate(range(min = 0, max = 10000))]
+    pub vat_rate_basis_points: Option<i32>,
+
+    #[validate(length(max = 5000))]
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct InvoicesQuery {
+    pub client_id: Option<Uuid>,
+    pub matter_id: Option<Uuid>,
+    pub status: Option<String>,
+    pub year: Option<i32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InvoiceResponse {
+    #[serde(flatten)]
+    pub invoice: Invoice,
+    pub lines: Vec<InvoiceLine>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
@@ -0,0 +1,166 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::Validate;
+
+pub const MATTER_OPEN: &str = "open";
+pub const MATTER_CLOSED: &str = "closed";
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Matter {
+    pub id: Uuid,
+    /// Office file number, e.g. `2024-0153`
+    pub matter_number: String,
+    pub title: String,
+    pub client_id: Uuid,
+    pub opposing_party: Option<String>,
+    pub court: Option<String>,
+    pub case_number: Option<String>,
+    pub responsible_lawyer_id: Option<Uuid>,
+    pub description: Option<String>,
+    /// `open` or `closed`
+    pub status: String,
+    pub opened_at: NaiveDate,
+    pub closed_at: Option<DateTime<Utc>>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateMatterRequest {
+    #[validate(length(min = 1, max = 50))]
+    pub matter_number: String,
+
+    #[validate(length(min = 1, max = 500))]
+    pub title: String,
+
+    pub client_id: Uuid,
+
+    pub opposing_party: Option<String>,
+
+    #[validate(length(max = 200))]
+    pub court: Option<String>,
+
+    #[validate(length(max = 100))]
+    pub case_number: Option<String>,
+
+    pub responsible_lawyer_id: Option<Uuid>,
+    pub description: Option<String>,
+
+    /// Defaults to today
+    pub opened_at: Option<NaiveDate>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateMatterRequest {
+    #[validate(length(min = 1, max = 50))]
+    pub matter_number: Option<String>,
+
+    #[validate(length(min = 1, max = 500))]
+    pub title: Option<String>,
+
+    pub client_id: Option<Uuid>,
+    pub opposing_party: Option<String>,
+
+    #[validate(length(max = 200))]
+    pub court: Option<String>,
+
+    #[validate(length(max = 100))]
+    pub case_number: Option<String>,
+
+    pub responsible_lawyer_id: Option<Uuid>,
+    pub description: Option<String>,
+    pub opened_at: Option<NaiveDate>,
+
+    /// Closing a matter with open tasks is allowed, but flagged
+    #[validate(custom = "validate_matter_status")]
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct MattersQuery {
+    pub client_id: Option<Uuid>,
+    pub status: Option<String>,
+    pub responsible_lawyer_id: Option<Uuid>,
+    /// Matches matter number, title, case number or opposing party
+    pub q: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MatterResponse {
+    #[serde(flatten)]
+    pub matter: Matter,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+impl From<Matter> for MatterResponse {
+    fn from(matter: Matter) -> Self {
+        Self {
+            matter,
+            warnings: Vec::new(),
+        }
+    }
+}
+
+/// Task counts for one matter, by status.
+#[derive(Debug, Serialize, FromRow)]
+pub struct MatterStats {
+    pub matter_id: Uuid,
+    pub total: i64,
+    pub new: i64,
+    pub in_progress: i64,
+    pub completed: i64,
+    pub cancelled: i64,
+    /// Open tasks past their due date
+    pub overdue: i64,
+    /// Earliest due date among open tasks
+    pub next_due_date: Option<NaiveDate>,
+}
+
+/// Someone else involved in a matter, checked for conflicts of interest.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct MatterParty {
+    pub id: Uuid,
+    pub matter_id: Uuid,
+    pub name: String,
+    pub id_number: Option<String>,
+    /// `opposing_party`, `opposing_counsel` or `related`
+    pub role: String,
+    pub notes: Option<String>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateMatterPartyRequest {
+    #[validate(length(min = 1, max = 300))]
+    pub name: String,
+
+    #[validate(length(min = 1, max = 50))]
+    pub id_number: Option<String>,
+
+    /// Defaults to `related`
+    #[validate(custom = "validate_party_role")]
+    pub role: Option<String>,
+
+    pub notes: Option<String>,
+}
+
+fn validate_party_role(role: &str) -> Result<(), validator::ValidationError> {
+    if ["opposing_party", "opposing_counsel", "related"].contains(&role) {
+        Ok(())
+    } else {
+        Err(validator::ValidationError::new("invalid_party_role"))
+    }
+}
+
+fn validate_matter_status(status: &str) -> Result<(), validator::ValidationError> {
+    if [MATTER_OPEN, MATTER_CLOSED].contains(&status) {
+        Ok(())
+    } else {
+        Err(validator::ValidationError::new("invalid_matter_status"))
+    }
+}
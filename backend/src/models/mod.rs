@@ -2,8 +2,54 @@ pub mod task;
 pub mod user;
 pub mod attachment;
 pub mod audit;
+pub mod comment;
+pub mod activity;
+pub mod checklist;
+pub mod dependency;
+pub mod recurring;
+pub mod template;
+pub mod outbox;
+pub mod notification;
+pub mod push;
+pub mod event;
+pub mod webhook;
+pub mod inbound;
+pub mod client;
+pub mod matter;
+pub mod deadline;
+pub mod hearing;
+pub mod calendar;
+pub mod time_entry;
+pub mod invoice;
+pub mod conflict;
+pub mod contact;
+pub mod document;
+pub mod expiry;
 
 pub use task::*;
 pub use user::*;
 pub use attachment::*;
 pub use audit::*;
+pub use comment::*;
+pub use activity::*;
+pub use checklist::*;
+pub use dependency::*;
+pub use recurring::*;
+pub use template::*;
+pub use outbox::*;
+pub use notification::*;
+pub use push::*;
+pub use event::*;
+pub use webhook::*;
+pub use inbound::*;
+pub use client::*;
+pub use matter::*;
+pub use deadline::*;
+pub use hearing::*;
+pub use calendar::*;
+pub use time_entry::*;
+pub use invoice::*;
+pub use conflict::*;
+pub use contact::*;
+pub use document::*;
+pub use expiry::*;
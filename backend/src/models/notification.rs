@@ -0,0 +1,262 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::models::{priority_rank, validate_priority};
+
+/// Time zone used when a user has not chosen one.
+pub const DEFAULT_TIMEZONE: Tz = chrono_tz::Asia::Jerusalem;
+
+/// Kinds of events a user can tune notifications for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationEvent {
+    TaskCreated,
+    TaskAssigned,
+    TaskUpdated,
+    StatusChanged,
+    Comment,
+    DueSoon,
+}
+
+impl NotificationEvent {
+    pub const ALL: [NotificationEvent; 6] = [
+        NotificationEvent::TaskCreated,
+        NotificationEvent::TaskAssigned,
+        NotificationEvent::TaskUpdated,
+        NotificationEvent::StatusChanged,
+        NotificationEvent::Comment,
+        NotificationEvent::DueSoon,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationEvent::TaskCreated => "task_created",
+            NotificationEvent::TaskAssigned => "task_assigned",
+            NotificationEvent::TaskUpdated => "task_updated",
+            NotificationEvent::StatusChanged => "status_changed",
+            NotificationEvent::Comment => "comment",
+            NotificationEvent::DueSoon => "due_soon",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct NotificationSettings {
+    pub user_id: Uuid,
+    pub timezone: String,
+    pub quiet_hours_start: Option<NaiveTime>,
+    pub quiet_hours_end: Option<NaiveTime>,
+    pub digest_time: NaiveTime,
+    pub last_digest_sent_at: Option<DateTime<Utc>>,
+    /// `off`, `daily` or `weekly` summary of the user's tasks
+    pub task_digest: String,
+    /// Day the weekly digest goes out, 0 = Sunday
+    pub digest_weekday: i16,
+}
+
+impl NotificationSettings {
+    pub fn default_for(user_id: Uuid) -> Self {
+        Self {
+            user_id,
+            timezone: DEFAULT_TIMEZONE.name().to_string(),
+            quiet_hours_start: None,
+            quiet_hours_end: None,
+            digest_time: NaiveTime::from_hms_opt(8, 0, 0).unwrap_or_default(),
+            last_digest_sent_at: None,
+            task_digest: "off".to_string(),
+            digest_weekday: 0,
+        }
+    }
+
+    pub fn tz(&self) -> Tz {
+        self.timezone.parse().unwrap_or(DEFAULT_TIMEZONE)
+    }
+
+    /// When the quiet period `now` falls in ends, or `None` outside quiet hours.
+    pub fn quiet_hours_end_after(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let (start, end) = (self.quiet_hours_start?, self.quiet_hours_end?);
+        if start == end {
+            return None;
+        }
+
+        let tz = self.tz();
+        let local = now.with_timezone(&tz);
+        let time = local.time();
+
+        // A window such as 22:00-07:00 wraps past midnight
+        let quiet = if start < end {
+            time >= start && time < end
+        } else {
+            time >= start || time < end
+        };
+        if !quiet {
+            return None;
+        }
+
+        let end_date = if time < end {
+            local.date_naive()
+        } else {
+            local.date_naive() + Duration::days(1)
+        };
+
+        // The end time may not exist on a DST change day; fall back to an hour
+        Some(
+            tz.from_local_datetime(&end_date.and_time(end))
+                .earliest()
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(|| now + Duration::hours(1)),
+        )
+    }
+
+    /// Today's date in the user's time zone.
+    pub fn local_date(&self, now: DateTime<Utc>) -> NaiveDate {
+        now.with_timezone(&self.tz()).date_naive()
+    }
+
+    /// Whether the daily digest is due: the user's digest time has passed
+    /// today and no digest went out yet today.
+    pub fn digest_due(&self, now: DateTime<Utc>) -> bool {
+        let local = now.with_timezone(&self.tz());
+        if local.time() < self.digest_time {
+            return false;
+        }
+
+        self.last_digest_sent_at
+            .map_or(true, |sent| self.local_date(sent) < local.date_naive())
+    }
+
+    /// Whether today's digest should include the task summary, and how far
+    /// back its "what changed" section looks.
+    pub fn task_digest_window(&self, now: DateTime<Utc>) -> Option<Duration> {
+        match self.task_digest.as_str() {
+            "daily" => Some(Duration::days(1)),
+            "weekly" => {
+                let weekday = now.with_timezone(&self.tz()).weekday().num_days_from_sunday();
+                (weekday as i16 == self.digest_weekday).then(|| Duration::days(7))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct NotificationPreference {
+    pub user_id: Uuid,
+    pub event_type: String,
+    pub email_enabled: bool,
+    pub push_enabled: bool,
+    pub in_app_enabled: bool,
+    pub min_priority: Option<String>,
+    pub digest: bool,
+}
+
+impl NotificationPreference {
+    pub fn default_for(user_id: Uuid, event: NotificationEvent) -> Self {
+        Self {
+            user_id,
+            event_type: event.as_str().to_string(),
+            email_enabled: true,
+            push_enabled: true,
+            in_app_enabled: true,
+            min_priority: None,
+            digest: false,
+        }
+    }
+
+    /// Whether a task of `priority` is important enough to notify about.
+    pub fn allows_priority(&self, priority: &str) -> bool {
+        self.min_priority
+            .as_deref()
+            .map_or(true, |min| priority_rank(priority) >= priority_rank(min))
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct NotificationPreferencesResponse {
+    pub settings: NotificationSettings,
+    /// One entry per event type, with defaults for those never customised
+    pub preferences: Vec<NotificationPreference>,
+}
+
+/// Replaces the user's delivery settings; omitted values fall back to the
+/// defaults. Event types not listed in `preferences` keep their current setup.
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateNotificationPreferencesRequest {
+    /// IANA time zone name, e.g. `Asia/Jerusalem`
+    pub timezone: Option<String>,
+
+    /// Quiet hours are set as a pair, or left out to disable them
+    pub quiet_hours_start: Option<NaiveTime>,
+    pub quiet_hours_end: Option<NaiveTime>,
+
+    pub digest_time: Option<NaiveTime>,
+
+    /// `off` (default), `daily` or `weekly`
+    #[validate(custom = "validate_task_digest")]
+    pub task_digest: Option<String>,
+
+    #[validate(range(min = 0, max = 6))]
+    pub digest_weekday: Option<i16>,
+
+    #[serde(default)]
+    #[validate(nested)]
+    pub preferences: Vec<EventPreferenceRequest>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct EventPreferenceRequest {
+    pub event_type: NotificationEvent,
+    pub email_enabled: bool,
+    pub push_enabled: bool,
+    pub in_app_enabled: bool,
+
+    #[validate(custom = "validate_priority")]
+    pub min_priority: Option<String>,
+
+    #[serde(default)]
+    pub digest: bool,
+}
+
+/// An entry in a user's in-app notification inbox.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct InAppNotification {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub event_type: String,
+    pub title: String,
+    pub task_id: Option<Uuid>,
+    pub read_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NotificationsQuery {
+    #[serde(default)]
+    pub unread: bool,
+    pub limit: Option<i64>,
+}
+
+/// A notification held back for the user's digest.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct DigestItem {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub event_type: String,
+    pub summary: String,
+    pub task_id: Option<Uuid>,
+    pub sent_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+fn validate_task_digest(frequency: &str) -> Result<(), validator::ValidationError> {
+    let valid_frequencies = ["off", "daily", "weekly"];
+    if valid_frequencies.contains(&frequency) {
+        Ok(())
+    } else {
+        Err(validator::ValidationError::new("invalid_task_digest"))
+    }
+}
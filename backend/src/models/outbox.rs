@@ -0,0 +1,84 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// A rendered email waiting in, or delivered from, the outbox.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct OutboxEmail {
+    pub id: Uuid,
+    pub recipient: String,
+    pub subject: String,
+    pub html_body: String,
+    pub text_body: Option<String>,
+    pub kind: String,
+    pub task_id: Option<Uuid>,
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub locked_until: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub smtp_response: Option<String>,
+    pub sent_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub calendar_invite: Option<String>,
+}
+
+/// An email to be queued in the outbox.
+#[derive(Debug, Clone)]
+pub struct NewEmail {
+    pub recipient: String,
+    pub subject: String,
+    pub html_body: String,
+    pub text_body: Option<String>,
+    pub kind: String,
+    pub task_id: Option<Uuid>,
+    /// Hold the message until this time, e.g. the end of the recipient's quiet hours
+    pub send_after: Option<DateTime<Utc>>,
+    /// iCalendar object attached as `invite.ics`
+    pub calendar_invite: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OutboxQuery {
+    /// Defaults to `dead`, i.e. messages that gave up retrying
+    pub status: Option<String>,
+    pub limit: Option<i64>,
+}
+
+/// Outbox entry without the message bodies, for admin listings.
+#[derive(Debug, Serialize)]
+pub struct OutboxEmailSummary {
+    pub id: Uuid,
+    pub recipient: String,
+    pub subject: String,
+    pub kind: String,
+    pub task_id: Option<Uuid>,
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+    pub smtp_response: Option<String>,
+    pub sent_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<OutboxEmail> for OutboxEmailSummary {
+    fn from(email: OutboxEmail) -> Self {
+        Self {
+            id: email.id,
+            recipient: email.recipient,
+            subject: email.subject,
+            kind: email.kind,
+            task_id: email.task_id,
+            status: email.status,
+            attempts: email.attempts,
+            next_attempt_at: email.next_attempt_at,
+            last_error: email.last_error,
+            smtp_response: email.smtp_response,
+            sent_at: email.sent_at,
+            created_at: email.created_at,
+        }
+    }
+}
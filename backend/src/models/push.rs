@@ -0,0 +1,76 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct PushSubscription {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub endpoint: String,
+    #[serde(skip_serializing)]
+    pub p256dh: String,
+    #[serde(skip_serializing)]
+    pub auth: String,
+    pub user_agent: Option<String>,
+    pub last_success_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// The browser's `PushSubscription.toJSON()`.
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreatePushSubscriptionRequest {
+    #[validate(url)]
+    pub endpoint: String,
+
+    pub keys: PushSubscriptionKeys,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PushSubscriptionKeys {
+    pub p256dh: String,
+    pub auth: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DeletePushSubscriptionRequest {
+    pub endpoint: String,
+}
+
+/// A push message for one subscription, waiting in or delivered from the queue.
+#[derive(Debug, Clone, FromRow)]
+pub struct PushDelivery {
+    pub id: Uuid,
+    pub subscription_id: Uuid,
+    pub title: String,
+    pub body: String,
+    pub url: Option<String>,
+    pub task_id: Option<Uuid>,
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+}
+
+/// A push message to be queued for all of a user's devices.
+#[derive(Debug, Clone)]
+pub struct NewPush {
+    pub title: String,
+    pub body: String,
+    pub url: Option<String>,
+    pub task_id: Option<Uuid>,
+    pub send_after: Option<DateTime<Utc>>,
+}
+
+/// JSON payload understood by the service worker's `push` handler.
+#[derive(Debug, Serialize)]
+pub struct PushPayload<'a> {
+    pub title: &'a str,
+    pub body: &'a str,
+    pub icon: &'a str,
+    pub badge: &'a str,
+    pub url: &'a str,
+    pub dir: &'a str,
+    pub lang: &'a str,
+}
@@ -0,0 +1,183 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::models::{validate_priority, CreateTaskRequest, TaskResponse, UpdateTaskRequest};
+use crate::services::RecurrenceRule;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RecurringTaskSeries {
+    pub id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    pub category: String,
+    pub assigned_to: String,
+    pub assigned_to_email: String,
+    pub created_by: String,
+    pub created_by_email: String,
+    pub priority: String,
+    pub notes: Option<String>,
+    pub rrule: String,
+    pub start_date: NaiveDate,
+    pub lead_days: i32,
+    pub next_occurrence: Option<NaiveDate>,
+    pub occurrences_created: i32,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl RecurringTaskSeries {
+    pub fn rule(&self) -> anyhow::Result<RecurrenceRule> {
+        RecurrenceRule::parse(&self.rrule)
+    }
+
+    /// The task to create for the occurrence falling on `date`.
+    pub fn task_request(&self, date: NaiveDate) -> CreateTaskRequest {
+        CreateTaskRequest {
+            title: self.title.clone(),
+            description: self.description.clone(),
+            category: self.category.clone(),
+            assigned_to: self.assigned_to.clone(),
+            assigned_to_email: self.assigned_to_email.clone(),
+            created_by: self.created_by.clone(),
+            created_by_email: self.created_by_email.clone(),
+            due_date: Some(date),
+            priority: Some(self.priority.clone()),
+            attachments_folder_url: None,
+            notes: self.notes.clone(),
+            parent_id: None,
+            matter_id: None,
+            series_id: Some(self.id),
+            occurrence_date: Some(date),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateRecurringTaskRequest {
+    #[validate(length(min = 1, max = 500))]
+    pub title: String,
+
+    pub description: Option<String>,
+
+    #[validate(length(min = 1, max = 100))]
+    pub category: String,
+
+    #[validate(length(min = 1, max = 100))]
+    pub assigned_to: String,
+
+    #[validate(email)]
+    pub assigned_to_email: String,
+
+    #[validate(length(min = 1, max = 100))]
+    pub created_by: String,
+
+    #[validate(email)]
+    pub created_by_email: String,
+
+    #[validate(custom = "validate_priority")]
+    pub priority: Option<String>,
+
+    pub notes: Option<String>,
+
+    #[validate(custom = "validate_rrule")]
+    pub rrule: String,
+
+    pub start_date: NaiveDate,
+
+    /// How many days before each occurrence its task is created
+    #[validate(range(min = 0, max = 365))]
+    pub lead_days: Option<i32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SeriesEditScope {
+    ThisOccurrence,
+    AllFuture,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateRecurringTaskRequest {
+    pub scope: SeriesEditScope,
+
+    /// The occurrence being edited; required for `this_occurrence`, and for
+    /// `all_future` the first occurrence the change applies to (defaults to today)
+    pub occurrence_date: Option<NaiveDate>,
+
+    #[validate(length(min = 1, max = 500))]
+    pub title: Option<String>,
+
+    pub description: Option<String>,
+
+    #[validate(length(min = 1, max = 100))]
+    pub category: Option<String>,
+
+    #[validate(length(min = 1, max = 100))]
+    pub assigned_to: Option<String>,
+
+    #[validate(email)]
+    pub assigned_to_email: Option<String>,
+
+    #[validate(custom = "validate_priority")]
+    pub priority: Option<String>,
+
+    pub notes: Option<String>,
+
+    /// Only allowed with `all_future`
+    #[validate(custom = "validate_rrule")]
+    pub rrule: Option<String>,
+
+    #[validate(range(min = 0, max = 365))]
+    pub lead_days: Option<i32>,
+}
+
+impl UpdateRecurringTaskRequest {
+    pub fn has_task_changes(&self) -> bool {
+        self.title.is_some()
+            || self.description.is_some()
+            || self.category.is_some()
+            || self.assigned_to.is_some()
+            || self.assigned_to_email.is_some()
+            || self.priority.is_some()
+            || self.notes.is_some()
+    }
+
+    /// The same field changes, expressed as an update to one materialized task.
+    pub fn task_update(&self) -> UpdateTaskRequest {
+        UpdateTaskRequest {
+            title: self.title.clone(),
+            description: self.description.clone(),
+            category: self.category.clone(),
+            assigned_to: self.assigned_to.clone(),
+            assigned_to_email: self.assigned_to_email.clone(),
+            due_date: None,
+            priority: self.priority.clone(),
+            status: None,
+            attachments_folder_url: None,
+            attachments_count: None,
+            notes: self.notes.clone(),
+            matter_id: None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OccurrencesQuery {
+    pub limit: Option<usize>,
+}
+
+fn validate_rrule(rrule: &str) -> Result<(), validator::ValidationError> {
+    RecurrenceRule::parse(rrule)
+        .map(|_| ())
+        .map_err(|_| validator::ValidationError::new("invalid_rrule"))
+}
+
+#[derive(Debug, Serialize)]
+pub struct RecurringTaskUpdateResponse {
+    pub series: RecurringTaskSeries,
+    pub updated_tasks: Vec<TaskResponse>,
+}
@@ -23,6 +23,10 @@ pub struct Task {
     pub attachments_folder_url: Option<String>,
     pub attachments_count: i32,
     pub notes: Option<String>,
+    pub parent_id: Option<Uuid>,
+    pub series_id: Option<Uuid>,
+    pub occurrence_date: Option<NaiveDate>,
+    pub matter_id: Option<Uuid>,
 }
 
 #[derive(Debug, Deserialize, Validate)]
@@ -54,9 +58,17 @@ pub struct CreateTaskRequest {
 
     pub attachments_folder_url: Option<String>,
     pub notes: Option<String>,
+    pub parent_id: Option<Uuid>,
+    pub matter_id: Option<Uuid>,
+
+    /// Set only when the recurrence scheduler materializes an occurrence
+    #[serde(skip_deserializing)]
+    pub series_id: Option<Uuid>,
+    #[serde(skip_deserializing)]
+    pub occurrence_date: Option<NaiveDate>,
 }
 
-#[derive(Debug, Deserialize, Validate)]
+#[derive(Debug, Default, Deserialize, Validate)]
 pub struct UpdateTaskRequest {
     pub title: Option<String>,
     pub description: Option<String>,
@@ -74,6 +86,94 @@ pub struct UpdateTaskRequest {
     pub attachments_folder_url: Option<String>,
     pub attachments_count: Option<i32>,
     pub notes: Option<String>,
+    pub matter_id: Option<Uuid>,
+}
+
+/// One field that differs between two versions of a task, formatted for display.
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskFieldChange {
+    /// Field name as used in the API, e.g. `due_date`
+    pub field: &'static str,
+    /// Hebrew label shown in notifications
+    pub label: &'static str,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+}
+
+impl Task {
+    /// Field-level diff from `old` to `self`. Bookkeeping columns such as
+    /// `updated_at` and `attachments_count` are not reported.
+    pub fn changes_from(&self, old: &Task) -> Vec<TaskFieldChange> {
+        let fields: [(&'static str, &'static str, Option<String>, Option<String>); 9] = [
+            ("title", "כותרת", Some(old.title.clone()), Some(self.title.clone())),
+            ("description", "תיאור", old.description.clone(), self.description.clone()),
+            ("category", "קטגוריה", Some(old.category.clone()), Some(self.category.clone())),
+            (
+                "assigned_to",
+                "אחראי",
+                Some(format!("{} ({})", old.assigned_to, old.assigned_to_email)),
+                Some(format!("{} ({})", self.assigned_to, self.assigned_to_email)),
+            ),
+            (
+                "due_date",
+                "תאריך יעד",
+                old.due_date.map(|d| d.to_string()),
+                self.due_date.map(|d| d.to_string()),
+            ),
+            ("priority", "עדיפות", Some(old.priority.clone()), Some(self.priority.clone())),
+            ("status", "סטטוס", Some(old.status.clone()), Some(self.status.clone())),
+            (
+                "attachments_folder_url",
+                "תיקיית קבצים",
+                old.attachments_folder_url.clone(),
+                self.attachments_folder_url.clone(),
+            ),
+            ("notes", "הערות", old.notes.clone(), self.notes.clone()),
+        ];
+
+        fields
+            .into_iter()
+            .filter(|(_, _, old_value, new_value)| old_value != new_value)
+            .map(|(field, label, old_value, new_value)| TaskFieldChange {
+                field,
+                label,
+                old_value,
+                new_value,
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct UpdateTaskParams {
+    /// Lets an admin complete a parent task while subtasks are still open
+    #[serde(default)]
+    pub force: bool,
+}
+
+/// Rolled-up completion of a task's subtasks and checklist items.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct TaskProgress {
+    pub task_id: Uuid,
+    pub subtasks_total: i64,
+    pub subtasks_completed: i64,
+    pub subtasks_open: i64,
+    pub checklist_total: i64,
+    pub checklist_completed: i64,
+}
+
+impl TaskProgress {
+    /// Percentage of finished subtasks and checklist items, or `None` when
+    /// the task has neither.
+    pub fn completion_percentage(&self) -> Option<f64> {
+        let total = self.subtasks_total + self.checklist_total;
+        if total == 0 {
+            return None;
+        }
+
+        let completed = self.subtasks_completed + self.checklist_completed;
+        Some((completed as f64 * 100.0 / total as f64).round())
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -95,6 +195,64 @@ pub struct TaskResponse {
     pub attachments_folder_url: Option<String>,
     pub attachments_count: i32,
     pub notes: Option<String>,
+    pub parent_id: Option<Uuid>,
+    pub series_id: Option<Uuid>,
+    pub occurrence_date: Option<NaiveDate>,
+    pub matter_id: Option<Uuid>,
+    pub progress: Option<TaskProgressResponse>,
+    pub is_blocked: bool,
+    pub blocked_by: Vec<Uuid>,
+    /// Minutes logged in finished time entries
+    pub time_spent_minutes: i64,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TaskProgressResponse {
+    pub subtasks_total: i64,
+    pub subtasks_completed: i64,
+    pub checklist_total: i64,
+    pub checklist_completed: i64,
+    pub completion_percentage: Option<f64>,
+}
+
+impl From<TaskProgress> for TaskProgressResponse {
+    fn from(progress: TaskProgress) -> Self {
+        Self {
+            completion_percentage: progress.completion_percentage(),
+            subtasks_total: progress.subtasks_total,
+            subtasks_completed: progress.subtasks_completed,
+            checklist_total: progress.checklist_total,
+            checklist_completed: progress.checklist_completed,
+        }
+    }
+}
+
+impl TaskResponse {
+    pub fn with_progress(mut self, progress: Option<TaskProgress>) -> Self {
+        self.progress = progress
+            .filter(|p| p.subtasks_total + p.checklist_total > 0)
+            .map(|p| p.into());
+        self
+    }
+
+    /// Marks the task as blocked by the given unfinished tasks.
+    pub fn with_open_blockers(mut self, blockers: Vec<Uuid>) -> Self {
+        self.is_blocked = !blockers.is_empty();
+        self.blocked_by = blockers;
+        self
+    }
+
+    pub fn with_time_spent(mut self, minutes: i64) -> Self {
+        self.time_spent_minutes = minutes;
+        self
+    }
+
+    pub fn with_warning(mut self, warning: String) -> Self {
+        self.warnings.push(warning);
+        self
+    }
 }
 
 impl From<Task> for TaskResponse {
@@ -117,11 +275,20 @@ impl From<Task> for TaskResponse {
             attachments_folder_url: task.attachments_folder_url,
             attachments_count: task.attachments_count,
             notes: task.notes,
+            parent_id: task.parent_id,
+            series_id: task.series_id,
+            occurrence_date: task.occurrence_date,
+            matter_id: task.matter_id,
+            progress: None,
+            is_blocked: false,
+            blocked_by: Vec::new(),
+            time_spent_minutes: 0,
+            warnings: Vec::new(),
         }
     }
 }
 
-fn validate_priority(priority: &str) -> Result<(), validator::ValidationError> {
+pub(crate) fn validate_priority(priority: &str) -> Result<(), validator::ValidationError> {
     let valid_priorities = ["נמוכה", "רגילה", "גבוהה", "דחופה"];
     if valid_priorities.contains(&priority) {
         Ok(())
@@ -130,6 +297,16 @@ fn validate_priority(priority: &str) -> Result<(), validator::ValidationError> {
     }
 }
 
+/// Orders priorities from `נמוכה` (0) to `דחופה` (3); unknown values rank as `רגילה`.
+pub(crate) fn priority_rank(priority: &str) -> u8 {
+    match priority {
+        "נמוכה" => 0,
+        "גבוהה" => 2,
+        "דחופה" => 3,
+        _ => 1,
+    }
+}
+
 fn validate_status(status: &str) -> Result<(), validator::ValidationError> {
     let valid_statuses = ["חדשה", "בטיפול", "הושלמה", "בוטלה"];
     if valid_statuses.contains(&status) {
@@ -0,0 +1,142 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{types::Json, FromRow};
+use std::collections::HashMap;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::models::{validate_priority, TaskResponse};
+
+/// Largest due-date offset a template or its subtasks may use, either way.
+pub const MAX_DUE_OFFSET_DAYS: i32 = 3650;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TaskTemplate {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub title: String,
+    pub category: String,
+    pub priority: String,
+    pub task_description: Option<String>,
+    pub notes: Option<String>,
+    pub due_offset_days: Option<i32>,
+    pub checklist: Json<Vec<String>>,
+    pub subtasks: Json<Vec<TemplateSubtask>>,
+    pub sequential_subtasks: bool,
+    pub is_active: bool,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A subtask created together with the template's main task. Fields left
+/// empty are inherited from the main task.
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct TemplateSubtask {
+    pub title: String,
+    pub description: Option<String>,
+    #[validate(length(min = 1, max = 100))]
+    pub category: Option<String>,
+    pub priority: Option<String>,
+    pub assigned_to: Option<String>,
+    pub assigned_to_email: Option<String>,
+    pub due_offset_days: Option<i32>,
+    #[serde(default)]
+    pub checklist: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateTaskTemplateRequest {
+    #[validate(length(min = 1, max = 200))]
+    pub name: String,
+
+    pub description: Option<String>,
+
+    #[validate(length(min = 1, max = 500))]
+    pub title: String,
+
+    #[validate(length(min = 1, max = 100))]
+    pub category: String,
+
+    #[validate(custom = "validate_priority")]
+    pub priority: Option<String>,
+
+    pub task_description: Option<String>,
+    pub notes: Option<String>,
+
+    #[validate(range(min = -MAX_DUE_OFFSET_DAYS, max = MAX_DUE_OFFSET_DAYS))]
+    pub due_offset_days: Option<i32>,
+
+    #[serde(default)]
+    pub checklist: Vec<String>,
+
+    #[serde(default)]
+    pub subtasks: Vec<TemplateSubtask>,
+
+    #[serde(default)]
+    pub sequential_subtasks: bool,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateTaskTemplateRequest {
+    #[validate(length(min = 1, max = 200))]
+    pub name: Option<String>,
+
+    pub description: Option<String>,
+
+    #[validate(length(min = 1, max = 500))]
+    pub title: Option<String>,
+
+    #[validate(length(min = 1, max = 100))]
+    pub category: Option<String>,
+
+    #[validate(custom = "validate_priority")]
+    pub priority: Option<String>,
+
+    pub task_description: Option<String>,
+    pub notes: Option<String>,
+
+    #[validate(range(min = -MAX_DUE_OFFSET_DAYS, max = MAX_DUE_OFFSET_DAYS))]
+    pub due_offset_days: Option<i32>,
+
+    pub checklist: Option<Vec<String>>,
+    pub subtasks: Option<Vec<TemplateSubtask>>,
+    pub sequential_subtasks: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct InstantiateTemplateRequest {
+    #[validate(length(min = 1, max = 100))]
+    pub assigned_to: String,
+
+    #[validate(email)]
+    pub assigned_to_email: String,
+
+    #[validate(length(min = 1, max = 100))]
+    pub created_by: String,
+
+    #[validate(email)]
+    pub created_by_email: String,
+
+    /// Date the due-date offsets count from; defaults to today
+    pub start_date: Option<NaiveDate>,
+
+    /// Values for the template's `{placeholders}`
+    #[serde(default)]
+    pub variables: HashMap<String, String>,
+
+    /// Whether to create the template's subtasks as well (default: yes)
+    pub include_subtasks: Option<bool>,
+
+    pub attachments_folder_url: Option<String>,
+
+    /// Files the task and its subtasks under this matter
+    pub matter_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InstantiateTemplateResponse {
+    pub task: TaskResponse,
+    pub subtasks: Vec<TaskResponse>,
+}
@@ -0,0 +1,160 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::Validate;
+
+/// Time spent on a task or matter. Money amounts are in agorot.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TimeEntry {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub task_id: Option<Uuid>,
+    pub matter_id: Option<Uuid>,
+    pub entry_date: NaiveDate,
+    pub started_at: Option<DateTime<Utc>>,
+    pub ended_at: Option<DateTime<Utc>>,
+    /// `None` while the timer is running
+    pub duration_minutes: Option<i32>,
+    pub billable: bool,
+    pub hourly_rate_agorot: Option<i64>,
+    pub amount_agorot: i64,
+    pub narrative: Option<String>,
+    pub invoice_id: Option<Uuid>,
+    pub invoiced_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TimeEntry {
+    pub fn is_running(&self) -> bool {
+        self.duration_minutes.is_none()
+    }
+
+    /// Invoiced entries are locked.
+    pub fn is_locked(&self) -> bool {
+        self.invoice_id.is_some()
+    }
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct StartTimerRequest {
+    pub task_id: Option<Uuid>,
+    /// Defaults to the task's matter
+    pub matter_id: Option<Uuid>,
+    pub billable: Option<bool>,
+
+    #[validate(length(max = 5000))]
+    pub narrative: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct StopTimerRequest {
+    #[validate(length(max = 5000))]
+    pub narrative: Option<String>,
+}
+
+/// Time entered after the fact: either a duration, or a start and end.
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateTimeEntryRequest {
+    pub task_id: Option<Uuid>,
+    /// Defaults to the task's matter
+    pub matter_id: Option<Uuid>,
+
+    /// Defaults to the start date, or today
+    pub entry_date: Option<NaiveDate>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub ended_at: Option<DateTime<Utc>>,
+
+    #[validate(range(min = 1, max = 1440))]
+    pub duration_minutes: Option<i32>,
+
+    pub billable: Option<bool>,
+
+    /// Defaults to the user's rate
+    #[validate(range(min = 0))]
+    pub hourly_rate_agorot: Option<i64>,
+
+    #[validate(length(max = 5000))]
+    pub narrative: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateTimeEntryRequest {
+    pub task_id: Option<Uuid>,
+    pub matter_id: Option<Uuid>,
+    pub entry_date: Option<NaiveDate>,
+
+    #[validate(range(min = 1, max = 1440))]
+    pub duration_minutes: Option<i32>,
+
+    pub billable: Option<bool>,
+
+    #[validate(range(min = 0))]
+    pub hourly_rate_agorot: Option<i64>,
+
+    #[validate(length(max = 5000))]
+    pub narrative: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct TimeEntriesQuery {
+    /// Only admins may look at other users' time
+    pub user_id: Option<Uuid>,
+    pub task_id: Option<Uuid>,
+    pub matter_id: Option<Uuid>,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    pub billable: Option<bool>,
+    /// Only entries not yet invoiced
+    #[serde(default)]
+    pub uninvoiced: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TimeReportQuery {
+    /// `user`, `matter` or `period`
+    pub group_by: Option<String>,
+    /// `day`, `week` or `month`, when grouping by period
+    pub period: Option<String>,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    pub user_id: Option<Uuid>,
+    pub matter_id: Option<Uuid>,
+}
+
+/// Time totals for one user, matter or period.
+#[derive(Debug, Serialize, FromRow)]
+pub struct TimeReportRow {
+    pub key: Option<String>,
+    pub label: Option<String>,
+    pub entries: i64,
+    pub total_minutes: i64,
+    pub billable_minutes: i64,
+    pub billable_amount_agorot: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TimeReport {
+    pub group_by: String,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    pub rows: Vec<TimeReportRow>,
+    pub total_minutes: i64,
+    pub billable_minutes: i64,
+    pub billable_amount_agorot: i64,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct SetHourlyRateRequest {
+    /// `None` clears the rate
+    #[validate(range(min = 0))]
+    pub hourly_rate_agorot: Option<i64>,
+}
+
+/// Minutes logged against each task, for task responses.
+#[derive(Debug, Clone, FromRow)]
+pub struct TaskTimeTotal {
+    pub task_id: Uuid,
+    pub total_minutes: i64,
+}
@@ -16,6 +16,8 @@ pub struct User {
     pub updated_at: DateTime<Utc>,
     pub last_login: Option<DateTime<Utc>>,
     pub is_active: bool,
+    /// Default rate for the user's time entries, in agorot
+    pub hourly_rate_agorot: Option<i64>,
 }
 
 #[derive(Debug, Deserialize, Validate)]
@@ -0,0 +1,87 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::Validate;
+
+/// Event type of the deliveries created by "send test event".
+pub const WEBHOOK_TEST_EVENT: &str = "ping";
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct WebhookSubscription {
+    pub id: Uuid,
+    pub name: String,
+    pub url: String,
+    #[serde(skip_serializing)]
+    pub secret: String,
+    pub event_types: Vec<String>,
+    pub is_active: bool,
+    pub consecutive_failures: i32,
+    pub last_success_at: Option<DateTime<Utc>>,
+    pub disabled_at: Option<DateTime<Utc>>,
+    pub disabled_reason: Option<String>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Returned once, on creation: the only time the signing secret is shown.
+#[derive(Debug, Serialize)]
+pub struct WebhookSubscriptionCreated {
+    #[serde(flatten)]
+    pub subscription: WebhookSubscription,
+    pub secret: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateWebhookRequest {
+    #[validate(length(min = 1, max = 200))]
+    pub name: String,
+
+    #[validate(url)]
+    pub url: String,
+
+    /// Task event types to deliver; empty or omitted means all of them
+    #[serde(default)]
+    pub event_types: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateWebhookRequest {
+    #[validate(length(min = 1, max = 200))]
+    pub name: Option<String>,
+
+    #[validate(url)]
+    pub url: Option<String>,
+
+    pub event_types: Option<Vec<String>>,
+
+    /// Re-enabling a subscription resets its failure count
+    pub is_active: Option<bool>,
+}
+
+/// One event sent, or to be sent, to one subscription.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct WebhookDelivery {
+    pub id: Uuid,
+    pub subscription_id: Uuid,
+    pub event_id: Option<i64>,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub locked_until: Option<DateTime<Utc>>,
+    pub response_status: Option<i32>,
+    pub response_body: Option<String>,
+    pub last_error: Option<String>,
+    pub delivered_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WebhookDeliveriesQuery {
+    pub status: Option<String>,
+    pub limit: Option<i64>,
+}
@@ -1,4 +1,5 @@
 use axum::{
+    extract::DefaultBodyLimit,
     routing::{delete, get, post, put},
     Router,
 };
@@ -19,6 +20,199 @@ pub fn create_router(state: AppState) -> Router {
         .route("/api/tasks/:id", delete(handlers::delete_task))
         .route("/api/tasks/assignee/:assignee", get(handlers::get_tasks_by_assignee))
         .route("/api/tasks/status/:status", get(handlers::get_tasks_by_status))
+        .route("/api/tasks/:id/subtasks", get(handlers::get_subtasks))
+        .route("/api/tasks/from-template/:id", post(handlers::create_task_from_template))
+
+        // Checklist endpoints
+        .route("/api/tasks/:id/checklist", get(handlers::get_checklist))
+        .route("/api/tasks/:id/checklist", post(handlers::create_checklist_item))
+        .route("/api/tasks/:id/checklist/:item_id", put(handlers::update_checklist_item))
+        .route("/api/tasks/:id/checklist/:item_id", delete(handlers::delete_checklist_item))
+
+        // Dependency endpoints
+        .route("/api/tasks/:id/dependencies", get(handlers::get_task_dependencies))
+        .route("/api/tasks/:id/dependencies", post(handlers::add_task_dependency))
+        .route("/api/tasks/:id/dependencies/:blocked_by_id", delete(handlers::remove_task_dependency))
+
+        // Comment endpoints
+        .route("/api/tasks/:id/comments", get(handlers::get_task_comments))
+        .route("/api/tasks/:id/comments", post(handlers::create_task_comment))
+        .route("/api/tasks/:id/comments/:comment_id", put(handlers::update_task_comment))
+        .route("/api/tasks/:id/comments/:comment_id", delete(handlers::delete_task_comment))
+        .route("/api/tasks/:id/comments/:comment_id/history", get(handlers::get_task_comment_history))
+        .route("/api/tasks/:id/activity", get(handlers::get_task_activity))
+
+        // Court deadline endpoints
+        .route("/api/deadlines/calculate", post(handlers::calculate_deadline))
+        .route("/api/deadlines/calendar/:year", get(handlers::get_court_calendar))
+        .route("/api/tasks/:id/deadline", post(handlers::set_task_deadline))
+        .route("/api/tasks/:id/deadlines", get(handlers::get_task_deadlines))
+
+        // Task template endpoints
+        .route("/api/task-templates", post(handlers::create_task_template))
+        .route("/api/task-templates", get(handlers::get_all_task_templates))
+        .route("/api/task-templates/:id", get(handlers::get_task_template))
+        .route("/api/task-templates/:id", put(handlers::update_task_template))
+        .route("/api/task-templates/:id", delete(handlers::delete_task_template))
+
+        // Client endpoints
+        .route("/api/clients", get(handlers::get_clients))
+        .route("/api/clients", post(handlers::create_client))
+        .route("/api/clients/:id", get(handlers::get_client))
+        .route("/api/clients/:id", put(handlers::update_client))
+        .route("/api/clients/:id", delete(handlers::delete_client))
+        .route("/api/clients/:id/matters", get(handlers::get_client_matters))
+
+        // Matter endpoints
+        .route("/api/matters", get(handlers::get_matters))
+        .route("/api/matters", post(handlers::create_matter))
+        .route("/api/matters/:id", get(handlers::get_matter))
+        .route("/api/matters/:id", put(handlers::update_matter))
+        .route("/api/matters/:id", delete(handlers::delete_matter))
+        .route("/api/matters/:id/tasks", get(handlers::get_matter_tasks))
+        .route("/api/matters/:id/stats", get(handlers::get_matter_stats))
+        .route("/api/matters/:id/hearings", get(handlers::get_matter_hearings))
+        .route("/api/matters/:id/parties", get(handlers::get_matter_parties))
+        .route("/api/matters/:id/parties", post(handlers::create_matter_party))
+        .route("/api/matters/:id/parties/:party_id", delete(handlers::delete_matter_party))
+        .route("/api/matters/:id/expiries", get(handlers::get_matter_expiries))
+        .route("/api/matters/:id/expiries", post(handlers::create_matter_expiry))
+        .route("/api/matters/:id/expiries/:expiry_id", put(handlers::update_matter_expiry))
+        .route("/api/matters/:id/expiries/:expiry_id", delete(handlers::delete_matter_expiry))
+        .route("/api/matters/:id/expiries/:expiry_id/resolve", post(handlers::resolve_matter_expiry))
+        .route("/api/reports/expiries", get(handlers::get_expiry_report))
+
+        .route("/api/matters/:id/contacts", get(handlers::get_matter_contacts))
+        .route("/api/matters/:id/contacts", post(handlers::link_matter_contact))
+        .route("/api/matters/:id/contacts/:link_id", delete(handlers::unlink_matter_contact))
+
+        // Contact endpoints
+        .route("/api/contacts", get(handlers::get_contacts))
+        .route("/api/contacts", post(handlers::create_contact))
+        .route("/api/contacts/export", get(handlers::export_contacts))
+        .route(
+            "/api/contacts/import",
+            post(handlers::import_contacts)
+                .layer(DefaultBodyLimit::max(handlers::CONTACTS_IMPORT_MAX_BYTES)),
+        )
+        .route("/api/contacts/:id", get(handlers::get_contact))
+        .route("/api/contacts/:id", put(handlers::update_contact))
+        .route("/api/contacts/:id", delete(handlers::delete_contact))
+        .route("/api/contacts/:id/vcard", get(handlers::export_contact))
+        .route("/api/tasks/:id/contacts", get(handlers::get_task_contacts))
+        .route("/api/tasks/:id/contacts", post(handlers::link_task_contact))
+        .route("/api/tasks/:id/contacts/:link_id", delete(handlers::unlink_task_contact))
+
+        // Conflict-of-interest checks
+        .route("/api/conflicts/check", post(handlers::check_conflicts))
+        .route("/api/conflicts/checks/:id", get(handlers::get_conflict_check))
+
+        // Document generation endpoints
+        .route("/api/document-templates", get(handlers::get_document_templates))
+        .route(
+            "/api/document-templates",
+            post(handlers::create_document_template)
+                .layer(DefaultBodyLimit::max(handlers::DOCUMENT_TEMPLATE_MAX_BYTES)),
+        )
+        .route("/api/document-templates/:id", get(handlers::get_document_template))
+        .route(
+            "/api/document-templates/:id",
+            put(handlers::update_document_template)
+                .layer(DefaultBodyLimit::max(handlers::DOCUMENT_TEMPLATE_MAX_BYTES)),
+        )
+        .route("/api/document-templates/:id", delete(handlers::delete_document_template))
+        .route("/api/document-templates/:id/file", get(handlers::download_document_template))
+        .route("/api/tasks/:id/documents", post(handlers::generate_task_document))
+
+        // Hearing endpoints
+        .route("/api/hearings", get(handlers::get_hearings))
+        .route("/api/hearings", post(handlers::create_hearing))
+        .route("/api/hearings/:id", get(handlers::get_hearing))
+        .route("/api/hearings/:id", put(handlers::update_hearing))
+        .route("/api/hearings/:id", delete(handlers::delete_hearing))
+        .route("/api/hearings/:id/cancel", post(handlers::cancel_hearing))
+        .route("/api/hearing-preparation-steps", get(handlers::get_preparation_steps))
+        .route("/api/hearing-preparation-steps", post(handlers::create_preparation_step))
+        .route("/api/hearing-preparation-steps/:id", delete(handlers::delete_preparation_step))
+
+        // Time tracking endpoints
+        .route("/api/time-entries", get(handlers::get_time_entries))
+        .route("/api/time-entries", post(handlers::create_time_entry))
+        .route("/api/time-entries/timer", get(handlers::get_running_timer))
+        .route("/api/time-entries/timer/start", post(handlers::start_timer))
+        .route("/api/time-entries/timer/stop", post(handlers::stop_timer))
+        .route("/api/time-entries/:id", get(handlers::get_time_entry))
+        .route("/api/time-entries/:id", put(handlers::update_time_entry))
+        .route("/api/time-entries/:id", delete(handlers::delete_time_entry))
+        .route("/api/tasks/:id/time-entries", get(handlers::get_task_time_entries))
+        .route("/api/reports/time", get(handlers::get_time_report))
+        .route("/api/admin/users/:id/hourly-rate", put(handlers::set_user_hourly_rate))
+
+        // Invoice endpoints
+        .route("/api/invoices", get(handlers::get_invoices))
+        .route("/api/invoices", post(handlers::create_invoice))
+        .route("/api/invoices/:id", get(handlers::get_invoice))
+        .route("/api/invoices/:id", delete(handlers::delete_invoice))
+        .route("/api/invoices/:id/issue", post(handlers::issue_invoice))
+        .route("/api/invoices/:id/document", get(handlers::get_invoice_document))
+
+        // Recurring task endpoints
+        .route("/api/recurring-tasks", post(handlers::create_recurring_task))
+        .route("/api/recurring-tasks", get(handlers::get_all_recurring_tasks))
+        .route("/api/recurring-tasks/:id", get(handlers::get_recurring_task))
+        .route("/api/recurring-tasks/:id", put(handlers::update_recurring_task))
+        .route("/api/recurring-tasks/:id", delete(handlers::delete_recurring_task))
+        .route("/api/recurring-tasks/:id/occurrences", get(handlers::get_recurring_task_occurrences))
+        .route("/api/recurring-tasks/:id/tasks", get(handlers::get_recurring_task_tasks))
+
+        // Notification endpoints
+        .route("/api/notifications", get(handlers::get_notifications))
+        .route("/api/notifications/read-all", post(handlers::mark_all_notifications_read))
+        .route("/api/notifications/:id/read", post(handlers::mark_notification_read))
+        .route("/api/notification-preferences", get(handlers::get_notification_preferences))
+        .route("/api/notification-preferences", put(handlers::update_notification_preferences))
+
+        // Calendar endpoints
+        .route("/api/calendar/feed", get(handlers::get_calendar_feed))
+        .route("/api/calendar/feed", post(handlers::create_calendar_feed))
+        .route("/api/calendar/feed", delete(handlers::delete_calendar_feed))
+        // `:token.ics`; the router matches whole segments, so the handler strips the extension
+        .route("/api/calendar/:token", get(handlers::get_calendar_ics))
+
+        // Real-time event stream
+        .route("/api/events", get(handlers::get_task_events))
+
+        // Web Push endpoints
+        .route("/api/push/vapid-public-key", get(handlers::get_vapid_public_key))
+        .route("/api/push/subscriptions", get(handlers::get_push_subscriptions))
+        .route("/api/push/subscriptions", post(handlers::create_push_subscription))
+        .route("/api/push/subscriptions", delete(handlers::delete_push_subscription))
+
+        // Email outbox endpoints
+        .route("/api/admin/email-outbox", get(handlers::get_email_outbox))
+        .route("/api/admin/email-outbox/:id/resend", post(handlers::resend_outbox_email))
+
+        // Attachment endpoints
+        .route("/api/tasks/:id/attachments", get(handlers::get_task_attachments))
+        .route("/api/attachments/:id/content", get(handlers::download_attachment))
+
+        // Inbound email endpoints
+        .route(
+            "/api/inbound/email",
+            post(handlers::receive_inbound_email)
+                .layer(DefaultBodyLimit::max(handlers::INBOUND_EMAIL_MAX_BYTES)),
+        )
+        .route("/api/admin/inbound-emails", get(handlers::get_inbound_emails))
+
+        // Webhook endpoints
+        .route("/api/admin/webhooks", get(handlers::get_webhooks))
+        .route("/api/admin/webhooks", post(handlers::create_webhook))
+        .route("/api/admin/webhooks/:id", get(handlers::get_webhook))
+        .route("/api/admin/webhooks/:id", put(handlers::update_webhook))
+        .route("/api/admin/webhooks/:id", delete(handlers::delete_webhook))
+        .route("/api/admin/webhooks/:id/test", post(handlers::send_test_webhook))
+        .route("/api/admin/webhooks/:id/deliveries", get(handlers::get_webhook_deliveries))
+        .route("/api/admin/webhooks/:id/deliveries/:delivery_id/redeliver", post(handlers::redeliver_webhook))
 
         // Stats endpoints
         .route("/api/stats", get(handlers::get_stats))
@@ -0,0 +1,338 @@
+use crate::models::{ConflictCandidate, ConflictHit, ConflictMatter};
+
+/// Hits scoring below this are left out.
+pub const MIN_CONFLICT_SCORE: f64 = 0.75;
+
+/// Most hits returned by one check.
+pub const MAX_CONFLICT_HITS: usize = 50;
+
+/// A match found only through transliteration ranks just below the same
+/// match spelled alike.
+const TRANSLITERATION_WEIGHT: f64 = 0.95;
+
+/// Caps the spellings tried for one Hebrew word (each medial vav doubles them).
+const MAX_SKELETON_VARIANTS: usize = 8;
+
+/// Company suffixes and titles that say nothing about who someone is.
+const IGNORED_WORDS: &[&str] = &[
+    "בעמ", "עוד", "דר", "מר", "גב", "ltd", "inc", "llc", "corp", "co", "adv", "dr", "mr", "mrs", "ms",
+];
+
+/// Scores every candidate against the name and ID number checked, and
+/// merges the rows for the same person into one hit listing their matters,
+/// best first.
+pub fn rank_conflict_hits(
+    name: Option<&str>,
+    id_number: Option<&str>,
+    candidates: &[ConflictCandidate],
+) -> Vec<ConflictHit> {
+    let query_words = name.map(name_words).unwrap_or_default();
+    let query_id = id_number.map(normalize_id_number).filter(|id| !id.is_empty());
+
+    let mut keys: Vec<String> = Vec::new();
+    let mut hits: Vec<ConflictHit> = Vec::new();
+
+    for candidate in candidates {
+        let id_match = match (&query_id, &candidate.id_number) {
+            (Some(query_id), Some(id)) => *query_id == normalize_id_number(id),
+            _ => false,
+        };
+
+        let (score, match_type) = if id_match {
+            (1.0, "id_number")
+        } else {
+            match name_score(&query_words, &name_words(&candidate.name)) {
+                Some(scored) => scored,
+                None => continue,
+            }
+        };
+        if score < MIN_CONFLICT_SCORE {
+            continue;
+        }
+
+        let key = match candidate.client_id {
+            Some(client_id) => client_id.to_string(),
+            None => format!(
+                "{}:{}:{}",
+                candidate.source,
+                name_words(&candidate.name).join(" "),
+                candidate.id_number.as_deref().map(normalize_id_number).unwrap_or_default()
+            ),
+        };
+
+        let index = match keys.iter().position(|k| *k == key) {
+            Some(index) => index,
+            None => {
+                keys.push(key);
+                hits.push(ConflictHit {
+                    source: candidate.source.clone(),
+                    name: candidate.name.clone(),
+                    id_number: candidate.id_number.clone(),
+                    client_id: candidate.client_id,
+                    score: 0.0,
+                    match_type: match_type.to_string(),
+                    matters: Vec::new(),
+                });
+                hits.len() - 1
+            }
+        };
+
+        let hit = &mut hits[index];
+        if score > hit.score {
+            hit.score = score;
+            hit.match_type = match_type.to_string();
+        }
+        if let (Some(matter_id), Some(number), Some(title), Some(status)) = (
+            candidate.matter_id,
+            &candidate.matter_number,
+            &candidate.matter_title,
+            &candidate.matter_status,
+        ) {
+            if !hit.matters.iter().any(|m| m.matter_id == matter_id) {
+                hit.matters.push(ConflictMatter {
+                    matter_id,
+                    matter_number: number.clone(),
+                    title: title.clone(),
+                    status: status.clone(),
+                    role: candidate.role.clone(),
+                });
+            }
+        }
+    }
+
+    for hit in hits.iter_mut() {
+        hit.score = (hit.score * 1000.0).round() / 1000.0;
+    }
+    hits.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    hits.truncate(MAX_CONFLICT_HITS);
+    hits
+}
+
+/// How well the candidate's name matches the one checked, and how. Every
+/// word checked is paired with its closest word in the candidate's name;
+/// a candidate whose own words are all found in the query also counts, a
+/// little less.
+fn name_score(query: &[String], candidate: &[String]) -> Option<(f64, &'static str)> {
+    if query.is_empty() || candidate.is_empty() {
+        return None;
+    }
+    if query == candidate {
+        return Some((1.0, "exact"));
+    }
+
+    let (forward, forward_transliterated) = coverage(query, candidate);
+    let (backward, backward_transliterated) = coverage(candidate, query);
+
+    let (score, transliterated) = if forward >= backward * 0.9 {
+        (forward, forward_transliterated)
+    } else {
+        (backward * 0.9, backward_transliterated)
+    };
+
+    Some((score, if transliterated { "transliteration" } else { "fuzzy" }))
+}
+
+/// The mean, over `words`, of each word's best match in `other`, and
+/// whether transliteration decided any of them.
+fn coverage(words: &[String], other: &[String]) -> (f64, bool) {
+    let mut total = 0.0;
+    let mut transliterated = false;
+
+    for word in words {
+        let mut best = 0.0;
+        let mut best_transliterated = false;
+        for candidate in other {
+            let spelled = similarity(word, candidate);
+            let sounded = skeleton_similarity(word, candidate) * TRANSLITERATION_WEIGHT;
+            if spelled >= best && spelled >= sounded {
+                best = spelled;
+                best_transliterated = false;
+            } else if sounded > best {
+                best = sounded;
+                best_transliterated = true;
+            }
+        }
+        total += best;
+        transliterated |= best_transliterated;
+    }
+
+    (total / words.len() as f64, transliterated)
+}
+
+fn skeleton_similarity(a: &str, b: &str) -> f64 {
+    let mut best: f64 = 0.0;
+    for x in skeletons(a) {
+        for y in skeletons(b) {
+            if x.is_empty() || y.is_empty() {
+                continue;
+            }
+            best = best.max(similarity(&x, &y));
+        }
+    }
+    best
+}
+
+/// 1 minus the edit distance over the longer length.
+fn similarity(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 0.0;
+    }
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for i in 1..=a.len() {
+        current[0] = i;
+        for j in 1..=b.len() {
+            let substitution = previous[j - 1] + usize::from(a[i - 1] != b[j - 1]);
+            current[j] = substitution.min(previous[j] + 1).min(current[j - 1] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    1.0 - previous[b.len()] as f64 / longest as f64
+}
+
+/// Lower-case words without punctuation, niqqud, final letter forms or
+/// company suffixes, so `כ"ץ בע"מ` and `כץ` compare equal.
+pub fn name_words(name: &str) -> Vec<String> {
+    let mut cleaned = String::with_capacity(name.len());
+    for ch in name.chars().flat_map(char::to_lowercase) {
+        match ch {
+            '\u{0591}'..='\u{05C7}' | '"' | '\'' | '׳' | '״' | '`' | '’' => {}
+            'ך' => cleaned.push('כ'),
+            'ם' => cleaned.push('מ'),
+            'ן' => cleaned.push('נ'),
+            'ף' => cleaned.push('פ'),
+            'ץ' => cleaned.push('צ'),
+            ch if ch.is_alphanumeric() => cleaned.push(ch),
+            _ => cleaned.push(' '),
+        }
+    }
+
+    cleaned
+        .split_whitespace()
+        .filter(|word| !IGNORED_WORDS.contains(word))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Digits only, without leading zeros; Israeli IDs are often written
+/// without them.
+pub fn normalize_id_number(id_number: &str) -> String {
+    id_number
+        .chars()
+        .filter(char::is_ascii_digit)
+        .collect::<String>()
+        .trim_start_matches('0')
+        .to_string()
+}
+
+/// The consonants of a word as they sound, in a form shared by Hebrew and
+/// Latin spellings: `כהן` and `Cohen` both give `khn`. Hebrew vav may be a
+/// consonant or a vowel, so each medial vav gives two spellings.
+fn skeletons(word: &str) -> Vec<String> {
+    let chars: Vec<char> = word.chars().collect();
+    let is_hebrew = chars.iter().any(|c| ('\u{05D0}'..='\u{05EA}').contains(c));
+
+    let mut variants = vec![String::new()];
+    if is_hebrew {
+        for (i, ch) in chars.iter().enumerate() {
+            let last = i + 1 == chars.len();
+            let options: &[&str] = match ch {
+                'א' | 'ע' => &[""],
+                'ב' => &["b"],
+                'ג' => &["g"],
+                'ד' => &["d"],
+                'ה' if last => &[""],
+                'ה' | 'ח' => &["h"],
+                'ו' if i == 0 || chars.get(i + 1) == Some(&'ו') || (i > 0 && chars[i - 1] == 'ו') => &["b"],
+                'ו' => &["b", ""],
+                'ז' => &["z"],
+                'ט' | 'ת' => &["t"],
+                'י' if i == 0 => &["y"],
+                'י' => &[""],
+                'כ' | 'ק' => &["k"],
+                'ל' => &["l"],
+                'מ' => &["m"],
+                'נ' => &["n"],
+                'ס' | 'ש' => &["s"],
+                'פ' => &["p"],
+                'צ' => &["z"],
+                'ר' => &["r"],
+                _ => {
+                    for variant in variants.iter_mut() {
+                        variant.push(*ch);
+                    }
+                    continue;
+                }
+            };
+
+            if options.len() > 1 && variants.len() * options.len() <= MAX_SKELETON_VARIANTS {
+                variants = variants
+                    .iter()
+                    .flat_map(|v| options.iter().map(move |o| format!("{}{}", v, o)))
+                    .collect();
+            } else {
+                for variant in variants.iter_mut() {
+                    variant.push_str(options[0]);
+                }
+            }
+        }
+    } else {
+        let mut skeleton = String::new();
+        let mut i = 0;
+        while i < chars.len() {
+            let pair: String = chars[i..(i + 2).min(chars.len())].iter().collect();
+            let digraph = match pair.as_str() {
+                "ch" | "kh" => Some("h"),
+                "sh" | "zh" => Some("s"),
+                "tz" | "ts" => Some("z"),
+                "ph" => Some("p"),
+                "th" => Some("t"),
+                "ck" => Some("k"),
+                _ => None,
+            };
+            if let Some(sound) = digraph {
+                skeleton.push_str(sound);
+                i += 2;
+                continue;
+            }
+
+            let last = i + 1 == chars.len();
+            match chars[i] {
+                'a' | 'e' | 'i' | 'o' | 'u' => {}
+                'y' if i == 0 => skeleton.push('y'),
+                'y' => {}
+                'h' if last => {}
+                'b' | 'v' | 'w' => skeleton.push('b'),
+                'f' | 'p' => skeleton.push('p'),
+                'c' | 'k' | 'q' => skeleton.push('k'),
+                'j' => skeleton.push('g'),
+                'x' => skeleton.push_str("ks"),
+                ch => skeleton.push(ch),
+            }
+            i += 1;
+        }
+        variants = vec![skeleton];
+    }
+
+    variants.iter().map(|v| collapse_repeats(v)).collect()
+}
+
+fn collapse_repeats(value: &str) -> String {
+    let mut collapsed = String::with_capacity(value.len());
+    for ch in value.chars() {
+        if !collapsed.ends_with(ch) {
+            collapsed.push(ch);
+        }
+    }
+    collapsed
+}
@@ -0,0 +1,229 @@
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use std::collections::HashMap;
+
+use crate::models::{CourtCalendarYear, CourtHoliday, CourtRecess};
+
+/// `NaiveDate::num_days_from_ce` of the day before 1 Tishrei AM 1, so that
+/// adding the days elapsed since creation gives the date of Rosh Hashanah.
+const HEBREW_EPOCH_OFFSET: i64 = -1_373_428;
+
+/// Days from 15 Nisan to the following 1 Tishrei; the months in between
+/// have fixed lengths.
+const PESACH_TO_ROSH_HASHANAH: i64 = 163;
+
+/// Whether the Hebrew year has a second Adar.
+fn is_hebrew_leap_year(year: i64) -> bool {
+    (7 * year + 1) % 19 < 7
+}
+
+/// Days from creation to 1 Tishrei of the Hebrew `year`: the molad of
+/// Tishrei, postponed by the dehiyyot so that Rosh Hashanah never falls on
+/// Sunday, Wednesday or Friday.
+fn hebrew_elapsed_days(year: i64) -> i64 {
+    let cycle_year = (year - 1) % 19;
+    let months = 235 * ((year - 1) / 19) + 12 * cycle_year + (7 * cycle_year + 1) / 19;
+    let parts_elapsed = 204 + 793 * (months % 1080);
+    let hours_elapsed = 5 + 12 * months + 793 * (months / 1080) + parts_elapsed / 1080;
+    let parts = 1080 * (hours_elapsed % 24) + parts_elapsed % 1080;
+    let mut day = 1 + 29 * months + hours_elapsed / 24;
+
+    if parts >= 19440
+        || (day % 7 == 2 && parts >= 9924 && !is_hebrew_leap_year(year))
+        || (day % 7 == 1 && parts >= 16789 && is_hebrew_leap_year(year - 1))
+    {
+        day += 1;
+    }
+    if matches!(day % 7, 0 | 3 | 5) {
+        day += 1;
+    }
+
+    day
+}
+
+/// Rosh Hashanah falling in the given Gregorian year.
+pub fn rosh_hashanah(year: i32) -> NaiveDate {
+    let days = hebrew_elapsed_days(year as i64 + 3761) + HEBREW_EPOCH_OFFSET;
+    NaiveDate::from_num_days_from_ce_opt(days as i32).expect("date within chrono's range")
+}
+
+/// First day of Pesach (15 Nisan) falling in the given Gregorian year.
+pub fn pesach(year: i32) -> NaiveDate {
+    rosh_hashanah(year) - Duration::days(PESACH_TO_ROSH_HASHANAH)
+}
+
+/// Independence Day: 5 Iyar, brought forward to Thursday when it falls on
+/// Friday or Saturday and postponed to Tuesday when it falls on Monday.
+fn independence_day(year: i32) -> NaiveDate {
+    let date = pesach(year) + Duration::days(20);
+    match date.weekday() {
+        Weekday::Fri => date - Duration::days(1),
+        Weekday::Sat => date - Duration::days(2),
+        Weekday::Mon => date + Duration::days(1),
+        _ => date,
+    }
+}
+
+/// Holidays on which the courts do not sit, in date order.
+pub fn court_holidays(year: i32) -> Vec<CourtHoliday> {
+    let rosh_hashanah = rosh_hashanah(year);
+    let pesach = pesach(year);
+    let holiday = |date: NaiveDate, name: &str| CourtHoliday {
+        date,
+        name: name.to_string(),
+    };
+
+    vec![
+        holiday(pesach, "פסח"),
+        holiday(pesach + Duration::days(6), "שביעי של פסח"),
+        holiday(independence_day(year), "יום העצמאות"),
+        holiday(pesach + Duration::days(50), "שבועות"),
+        holiday(rosh_hashanah, "ראש השנה"),
+        holiday(rosh_hashanah + Duration::days(1), "ראש השנה"),
+        holiday(rosh_hashanah + Duration::days(9), "יום הכיפורים"),
+        holiday(rosh_hashanah + Duration::days(14), "סוכות"),
+        holiday(rosh_hashanah + Duration::days(21), "שמיני עצרת"),
+    ]
+}
+
+/// Court recess periods per the Courts (Recess) Regulations: Pesach and
+/// Sukkot from the eve of the festival to the day after it ends, and the
+/// summer recess from 16 July to 31 August.
+pub fn court_recess_periods(year: i32) -> Vec<CourtRecess> {
+    let rosh_hashanah = rosh_hashanah(year);
+    let pesach = pesach(year);
+    let date = |month: u32, day: u32| NaiveDate::from_ymd_opt(year, month, day).expect("valid date");
+
+    vec![
+        CourtRecess {
+            name: "פגרת פסח".to_string(),
+            start: pesach - Duration::days(1),
+            end: pesach + Duration::days(7),
+        },
+        CourtRecess {
+            name: "פגרת קיץ".to_string(),
+            start: date(7, 16),
+            end: date(8, 31),
+        },
+        CourtRecess {
+            name: "פגרת סוכות".to_string(),
+            start: rosh_hashanah + Duration::days(13),
+            end: rosh_hashanah + Duration::days(22),
+        },
+    ]
+}
+
+pub fn court_calendar_year(year: i32) -> CourtCalendarYear {
+    CourtCalendarYear {
+        year,
+        holidays: court_holidays(year),
+        recess: court_recess_periods(year),
+    }
+}
+
+/// Holidays and recess periods, computed a year at a time as dates are
+/// looked up.
+#[derive(Debug, Default)]
+pub struct CourtCalendar {
+    years: HashMap<i32, CourtCalendarYear>,
+}
+
+impl CourtCalendar {
+    fn year(&mut self, year: i32) -> &CourtCalendarYear {
+        self.years
+            .entry(year)
+            .or_insert_with(|| court_calendar_year(year))
+    }
+
+    /// Why the courts are closed on `date` (the weekend or a holiday), or
+    /// `None` on a business day.
+    pub fn closure(&mut self, date: NaiveDate) -> Option<String> {
+        match date.weekday() {
+            Weekday::Fri => return Some("יום שישי".to_string()),
+            Weekday::Sat => return Some("שבת".to_string()),
+            _ => {}
+        }
+
+        self.year(date.year())
+            .holidays
+            .iter()
+            .find(|h| h.date == date)
+            .map(|h| h.name.clone())
+    }
+
+    /// The recess `date` falls in, if any.
+    pub fn recess(&mut self, date: NaiveDate) -> Option<String> {
+        self.year(date.year())
+            .recess
+            .iter()
+            .find(|r| r.start <= date && date <= r.end)
+            .map(|r| r.name.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn rosh_hashanah_dates() {
+        assert_eq!(rosh_hashanah(2023), date(2023, 9, 16));
+        assert_eq!(rosh_hashanah(2024), date(2024, 10, 3));
+        assert_eq!(rosh_hashanah(2025), date(2025, 9, 23));
+        assert_eq!(rosh_hashanah(2026), date(2026, 9, 12));
+    }
+
+    #[test]
+    fn pesach_dates() {
+        assert_eq!(pesach(2023), date(2023, 4, 6));
+        assert_eq!(pesach(2024), date(2024, 4, 23));
+        assert_eq!(pesach(2025), date(2025, 4, 13));
+        assert_eq!(pesach(2026), date(2026, 4, 2));
+    }
+
+    #[test]
+    fn rosh_hashanah_is_never_on_a_postponed_weekday() {
+        for year in 1950..2150 {
+            let weekday = rosh_hashanah(year).weekday();
+            assert!(
+                !matches!(weekday, Weekday::Sun | Weekday::Wed | Weekday::Fri),
+                "Rosh Hashanah {} falls on {:?}",
+                year,
+                weekday
+            );
+        }
+    }
+
+    #[test]
+    fn independence_day_moves_off_the_weekend_and_monday() {
+        // 5 Iyar fell on Monday in 2024, Saturday in 2025 and Wednesday in 2026
+        assert_eq!(independence_day(2024), date(2024, 5, 14));
+        assert_eq!(independence_day(2025), date(2025, 5, 1));
+        assert_eq!(independence_day(2026), date(2026, 4, 22));
+    }
+
+    #[test]
+    fn closures() {
+        let mut calendar = CourtCalendar::default();
+
+        assert_eq!(calendar.closure(date(2025, 9, 26)).as_deref(), Some("יום שישי"));
+        assert_eq!(calendar.closure(date(2025, 9, 27)).as_deref(), Some("שבת"));
+        assert_eq!(calendar.closure(date(2025, 10, 2)).as_deref(), Some("יום הכיפורים"));
+        assert_eq!(calendar.closure(date(2025, 9, 28)), None);
+    }
+
+    #[test]
+    fn recess_periods() {
+        let mut calendar = CourtCalendar::default();
+
+        assert_eq!(calendar.recess(date(2025, 7, 15)), None);
+        assert_eq!(calendar.recess(date(2025, 7, 16)).as_deref(), Some("פגרת קיץ"));
+        assert_eq!(calendar.recess(date(2025, 8, 31)).as_deref(), Some("פגרת קיץ"));
+        assert_eq!(calendar.recess(date(2025, 9, 1)), None);
+        assert_eq!(calendar.recess(date(2025, 4, 12)).as_deref(), Some("פגרת פסח"));
+        assert_eq!(calendar.recess(date(2025, 10, 6)).as_deref(), Some("פגרת סוכות"));
+    }
+}
@@ -0,0 +1,218 @@
+use anyhow::{anyhow, bail, Result};
+use chrono::{Duration, NaiveDate};
+use std::fmt;
+
+use crate::{
+    models::{DeadlineCalculation, SkippedDay},
+    services::CourtCalendar,
+};
+
+/// Longest deadline accepted, in days of either kind.
+pub const MAX_DEADLINE_DAYS: u32 = 3650;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeadlineUnit {
+    /// Calendar days; only the last day must be a business day
+    Days,
+    /// Sunday to Thursday, excluding holidays
+    BusinessDays,
+}
+
+/// A deadline such as `45 days`, `30 business days` or
+/// `45 days; skip court recess`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeadlineRule {
+    pub amount: u32,
+    pub unit: DeadlineUnit,
+    /// Recess days are not counted, and the deadline cannot end in a recess
+    pub skip_recess: bool,
+}
+
+impl DeadlineRule {
+    /// Parses a rule such as `30 business days, skip court recess`. Case
+    /// and the separator between parts are not significant.
+    pub fn parse(input: &str) -> Result<Self> {
+        let normalized = input.to_lowercase().replace([',', ';'], " ");
+        let words: Vec<&str> = normalized.split_whitespace().collect();
+
+        let (amount, rest) = words
+            .split_first()
+            .ok_or_else(|| anyhow!("Deadline rule is empty"))?;
+        let amount: u32 = amount
+            .parse()
+            .map_err(|_| anyhow!("Deadline rule must start with a number of days: {}", input))?;
+        if amount == 0 || amount > MAX_DEADLINE_DAYS {
+            bail!("Deadline must be between 1 and {} days", MAX_DEADLINE_DAYS);
+        }
+
+        let (unit, rest) = match rest {
+            ["day" | "days", rest @ ..] => (DeadlineUnit::Days, rest),
+            ["business", "day" | "days", rest @ ..] => (DeadlineUnit::BusinessDays, rest),
+            _ => bail!("Deadline unit must be 'days' or 'business days': {}", input),
+        };
+
+        let skip_recess = match rest {
+            [] => false,
+            ["skip", "recess"] | ["skip", "court", "recess"] => true,
+            _ => bail!("Unknown deadline option: {}", rest.join(" ")),
+        };
+
+        Ok(Self {
+            amount,
+            unit,
+            skip_recess,
+        })
+    }
+
+    /// The due date `self` gives when counting from `trigger`, with every
+    /// day that was passed over and why.
+    ///
+    /// Counting starts the day after the trigger. A deadline that ends on a
+    /// Friday, Saturday or holiday (or, when recess is skipped, in a recess)
+    /// moves to the next day the courts sit.
+    pub fn compute(&self, trigger: NaiveDate) -> DeadlineCalculation {
+        let mut calendar = CourtCalendar::default();
+        let mut skipped_days = Vec::new();
+        let mut date = trigger;
+        let mut counted = 0;
+
+        while counted < self.amount {
+            date += Duration::days(1);
+
+            let not_counted = if self.skip_recess {
+                calendar.recess(date)
+            } else {
+                None
+            }
+            .or_else(|| match self.unit {
+                DeadlineUnit::BusinessDays => calendar.closure(date),
+                DeadlineUnit::Days => None,
+            });
+
+            match not_counted {
+                Some(reason) => skipped_days.push(SkippedDay { date, reason }),
+                None => counted += 1,
+            }
+        }
+
+        // The last day must be one on which documents can be filed
+        loop {
+            let closed = calendar.closure(date).or_else(|| {
+                if self.skip_recess {
+                    calendar.recess(date)
+                } else {
+                    None
+                }
+            });
+
+            match closed {
+                Some(reason) => {
+                    skipped_days.push(SkippedDay { date, reason });
+                    date += Duration::days(1);
+                }
+                None => break,
+            }
+        }
+
+        DeadlineCalculation {
+            trigger_date: trigger,
+            rule: self.to_string(),
+            due_date: date,
+            skipped_days,
+        }
+    }
+}
+
+impl fmt::Display for DeadlineRule {
+    /// The canonical form, as stored with a computed deadline.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let unit = match self.unit {
+            DeadlineUnit::Days => "day",
+            DeadlineUnit::BusinessDays => "business day",
+        };
+        let plural = if self.amount == 1 { "" } else { "s" };
+        write!(f, "{} {}{}", self.amount, unit, plural)?;
+
+        if self.skip_recess {
+            write!(f, "; skip court recess")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn due(rule: &str, trigger: NaiveDate) -> NaiveDate {
+        DeadlineRule::parse(rule).unwrap().compute(trigger).due_date
+    }
+
+    #[test]
+    fn parses_rules() {
+        let rule = DeadlineRule::parse("30 Business Days, skip court recess").unwrap();
+        assert_eq!(rule.amount, 30);
+        assert_eq!(rule.unit, DeadlineUnit::BusinessDays);
+        assert!(rule.skip_recess);
+        assert_eq!(rule.to_string(), "30 business days; skip court recess");
+
+        assert_eq!(DeadlineRule::parse("1 day").unwrap().to_string(), "1 day");
+    }
+
+    #[test]
+    fn rejects_invalid_rules() {
+        assert!(DeadlineRule::parse("").is_err());
+        assert!(DeadlineRule::parse("0 days").is_err());
+        assert!(DeadlineRule::parse("3651 days").is_err());
+        assert!(DeadlineRule::parse("30 weeks").is_err());
+        assert!(DeadlineRule::parse("30 days; skip weekends").is_err());
+    }
+
+    #[test]
+    fn calendar_days_end_on_a_business_day() {
+        // 2025-01-01 + 30 days is Friday 2025-01-31
+        assert_eq!(due("30 days", date(2025, 1, 1)), date(2025, 2, 2));
+        assert_eq!(due("29 days", date(2025, 1, 1)), date(2025, 1, 30));
+    }
+
+    #[test]
+    fn business_days_skip_holidays() {
+        // Rosh Hashanah 2024 is Thursday and Friday, 3-4 October
+        let calculation = DeadlineRule::parse("3 business days")
+            .unwrap()
+            .compute(date(2024, 10, 1));
+
+        assert_eq!(calculation.due_date, date(2024, 10, 7));
+        let skipped: Vec<NaiveDate> = calculation.skipped_days.iter().map(|d| d.date).collect();
+        assert_eq!(skipped, [date(2024, 10, 3), date(2024, 10, 4), date(2024, 10, 5)]);
+        assert_eq!(calculation.skipped_days[0].reason, "ראש השנה");
+    }
+
+    #[test]
+    fn summer_recess_is_not_counted() {
+        assert_eq!(due("10 days; skip court recess", date(2024, 7, 10)), date(2024, 9, 5));
+        // Without the option the recess counts like any other day
+        assert_eq!(due("10 days", date(2024, 7, 10)), date(2024, 7, 21));
+    }
+
+    #[test]
+    fn deadline_ending_in_summer_recess_moves_past_it() {
+        // The tenth day is Friday 15 July 2022; the next day the courts sit
+        // is Thursday 1 September, after the summer recess
+        let calculation = DeadlineRule::parse("10 days; skip court recess")
+            .unwrap()
+            .compute(date(2022, 7, 5));
+
+        assert_eq!(calculation.due_date, date(2022, 9, 1));
+        assert_eq!(calculation.skipped_days.len(), 48);
+        assert_eq!(calculation.skipped_days[0].reason, "יום שישי");
+        assert_eq!(calculation.skipped_days[1].reason, "שבת");
+        assert_eq!(calculation.skipped_days[2].reason, "פגרת קיץ");
+        assert_eq!(calculation.skipped_days.last().unwrap().date, date(2022, 8, 31));
+    }
+}
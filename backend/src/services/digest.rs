@@ -0,0 +1,159 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use uuid::Uuid;
+
+use crate::{
+    db,
+    models::{priority_rank, NotificationSettings, Task},
+    AppState,
+};
+
+/// How often the digest job checks whose digest time has come.
+const DIGEST_INTERVAL: std::time::Duration = std::time::Duration::from_secs(15 * 60);
+
+/// How far ahead "due this week" looks.
+const DUE_SOON_DAYS: i64 = 7;
+
+/// A user's tasks sorted into the digest's sections. Each task appears in at
+/// most one of the open-task sections; `changed` may repeat any of them.
+#[derive(Debug, Default)]
+pub struct TaskDigest {
+    pub overdue: Vec<Task>,
+    pub due_today: Vec<Task>,
+    pub due_this_week: Vec<Task>,
+    pub other_open: Vec<Task>,
+    pub changed: Vec<Task>,
+}
+
+impl TaskDigest {
+    pub fn build(tasks: Vec<Task>, today: NaiveDate, changed_since: DateTime<Utc>) -> Self {
+        let mut digest = TaskDigest::default();
+        let week_end = today + Duration::days(DUE_SOON_DAYS);
+
+        for task in tasks {
+            if task.updated_at > changed_since {
+                digest.changed.push(task.clone());
+            }
+
+            if !matches!(task.status.as_str(), "חדשה" | "בטיפול") {
+                continue;
+            }
+
+            match task.due_date {
+                Some(due) if due < today => digest.overdue.push(task),
+                Some(due) if due == today => digest.due_today.push(task),
+                Some(due) if due <= week_end => digest.due_this_week.push(task),
+                _ => digest.other_open.push(task),
+            }
+        }
+
+        // Most urgent first, then by due date
+        for section in [
+            &mut digest.overdue,
+            &mut digest.due_today,
+            &mut digest.due_this_week,
+            &mut digest.other_open,
+            &mut digest.changed,
+        ] {
+            section.sort_by(|a, b| {
+                priority_rank(&b.priority)
+                    .cmp(&priority_rank(&a.priority))
+                    .then(a.due_date.cmp(&b.due_date))
+            });
+        }
+
+        digest
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.overdue.is_empty()
+            && self.due_today.is_empty()
+            && self.due_this_week.is_empty()
+            && self.other_open.is_empty()
+            && self.changed.is_empty()
+    }
+}
+
+/// Starts the background job that sends each user's digest once a day at
+/// their digest time: batched notifications, plus the task summary for
+/// users who opted in (daily, or weekly on their chosen day).
+pub fn spawn_notification_digest_job(state: AppState) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(DIGEST_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            match send_due_digests(&state).await {
+                Ok(0) => {}
+                Ok(count) => tracing::info!("📬 Queued {} digest email(s)", count),
+                Err(e) => tracing::error!("Digest job failed: {:?}", e),
+            }
+        }
+    });
+}
+
+pub async fn send_due_digests(state: &AppState) -> Result<usize> {
+    let now = Utc::now();
+    let mut sent = 0;
+
+    for user_id in db::get_digest_recipients(&state.pool).await? {
+        match send_digest(state, user_id, now).await {
+            Ok(true) => sent += 1,
+            Ok(false) => {}
+            Err(e) => tracing::error!("Failed to send digest to user {}: {:?}", user_id, e),
+        }
+    }
+
+    Ok(sent)
+}
+
+async fn send_digest(state: &AppState, user_id: Uuid, now: DateTime<Utc>) -> Result<bool> {
+    let settings = db::get_notification_settings(&state.pool, user_id)
+        .await?
+        .unwrap_or_else(|| NotificationSettings::default_for(user_id));
+
+    if !settings.digest_due(now) {
+        return Ok(false);
+    }
+
+    let user = match db::get_user_by_id(&state.pool, user_id).await? {
+        Some(user) if user.is_active => user,
+        _ => return Ok(false),
+    };
+
+    let task_digest = match settings.task_digest_window(now) {
+        Some(window) => {
+            let tasks = db::get_tasks_by_assignee(&state.pool, &user.name).await?;
+            Some(TaskDigest::build(tasks, settings.local_date(now), now - window))
+        }
+        None => None,
+    };
+
+    let mut tx = state.pool.begin().await?;
+
+    // Another instance may be sending the same digest
+    if !db::claim_digest(&mut *tx, user.id, settings.last_digest_sent_at).await? {
+        return Ok(false);
+    }
+
+    let mut items = db::take_pending_digest_items(&mut *tx, user.id).await?;
+    items.sort_by_key(|item| item.created_at);
+
+    let has_tasks = task_digest.as_ref().map_or(false, |d| !d.is_empty());
+    if items.is_empty() && !has_tasks {
+        return Ok(false);
+    }
+
+    let email = state.email_service.build_digest(
+        &user,
+        &settings,
+        &items,
+        task_digest.as_ref().filter(|d| !d.is_empty()),
+    )?;
+    db::enqueue_email(&mut *tx, &email).await?;
+
+    tx.commit().await?;
+
+    Ok(true)
+}
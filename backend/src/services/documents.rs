@@ -0,0 +1,513 @@
+use anyhow::{anyhow, bail, Context, Result};
+use askama::Template;
+use chrono::{Datelike, NaiveDate};
+use std::{
+    collections::HashMap,
+    io::{Cursor, Read, Write},
+};
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::{
+    models::{Client, Matter, Task, User, DOCUMENT_FORMAT_DOCX},
+    services::{fill_placeholders, find_placeholders},
+};
+
+pub const DOCX_MIME_TYPE: &str =
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.document";
+pub const HTML_MIME_TYPE: &str = "text/html; charset=utf-8";
+
+const HEBREW_MONTHS: [&str; 12] = [
+    "ינואר", "פברואר", "מרץ", "אפריל", "מאי", "יוני",
+    "יולי", "אוגוסט", "ספטמבר", "אוקטובר", "נובמבר", "דצמבר",
+];
+
+/// Parts of a DOCX file that hold text: the body, headers, footers and notes.
+const DOCX_TEXT_PARTS: &[&str] = &["document", "header", "footer", "footnotes", "endnotes"];
+
+/// Run properties that come after `<w:rtl/>` in the schema's order, and the
+/// end of the properties.
+const RUN_PROPERTIES_AFTER_RTL: &[&str] = &[
+    "<w:cs/>", "<w:cs ", "<w:em ", "<w:lang ", "<w:eastAsianLayout ", "<w:specVanish", "<w:oMath",
+    "</w:rPr>",
+];
+
+/// The records a document is filled from.
+pub struct DocumentContext<'a> {
+    pub task: &'a Task,
+    pub matter: Option<&'a Matter>,
+    pub client: Option<&'a Client>,
+    /// The matter's responsible lawyer
+    pub lawyer: Option<&'a User>,
+    /// Who the document is generated by
+    pub user: &'a User,
+    pub office_name: &'a str,
+    pub office_tax_id: &'a str,
+    pub today: NaiveDate,
+}
+
+/// Placeholder values from the records, e.g. `{client.name}` or
+/// `{matter.case_number}`. Fields that are not set get no value, so a
+/// template that needs them is reported rather than filled with blanks.
+pub fn document_values(context: &DocumentContext) -> HashMap<String, String> {
+    let mut values = HashMap::new();
+    let mut set = |name: &str, value: Option<&str>| {
+        if let Some(value) = value.map(str::trim).filter(|v| !v.is_empty()) {
+            values.insert(name.to_string(), value.to_string());
+        }
+    };
+
+    set("today", Some(&context.today.format("%d/%m/%Y").to_string()));
+    set("today_long", Some(&long_date(context.today)));
+
+    let task = context.task;
+    set("task.title", Some(&task.title));
+    set("task.code", Some(&task.task_id));
+    set("task.description", task.description.as_deref());
+    set("task.category", Some(&task.category));
+    set("task.status", Some(&task.status));
+    set("task.priority", Some(&task.priority));
+    set("task.assigned_to", Some(&task.assigned_to));
+    set("task.due_date", task.due_date.map(|d| d.format("%d/%m/%Y").to_string()).as_deref());
+
+    if let Some(matter) = context.matter {
+        set("matter.number", Some(&matter.matter_number));
+        set("matter.title", Some(&matter.title));
+        set("matter.court", matter.court.as_deref());
+        set("matter.case_number", matter.case_number.as_deref());
+        set("matter.opposing_party", matter.opposing_party.as_deref());
+        set("matter.description", matter.description.as_deref());
+        set("matter.opened_at", Some(&matter.opened_at.format("%d/%m/%Y").to_string()));
+    }
+
+    if let Some(client) = context.client {
+        set("client.name", Some(&client.name));
+        set("client.id_number", client.id_number.as_deref());
+        set("client.email", client.email.as_deref());
+        set("client.phone", client.phone.as_deref());
+        set("client.address", client.address.as_deref());
+    }
+
+    if let Some(lawyer) = context.lawyer {
+        set("lawyer.name", Some(&lawyer.name));
+        set("lawyer.email", Some(&lawyer.email));
+    }
+
+    set("user.name", Some(&context.user.name));
+    set("user.email", Some(&context.user.email));
+    set("office.name", Some(context.office_name));
+    set("office.tax_id", Some(context.office_tax_id));
+
+    values
+}
+
+/// `19 באוקטובר 2026`
+fn long_date(date: NaiveDate) -> String {
+    format!("{} ב{} {}", date.day(), HEBREW_MONTHS[date.month0() as usize], date.year())
+}
+
+/// The placeholders a template uses, in order of first use. Fails if the
+/// template cannot be read as its format.
+pub fn template_placeholders(format: &str, content: &[u8]) -> Result<Vec<String>> {
+    if format == DOCUMENT_FORMAT_DOCX {
+        return docx_placeholders(content);
+    }
+
+    let html = std::str::from_utf8(content).context("HTML template is not UTF-8")?;
+    Ok(find_placeholders(html))
+}
+
+/// Fills in a template. Values are escaped for the format, and every
+/// placeholder must have one.
+pub fn render_document(
+    format: &str,
+    content: &[u8],
+    title: &str,
+    values: &HashMap<String, String>,
+) -> Result<Vec<u8>> {
+    if format == DOCUMENT_FORMAT_DOCX {
+        return render_docx(content, values);
+    }
+
+    let html = std::str::from_utf8(content).context("HTML template is not UTF-8")?;
+    Ok(render_html(html, title, values)?.into_bytes())
+}
+
+fn missing_error(missing: Vec<String>) -> anyhow::Error {
+    anyhow!("Missing template variables: {}", missing.join(", "))
+}
+
+#[derive(Template)]
+#[template(path = "document.html")]
+struct DocumentHtml<'a> {
+    title: &'a str,
+    body: &'a str,
+}
+
+/// Values in running text are isolated with `<bdi>`, so that a Latin name,
+/// a case number or an address keeps its own direction without reordering
+/// the Hebrew around it. A template without an `<html>` element is wrapped
+/// in a right-to-left page.
+fn render_html(template: &str, title: &str, values: &HashMap<String, String>) -> Result<String> {
+    let plain: HashMap<String, String> = values
+        .iter()
+        .map(|(name, value)| (name.clone(), escape_markup(value)))
+        .collect();
+    let isolated: HashMap<String, String> = values
+        .iter()
+        .map(|(name, value)| {
+            let value = escape_markup(value).replace("\r\n", "\n").replace('\n', "<br>");
+            (name.clone(), format!("<bdi>{}</bdi>", value))
+        })
+        .collect();
+
+    let mut html = String::with_capacity(template.len());
+    let mut missing: Vec<String> = Vec::new();
+    for (segment, is_text) in html_segments(template) {
+        match fill_placeholders(segment, if is_text { &isolated } else { &plain }) {
+            Ok(filled) => html.push_str(&filled),
+            Err(names) => {
+                for name in names {
+                    if !missing.contains(&name) {
+                        missing.push(name);
+                    }
+                }
+            }
+        }
+    }
+    if !missing.is_empty() {
+        return Err(missing_error(missing));
+    }
+
+    let lower = html.to_ascii_lowercase();
+    let start = match lower.find("<html") {
+        Some(start) => start,
+        None => return Ok(DocumentHtml { title, body: &html }.render()?),
+    };
+    let end = lower[start..].find('>').map_or(lower.len(), |end| start + end);
+    if !lower[start..end].contains("dir=") {
+        html.insert_str(start + "<html".len(), " dir=\"rtl\"");
+    }
+
+    Ok(html)
+}
+
+/// Splits HTML into tags and the text between them, marking running text.
+/// The content of `<title>`, `<style>`, `<script>` and `<textarea>` is not
+/// running text, as markup there is not interpreted.
+fn html_segments(html: &str) -> Vec<(&str, bool)> {
+    let mut segments = Vec::new();
+    let mut in_raw_text = false;
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let tag_start = match rest.find('<') {
+            Some(0) => 0,
+            Some(start) => {
+                segments.push((&rest[..start], !in_raw_text));
+                start
+            }
+            None => {
+                segments.push((rest, !in_raw_text));
+                break;
+            }
+        };
+        let tag_end = rest[tag_start..].find('>').map_or(rest.len(), |end| tag_start + end + 1);
+        let tag = &rest[tag_start..tag_end];
+
+        let name = tag
+            .trim_start_matches(['<', '/'])
+            .split(|c: char| c.is_whitespace() || c == '>' || c == '/')
+            .next()
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        if ["title", "style", "script", "textarea"].contains(&name.as_str()) {
+            in_raw_text = !tag.starts_with("</");
+        }
+
+        segments.push((tag, false));
+        rest = &rest[tag_end..];
+    }
+
+    segments
+}
+
+fn escape_markup(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+fn open_docx(content: &[u8]) -> Result<ZipArchive<Cursor<&[u8]>>> {
+    let archive = ZipArchive::new(Cursor::new(content)).context("Not a DOCX file")?;
+    if archive.index_for_name("word/document.xml").is_none() {
+        bail!("Not a DOCX file: word/document.xml is missing");
+    }
+    Ok(archive)
+}
+
+fn is_docx_text_part(name: &str) -> bool {
+    match name.strip_prefix("word/").and_then(|n| n.strip_suffix(".xml")) {
+        Some(part) => !part.contains('/') && DOCX_TEXT_PARTS.iter().any(|p| part.starts_with(p)),
+        None => false,
+    }
+}
+
+fn docx_placeholders(content: &[u8]) -> Result<Vec<String>> {
+    let mut archive = open_docx(content)?;
+    let mut names: Vec<String> = Vec::new();
+
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i)?;
+        if !is_docx_text_part(file.name()) {
+            continue;
+        }
+        let mut xml = String::new();
+        file.read_to_string(&mut xml)?;
+
+        rewrite_docx_text(&xml, |text| {
+            for name in find_placeholders(text) {
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+            text.to_string()
+        });
+    }
+
+    Ok(names)
+}
+
+/// Everything but the text parts is copied over as it is.
+fn render_docx(content: &[u8], values: &HashMap<String, String>) -> Result<Vec<u8>> {
+    let mut archive = open_docx(content)?;
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let mut missing: Vec<String> = Vec::new();
+
+    for i in 0..archive.len() {
+        let name = archive.by_index_raw(i)?.name().to_string();
+        if !is_docx_text_part(&name) {
+            writer.raw_copy_file(archive.by_index_raw(i)?)?;
+            continue;
+        }
+
+        let mut xml = String::new();
+        archive.by_index(i)?.read_to_string(&mut xml)?;
+        let filled = rewrite_docx_text(&xml, |text| match fill_placeholders(text, values) {
+            Ok(filled) => filled,
+            Err(names) => {
+                for name in names {
+                    if !missing.contains(&name) {
+                        missing.push(name);
+                    }
+                }
+                text.to_string()
+            }
+        });
+
+        writer.start_file(name, options)?;
+        writer.write_all(filled.as_bytes())?;
+    }
+    if !missing.is_empty() {
+        return Err(missing_error(missing));
+    }
+
+    Ok(writer.finish()?.into_inner())
+}
+
+/// A `<w:t>` element, by byte range, and its text.
+struct TextNode {
+    start: usize,
+    end: usize,
+    text: String,
+}
+
+/// Runs `fill` over the text of each paragraph in a WordprocessingML part.
+///
+/// Word splits text into runs wherever formatting, spell checking or edit
+/// history changes, so a placeholder can be spread over several `<w:t>`
+/// elements. Its pieces are joined into the element where it starts, which
+/// keeps that run's formatting. A run that ends up with Hebrew and no Latin
+/// letters is marked right-to-left, as a placeholder typed in Latin letters
+/// into a Hebrew document sits in a left-to-right run.
+fn rewrite_docx_text(xml: &str, mut fill: impl FnMut(&str) -> String) -> String {
+    let mut output = String::with_capacity(xml.len());
+    let mut rest = xml;
+
+    while !rest.is_empty() {
+        let end = rest.find("</w:p>").map_or(rest.len(), |end| end + "</w:p>".len());
+        rewrite_paragraph(&rest[..end], &mut fill, &mut output);
+        rest = &rest[end..];
+    }
+
+    output
+}
+
+fn rewrite_paragraph(xml: &str, fill: &mut impl FnMut(&str) -> String, output: &mut String) {
+    let mut nodes = text_nodes(xml);
+    if !nodes.iter().any(|node| node.text.contains('{')) {
+        output.push_str(xml);
+        return;
+    }
+
+    let mut i = 0;
+    while i < nodes.len() {
+        let mut next = i + 1;
+        while has_open_brace(&nodes[i].text) && next < nodes.len() {
+            let piece = std::mem::take(&mut nodes[next].text);
+            nodes[i].text.push_str(&piece);
+            next += 1;
+        }
+        i = next;
+    }
+
+    let mut last = 0;
+    for node in nodes.iter() {
+        let filled = fill(&node.text);
+        let before = &xml[last..node.start];
+        if filled.chars().any(is_hebrew) && !filled.chars().any(|c| c.is_ascii_alphabetic()) {
+            output.push_str(&mark_run_rtl(before));
+        } else {
+            output.push_str(before);
+        }
+        output.push_str("<w:t xml:space=\"preserve\">");
+        output.push_str(&docx_text(&filled));
+        output.push_str("</w:t>");
+        last = node.end;
+    }
+    output.push_str(&xml[last..]);
+}
+
+fn text_nodes(xml: &str) -> Vec<TextNode> {
+    let mut nodes = Vec::new();
+    let mut offset = 0;
+
+    while let Some(found) = xml[offset..].find("<w:t") {
+        let start = offset + found;
+        let name_end = start + "<w:t".len();
+        // `<w:tab/>`, `<w:tbl>` and the like are other elements
+        if !matches!(xml[name_end..].chars().next(), Some('>') | Some(' ') | Some('/')) {
+            offset = name_end;
+            continue;
+        }
+        let open_end = match xml[name_end..].find('>') {
+            Some(end) => name_end + end + 1,
+            None => break,
+        };
+        if xml[..open_end].ends_with("/>") {
+            offset = open_end;
+            continue;
+        }
+        let close = match xml[open_end..].find("</w:t>") {
+            Some(close) => open_end + close,
+            None => break,
+        };
+
+        nodes.push(TextNode {
+            start,
+            end: close + "</w:t>".len(),
+            text: unescape_xml(&xml[open_end..close]),
+        });
+        offset = close + "</w:t>".len();
+    }
+
+    nodes
+}
+
+fn has_open_brace(text: &str) -> bool {
+    text.rfind('{').is_some_and(|open| !text[open..].contains('}'))
+}
+
+fn is_hebrew(ch: char) -> bool {
+    ('\u{0590}'..='\u{05FF}').contains(&ch)
+}
+
+/// Escapes text for a `<w:t>`, turning line breaks and tabs into their
+/// own elements.
+fn docx_text(text: &str) -> String {
+    escape_markup(text)
+        .replace("\r\n", "\n")
+        .replace('\n', "</w:t><w:br/><w:t xml:space=\"preserve\">")
+        .replace('\t', "</w:t><w:tab/><w:t xml:space=\"preserve\">")
+}
+
+/// Adds `<w:rtl/>` to the run that the markup before a `<w:t>` opens, in
+/// its place in the schema's order of run properties.
+fn mark_run_rtl(before: &str) -> String {
+    let run_start = match ["<w:r>", "<w:r "].iter().filter_map(|tag| before.rfind(tag)).max() {
+        Some(start) => start,
+        None => return before.to_string(),
+    };
+    let run = &before[run_start..];
+    if run.contains("<w:rtl/>") || run.contains("<w:rtl ") {
+        return before.to_string();
+    }
+
+    let mut marked = before.to_string();
+    if let Some(empty) = run.find("<w:rPr/>") {
+        let empty = run_start + empty;
+        marked.replace_range(empty..empty + "<w:rPr/>".len(), "<w:rPr><w:rtl/></w:rPr>");
+    } else if let Some(properties) = run.find("<w:rPr>") {
+        let properties = &run[properties..];
+        let at = RUN_PROPERTIES_AFTER_RTL
+            .iter()
+            .filter_map(|tag| properties.find(tag))
+            .min()
+            .unwrap_or(0);
+        let offset = before.len() - properties.len() + at;
+        marked.insert_str(offset, "<w:rtl/>");
+    } else if let Some(open_end) = run.find('>') {
+        marked.insert_str(run_start + open_end + 1, "<w:rPr><w:rtl/></w:rPr>");
+    }
+
+    marked
+}
+
+fn unescape_xml(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(amp) = rest.find('&') {
+        unescaped.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let entity = match rest.find(';') {
+            Some(end) => &rest[1..end],
+            None => break,
+        };
+        let ch = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity
+                .strip_prefix("#x")
+                .map(|hex| u32::from_str_radix(hex, 16))
+                .or_else(|| entity.strip_prefix('#').map(|dec| dec.parse::<u32>()))
+                .and_then(Result::ok)
+                .and_then(char::from_u32),
+        };
+        match ch {
+            Some(ch) => {
+                unescaped.push(ch);
+                rest = &rest[entity.len() + 2..];
+            }
+            None => {
+                unescaped.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    unescaped.push_str(rest);
+
+    unescaped
+}
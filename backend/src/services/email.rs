@@ -1,179 +1,561 @@
-use crate::models::Task;
+use crate::models::{
+    DigestItem, NewEmail, NotificationSettings, OutboxEmail, Task, TaskComment, TaskFieldChange,
+    UpcomingExpiry, User,
+};
+use crate::services::{render_calendar, reply_address, task_calendar_event, TaskDigest};
 use anyhow::Result;
-use lettre::message::header::ContentType;
+use askama::Template;
+use chrono::Utc;
+use lettre::message::{header::ContentType, Attachment, MultiPart};
 use lettre::transport::smtp::authentication::Credentials;
-use lettre::{Message, SmtpTransport, Transport};
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use uuid::Uuid;
 
+/// Renders notification emails and delivers them over a pooled SMTP
+/// connection. Handlers never send directly: they queue the rendered
+/// message with `db::enqueue_email` and the outbox worker calls [`EmailService::send`].
+///
+/// Bodies come from the askama templates in `templates/email/`, which are
+/// compiled into the binary. HTML templates escape every value by default.
 #[derive(Clone)]
 pub struct EmailService {
-    smtp_username: String,
-    smtp_password: String,
-    smtp_host: String,
-    smtp_port: u16,
+    from_address: String,
+    /// Public URL of the web app, which email links point to
+    frontend_url: String,
+    mailer: AsyncSmtpTransport<Tokio1Executor>,
+    /// Domain and signing secret of per-task reply addresses
+    reply_to: Option<(String, String)>,
 }
 
 impl EmailService {
-    pub fn new(username: String, password: String, host: String, port: u16) -> Self {
-        Self {
-            smtp_username: username,
-            smtp_password: password,
-            smtp_host: host,
-            smtp_port: port,
+    pub fn new(
+        username: String,
+        password: String,
+        host: String,
+        port: u16,
+        frontend_url: String,
+    ) -> Result<Self> {
+        let creds = Credentials::new(username.clone(), password);
+
+        let mailer = AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&host)?
+            .port(port)
+            .credentials(creds)
+            .build();
+
+        Ok(Self {
+            from_address: username,
+            frontend_url: frontend_url.trim_end_matches('/').to_string(),
+            mailer,
+            reply_to: None,
+        })
+    }
+
+    /// Sets a per-task Reply-To on task emails, so that replies reach the
+    /// inbound endpoint and land on the task as comments. Does nothing
+    /// unless both values are set.
+    pub fn with_reply_addresses(mut self, domain: &str, secret: &str) -> Self {
+        if !domain.is_empty() && !secret.is_empty() {
+            self.reply_to = Some((domain.to_string(), secret.to_string()));
         }
+        self
     }
 
-    pub async fn send_task_notification(&self, task: &Task) -> Result<()> {
-        let subject = format!("משימה חדשה: {}", task.title);
-        let body = self.create_task_email_body(task);
+    /// Link to the task's page in the web app.
+    pub fn task_url(&self, task_id: Uuid) -> String {
+        format!("{}/tasks/{}", self.frontend_url, task_id)
+    }
+
+    /// Link to the matter's page in the web app.
+    pub fn matter_url(&self, matter_id: Uuid) -> String {
+        format!("{}/matters/{}", self.frontend_url, matter_id)
+    }
 
-        self.send_email(&task.assigned_to_email, &subject, &body)
-            .await
+    /// The task's due date as an all-day event, with the same UID as in the
+    /// calendar feed so that an updated invite replaces the earlier one.
+    fn task_invite(&self, task: &Task) -> Option<String> {
+        task_calendar_event(task, &self.task_url(task.id))
+            .map(|event| render_calendar(&task.title, &[event]))
     }
 
-    pub async fn send_task_update_notification(&self, task: &Task) -> Result<()> {
-        let subject = format!("עדכון במשימה: {}", task.title);
-        let body = self.create_task_update_email_body(task);
+    pub fn build_task_notification(&self, task: &Task) -> Result<NewEmail> {
+        let task_url = self.task_url(task.id);
+        let due_date = format_due_date(task);
 
-        self.send_email(&task.assigned_to_email, &subject, &body)
-            .await
+        Ok(NewEmail {
+            recipient: task.assigned_to_email.clone(),
+            subject: format!("משימה חדשה: {}", task.title),
+            html_body: TaskCreatedHtml { task, due_date: &due_date, task_url: &task_url }.render()?,
+            text_body: Some(TaskCreatedText { task, due_date: &due_date, task_url: &task_url }.render()?),
+            kind: "task_created".to_string(),
+            task_id: Some(task.id),
+            send_after: None,
+            calendar_invite: self.task_invite(task),
+        })
     }
 
-    async fn send_email(&self, to: &str, subject: &str, body: &str) -> Result<()> {
-        let email = Message::builder()
-            .from(self.smtp_username.parse()?)
-            .to(to.parse()?)
-            .subject(subject)
-            .header(ContentType::TEXT_HTML)
-            .body(body.to_string())?;
+    /// Update email listing only the fields that changed, as old → new.
+    pub fn build_task_update_notification(
+        &self,
+        task: &Task,
+        changes: &[TaskFieldChange],
+        to: &str,
+    ) -> Result<NewEmail> {
+        let task_url = self.task_url(task.id);
+        let status_class = status_class(&task.status);
+        // Calendars only need updating when the due date or status moved
+        let invite = changes
+            .iter()
+            .any(|c| c.field == "due_date" || c.field == "status")
+            .then(|| self.task_invite(task))
+            .flatten();
 
-        let creds = Credentials::new(
-            self.smtp_username.clone(),
-            self.smtp_password.clone(),
-        );
+        Ok(NewEmail {
+            recipient: to.to_string(),
+            subject: format!("עדכון במשימה: {}", task.title),
+            html_body: TaskUpdatedHtml { task, changes, status_class, task_url: &task_url }
+                .render()?,
+            text_body: Some(TaskUpdatedText { task, changes, task_url: &task_url }.render()?),
+            kind: "task_updated".to_string(),
+            task_id: Some(task.id),
+            send_after: None,
+            calendar_invite: invite,
+        })
+    }
 
-        let mailer = SmtpTransport::starttls_relay(&self.smtp_host)?
-            .port(self.smtp_port)
-            .credentials(creds)
-            .build();
+    pub fn build_comment_mention_notification(
+        &self,
+        task: &Task,
+        comment: &TaskComment,
+        to: &str,
+    ) -> Result<NewEmail> {
+        let task_url = self.task_url(task.id);
+
+        Ok(NewEmail {
+            recipient: to.to_string(),
+            subject: format!("{} הזכיר/ה אותך במשימה: {}", comment.author_name, task.title),
+            html_body: CommentMentionHtml { task, comment, task_url: &task_url }.render()?,
+            text_body: Some(CommentMentionText { task, comment, task_url: &task_url }.render()?),
+            kind: "comment_mention".to_string(),
+            task_id: Some(task.id),
+            send_after: None,
+            calendar_invite: None,
+        })
+    }
+
+    pub fn build_task_unblocked_notification(&self, task: &Task, blocker: &Task) -> Result<NewEmail> {
+        let task_url = self.task_url(task.id);
+        let due_date = format_due_date(task);
 
-        // Send email in background (non-blocking)
-        let mailer_clone = mailer.clone();
-        let email_clone = email.clone();
+        Ok(NewEmail {
+            recipient: task.assigned_to_email.clone(),
+            subject: format!("המשימה שוחררה לביצוע: {}", task.title),
+            html_body: TaskUnblockedHtml { task, blocker, due_date: &due_date, task_url: &task_url }
+                .render()?,
+            text_body: Some(
+                TaskUnblockedText { task, blocker, due_date: &due_date, task_url: &task_url }
+                    .render()?,
+            ),
+            kind: "task_unblocked".to_string(),
+            task_id: Some(task.id),
+            send_after: None,
+            calendar_invite: self.task_invite(task),
+        })
+    }
 
-        tokio::spawn(async move {
-            match mailer_clone.send(&email_clone) {
-                Ok(_) => tracing::info!("Email sent successfully to {}", to),
-                Err(e) => tracing::error!("Failed to send email: {:?}", e),
+    /// Reminder to the assignee that a task is due in `days_left` days.
+    pub fn build_due_reminder(&self, task: &Task, days_left: i64) -> Result<NewEmail> {
+        let task_url = self.task_url(task.id);
+        let due_date = format_due_date(task);
+        let when = match days_left {
+            0 => "המשימה להיום".to_string(),
+            1 => "המשימה למחר".to_string(),
+            days => format!("המשימה בעוד {} ימים", days),
+        };
+
+        Ok(NewEmail {
+            recipient: task.assigned_to_email.clone(),
+            subject: format!("תזכורת ({}): {}", when, task.title),
+            html_body: DueReminderHtml { task, when: &when, due_date: &due_date, task_url: &task_url }
+                .render()?,
+            text_body: Some(
+                DueReminderText { task, when: &when, due_date: &due_date, task_url: &task_url }
+                    .render()?,
+            ),
+            kind: "due_reminder".to_string(),
+            task_id: Some(task.id),
+            send_after: None,
+            calendar_invite: self.task_invite(task),
+        })
+    }
+
+    /// Escalation of an overdue task to its creator or an admin.
+    pub fn build_overdue_escalation(&self, task: &Task, days_overdue: i64, to: &str) -> Result<NewEmail> {
+        let task_url = self.task_url(task.id);
+        let due_date = format_due_date(task);
+
+        Ok(NewEmail {
+            recipient: to.to_string(),
+            subject: format!("משימה באיחור ({} ימים): {}", days_overdue, task.title),
+            html_body: OverdueEscalationHtml {
+                task,
+                days_overdue,
+                due_date: &due_date,
+                task_url: &task_url,
             }
-        });
-
-        Ok(())
-    }
-
-    fn create_task_email_body(&self, task: &Task) -> String {
-        format!(
-            r#"
-<!DOCTYPE html>
-<html dir="rtl" lang="he">
-<head>
-    <meta charset="UTF-8">
-    <style>
-        body {{ font-family: Arial, sans-serif; background-color: #f4f4f4; padding: 20px; }}
-        .container {{ background-color: white; padding: 30px; border-radius: 10px; max-width: 600px; margin: 0 auto; box-shadow: 0 2px 4px rgba(0,0,0,0.1); }}
-        h1 {{ color: #2c3e50; border-bottom: 3px solid #3498db; padding-bottom: 10px; }}
-        .task-info {{ background-color: #ecf0f1; padding: 15px; border-radius: 5px; margin: 20px 0; }}
-        .label {{ font-weight: bold; color: #34495e; }}
-        .value {{ color: #2c3e50; margin-bottom: 10px; }}
-        .footer {{ text-align: center; margin-top: 30px; padding-top: 20px; border-top: 1px solid #ddd; color: #7f8c8d; }}
-    </style>
-</head>
-<body>
-    <div class="container">
-        <h1>🔔 משימה חדשה נוצרה</h1>
-
-        <div class="task-info">
-            <div class="value"><span class="label">מזהה משימה:</span> {}</div>
-            <div class="value"><span class="label">כותרת:</span> {}</div>
-            <div class="value"><span class="label">תיאור:</span> {}</div>
-            <div class="value"><span class="label">קטגוריה:</span> {}</div>
-            <div class="value"><span class="label">עדיפות:</span> {}</div>
-            <div class="value"><span class="label">תאריך יעד:</span> {}</div>
-            <div class="value"><span class="label">נוצר על ידי:</span> {} ({})</div>
-        </div>
-
-        <p style="text-align: center; margin-top: 30px;">
-            <a href="#" style="background-color: #3498db; color: white; padding: 12px 30px; text-decoration: none; border-radius: 5px; display: inline-block;">
-                צפה במשימה
-            </a>
-        </p>
-
-        <div class="footer">
-            <p>מערכת ניהול משימות - משרד עורכי דין</p>
-            <p>⚖️ GH Law Office</p>
-        </div>
-    </div>
-</body>
-</html>
-            "#,
-            task.task_id,
-            task.title,
-            task.description.as_deref().unwrap_or("אין תיאור"),
-            task.category,
-            task.priority,
-            task.due_date.map_or("לא צוין".to_string(), |d| d.to_string()),
-            task.created_by,
-            task.created_by_email
-        )
-    }
-
-    fn create_task_update_email_body(&self, task: &Task) -> String {
-        format!(
-            r#"
-<!DOCTYPE html>
-<html dir="rtl" lang="he">
-<head>
-    <meta charset="UTF-8">
-    <style>
-        body {{ font-family: Arial, sans-serif; background-color: #f4f4f4; padding: 20px; }}
-        .container {{ background-color: white; padding: 30px; border-radius: 10px; max-width: 600px; margin: 0 auto; box-shadow: 0 2px 4px rgba(0,0,0,0.1); }}
-        h1 {{ color: #2c3e50; border-bottom: 3px solid #e67e22; padding-bottom: 10px; }}
-        .task-info {{ background-color: #fef5e7; padding: 15px; border-radius: 5px; margin: 20px 0; }}
-        .label {{ font-weight: bold; color: #34495e; }}
-        .value {{ color: #2c3e50; margin-bottom: 10px; }}
-        .status {{ display: inline-block; padding: 5px 15px; border-radius: 20px; color: white; font-weight: bold; }}
-        .status-new {{ background-color: #3498db; }}
-        .status-in-progress {{ background-color: #f39c12; }}
-        .status-completed {{ background-color: #27ae60; }}
-        .status-cancelled {{ background-color: #95a5a6; }}
-        .footer {{ text-align: center; margin-top: 30px; padding-top: 20px; border-top: 1px solid #ddd; color: #7f8c8d; }}
-    </style>
-</head>
-<body>
-    <div class="container">
-        <h1>🔄 עדכון במשימה</h1>
-
-        <div class="task-info">
-            <div class="value"><span class="label">מזהה משימה:</span> {}</div>
-            <div class="value"><span class="label">כותרת:</span> {}</div>
-            <div class="value"><span class="label">סטטוס:</span> <span class="status">{}</span></div>
-            <div class="value"><span class="label">עדיפות:</span> {}</div>
-        </div>
-
-        <p style="text-align: center; margin-top: 30px;">
-            <a href="#" style="background-color: #e67e22; color: white; padding: 12px 30px; text-decoration: none; border-radius: 5px; display: inline-block;">
-                צפה במשימה
-            </a>
-        </p>
-
-        <div class="footer">
-            <p>מערכת ניהול משימות - משרד עורכי דין</p>
-            <p>⚖️ GH Law Office</p>
-        </div>
-    </div>
-</body>
-</html>
-            "#,
-            task.task_id, task.title, task.status, task.priority
-        )
+            .render()?,
+            text_body: Some(
+                OverdueEscalationText {
+                    task,
+                    days_overdue,
+                    due_date: &due_date,
+                    task_url: &task_url,
+                }
+                .render()?,
+            ),
+            kind: "overdue_escalation".to_string(),
+            task_id: Some(task.id),
+            send_after: None,
+            calendar_invite: None,
+        })
+    }
+
+    /// Reminder of a matter's limitation or renewal date, before it comes
+    /// and once it has passed without being resolved.
+    pub fn build_expiry_reminder(&self, expiry: &UpcomingExpiry, to: &str) -> Result<NewEmail> {
+        let matter_url = self.matter_url(expiry.expiry.matter_id);
+        let type_label = expiry.expiry.type_label();
+        let expires_on = expiry.expiry.expires_on.format("%d/%m/%Y").to_string();
+        let when = match expiry.days_left {
+            days if days < 0 => format!("המועד חלף לפני {} ימים", -days),
+            0 => "המועד היום".to_string(),
+            1 => "המועד מחר".to_string(),
+            days => format!("בעוד {} ימים", days),
+        };
+
+        Ok(NewEmail {
+            recipient: to.to_string(),
+            subject: format!(
+                "{} ({}): תיק {} – {}",
+                type_label, when, expiry.matter_number, expiry.matter_title
+            ),
+            html_body: ExpiryReminderHtml {
+                expiry,
+                type_label,
+                when: &when,
+                expires_on: &expires_on,
+                matter_url: &matter_url,
+            }
+            .render()?,
+            text_body: Some(
+                ExpiryReminderText {
+                    expiry,
+                    type_label,
+                    when: &when,
+                    expires_on: &expires_on,
+                    matter_url: &matter_url,
+                }
+                .render()?,
+            ),
+            kind: "expiry_reminder".to_string(),
+            task_id: None,
+            send_after: None,
+            calendar_invite: None,
+        })
+    }
+
+    /// The user's digest: batched notifications and, when given, the task
+    /// summary grouped by priority.
+    pub fn build_digest(
+        &self,
+        user: &User,
+        settings: &NotificationSettings,
+        items: &[DigestItem],
+        tasks: Option<&TaskDigest>,
+    ) -> Result<NewEmail> {
+        let tz = settings.tz();
+        let entries: Vec<DigestEntry> = items
+            .iter()
+            .map(|item| DigestEntry {
+                summary: &item.summary,
+                time: item.created_at.with_timezone(&tz).format("%d/%m %H:%M").to_string(),
+                url: item.task_id.map(|id| self.task_url(id)),
+            })
+            .collect();
+
+        let sections: Vec<DigestSection> = match tasks {
+            Some(digest) => [
+                ("⚠️ באיחור", &digest.overdue),
+                ("📅 להיום", &digest.due_today),
+                ("🗓️ בשבוע הקרוב", &digest.due_this_week),
+                ("📋 משימות פתוחות נוספות", &digest.other_open),
+                ("🔄 מה השתנה", &digest.changed),
+            ]
+            .into_iter()
+            .filter(|(_, tasks)| !tasks.is_empty())
+            .map(|(title, tasks)| DigestSection {
+                title,
+                groups: self.group_by_priority(tasks),
+            })
+            .collect(),
+            None => Vec::new(),
+        };
+
+        let heading = match (tasks.is_some(), settings.task_digest.as_str()) {
+            (true, "weekly") => "סיכום שבועי",
+            (true, _) => "סיכום יומי",
+            (false, _) => "סיכום עדכונים",
+        };
+        let today = settings.local_date(Utc::now()).format("%d/%m/%Y").to_string();
+
+        Ok(NewEmail {
+            recipient: user.email.clone(),
+            subject: format!("{} - {}", heading, today),
+            html_body: DigestHtml { user, heading, entries: &entries, sections: &sections }
+                .render()?,
+            text_body: Some(
+                DigestText { user, heading, entries: &entries, sections: &sections }.render()?,
+            ),
+            kind: "digest".to_string(),
+            task_id: None,
+            send_after: None,
+            calendar_invite: None,
+        })
+    }
+
+    fn group_by_priority<'a>(&self, tasks: &'a [Task]) -> Vec<DigestGroup<'a>> {
+        ["דחופה", "גבוהה", "רגילה", "נמוכה"]
+            .into_iter()
+            .map(|priority| DigestGroup {
+                priority,
+                tasks: tasks
+                    .iter()
+                    .filter(|t| t.priority == priority)
+                    .map(|task| DigestTask {
+                        task,
+                        due_date: format_due_date(task),
+                        url: self.task_url(task.id),
+                    })
+                    .collect(),
+            })
+            .filter(|group| !group.tasks.is_empty())
+            .collect()
+    }
+
+    /// Delivers a queued message, returning the SMTP server's reply.
+    pub async fn send(&self, email: &OutboxEmail) -> Result<String> {
+        let mut builder = Message::builder()
+            .from(self.from_address.parse()?)
+            .to(email.recipient.parse()?)
+            .subject(email.subject.as_str());
+
+        if let (Some(task_id), Some((domain, secret))) = (email.task_id, &self.reply_to) {
+            builder = builder.reply_to(reply_address(secret, domain, task_id).parse()?);
+        }
+
+        // Older messages in the outbox were queued without a text part
+        let message = match &email.text_body {
+            Some(text) => {
+                let body = MultiPart::alternative_plain_html(text.clone(), email.html_body.clone());
+                match &email.calendar_invite {
+                    Some(invite) => builder.multipart(
+                        MultiPart::mixed().multipart(body).singlepart(
+                            Attachment::new("invite.ics".to_string()).body(
+                                invite.clone(),
+                                ContentType::parse("text/calendar; charset=utf-8; method=PUBLISH")?,
+                            ),
+                        ),
+                    )?,
+                    None => builder.multipart(body)?,
+                }
+            }
+            None => builder
+                .header(ContentType::TEXT_HTML)
+                .body(email.html_body.clone())?,
+        };
+
+        let response = self.mailer.send(message).await?;
+        let reply = format!(
+            "{} {}",
+            response.code(),
+            response.message().collect::<Vec<&str>>().join(" ")
+        );
+
+        Ok(reply)
+    }
+}
+
+fn format_due_date(task: &Task) -> String {
+    task.due_date.map_or("לא צוין".to_string(), |d| d.to_string())
+}
+
+fn status_class(status: &str) -> &'static str {
+    match status {
+        "בטיפול" => "status-in-progress",
+        "הושלמה" => "status-completed",
+        "בוטלה" => "status-cancelled",
+        _ => "status-new",
     }
 }
+
+#[derive(Template)]
+#[template(path = "email/task_created.html")]
+struct TaskCreatedHtml<'a> {
+    task: &'a Task,
+    due_date: &'a str,
+    task_url: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "email/task_created.txt")]
+struct TaskCreatedText<'a> {
+    task: &'a Task,
+    due_date: &'a str,
+    task_url: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "email/task_updated.html")]
+struct TaskUpdatedHtml<'a> {
+    task: &'a Task,
+    changes: &'a [TaskFieldChange],
+    status_class: &'a str,
+    task_url: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "email/task_updated.txt")]
+struct TaskUpdatedText<'a> {
+    task: &'a Task,
+    changes: &'a [TaskFieldChange],
+    task_url: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "email/comment_mention.html")]
+struct CommentMentionHtml<'a> {
+    task: &'a Task,
+    comment: &'a TaskComment,
+    task_url: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "email/comment_mention.txt")]
+struct CommentMentionText<'a> {
+    task: &'a Task,
+    comment: &'a TaskComment,
+    task_url: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "email/task_unblocked.html")]
+struct TaskUnblockedHtml<'a> {
+    task: &'a Task,
+    blocker: &'a Task,
+    due_date: &'a str,
+    task_url: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "email/task_unblocked.txt")]
+struct TaskUnblockedText<'a> {
+    task: &'a Task,
+    blocker: &'a Task,
+    due_date: &'a str,
+    task_url: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "email/due_reminder.html")]
+struct DueReminderHtml<'a> {
+    task: &'a Task,
+    when: &'a str,
+    due_date: &'a str,
+    task_url: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "email/due_reminder.txt")]
+struct DueReminderText<'a> {
+    task: &'a Task,
+    when: &'a str,
+    due_date: &'a str,
+    task_url: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "email/overdue_escalation.html")]
+struct OverdueEscalationHtml<'a> {
+    task: &'a Task,
+    days_overdue: i64,
+    due_date: &'a str,
+    task_url: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "email/overdue_escalation.txt")]
+struct OverdueEscalationText<'a> {
+    task: &'a Task,
+    days_overdue: i64,
+    due_date: &'a str,
+    task_url: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "email/expiry_reminder.html")]
+struct ExpiryReminderHtml<'a> {
+    expiry: &'a UpcomingExpiry,
+    type_label: &'a str,
+    when: &'a str,
+    expires_on: &'a str,
+    matter_url: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "email/expiry_reminder.txt")]
+struct ExpiryReminderText<'a> {
+    expiry: &'a UpcomingExpiry,
+    type_label: &'a str,
+    when: &'a str,
+    expires_on: &'a str,
+    matter_url: &'a str,
+}
+
+struct DigestEntry<'a> {
+    summary: &'a str,
+    time: String,
+    url: Option<String>,
+}
+
+struct DigestSection<'a> {
+    title: &'static str,
+    groups: Vec<DigestGroup<'a>>,
+}
+
+struct DigestGroup<'a> {
+    priority: &'static str,
+    tasks: Vec<DigestTask<'a>>,
+}
+
+struct DigestTask<'a> {
+    task: &'a Task,
+    due_date: String,
+    url: String,
+}
+
+#[derive(Template)]
+#[template(path = "email/digest.html")]
+struct DigestHtml<'a> {
+    user: &'a User,
+    heading: &'a str,
+    entries: &'a [DigestEntry<'a>],
+    sections: &'a [DigestSection<'a>],
+}
+
+#[derive(Template)]
+#[template(path = "email/digest.txt")]
+struct DigestText<'a> {
+    user: &'a User,
+    heading: &'a str,
+    entries: &'a [DigestEntry<'a>],
+    sections: &'a [DigestSection<'a>],
+}
@@ -0,0 +1,114 @@
+use anyhow::Result;
+use chrono::Utc;
+use sqlx::postgres::PgListener;
+use std::{sync::Arc, time::Duration};
+use tokio::sync::broadcast;
+
+use crate::{db, models::TaskEvent, AppState};
+
+/// Postgres channel the `task_events` insert trigger notifies.
+const TASK_EVENTS_CHANNEL: &str = "task_events";
+
+/// Events buffered per subscriber; a client that falls further behind is
+/// disconnected and resumes from its `Last-Event-ID`.
+const TASK_EVENTS_BUFFER: usize = 256;
+
+/// Wait before reconnecting after the listener fails.
+const LISTENER_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// How long events are kept for resuming clients.
+const TASK_EVENTS_RETENTION_DAYS: i64 = 7;
+const TASK_EVENTS_PRUNE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Most events a listener catches up on after a lost connection.
+const CATCH_UP_LIMIT: i64 = 500;
+
+/// Fans out committed task events to the SSE connections of this instance.
+#[derive(Clone)]
+pub struct TaskEventBroadcaster {
+    sender: broadcast::Sender<Arc<TaskEvent>>,
+}
+
+impl TaskEventBroadcaster {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(TASK_EVENTS_BUFFER);
+        Self { sender }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<Arc<TaskEvent>> {
+        self.sender.subscribe()
+    }
+
+    fn publish(&self, event: TaskEvent) {
+        // No receivers just means nobody is connected right now
+        let _ = self.sender.send(Arc::new(event));
+    }
+}
+
+impl Default for TaskEventBroadcaster {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Starts listening for task events from every API instance, and pruning
+/// events too old to resume from.
+pub fn spawn_task_event_listener(state: AppState) {
+    let listener_state = state.clone();
+    tokio::spawn(async move {
+        loop {
+            if let Err(e) = listen_for_task_events(&listener_state).await {
+                tracing::error!("Task event listener failed: {:?}", e);
+            }
+            tokio::time::sleep(LISTENER_RETRY_DELAY).await;
+        }
+    });
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(TASK_EVENTS_PRUNE_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            let cutoff = Utc::now() - chrono::Duration::days(TASK_EVENTS_RETENTION_DAYS);
+            match db::prune_task_events(&state.pool, cutoff).await {
+                Ok(0) => {}
+                Ok(count) => tracing::info!("🧹 Pruned {} old task event(s)", count),
+                Err(e) => tracing::error!("Failed to prune task events: {:?}", e),
+            }
+        }
+    });
+}
+
+/// Broadcasts every notified event. The listener reconnects by itself when
+/// the connection drops; notifications sent meanwhile are lost, so it then
+/// catches up from the last event it saw.
+async fn listen_for_task_events(state: &AppState) -> Result<()> {
+    let mut listener = PgListener::connect_with(&state.pool).await?;
+    listener.listen(TASK_EVENTS_CHANNEL).await?;
+
+    let mut last_seen = db::get_latest_task_event_id(&state.pool).await?;
+
+    loop {
+        match listener.try_recv().await? {
+            Some(notification) => {
+                let Ok(id) = notification.payload().parse::<i64>() else {
+                    continue;
+                };
+
+                if let Some(event) = db::get_task_event_by_id(&state.pool, id).await? {
+                    last_seen = last_seen.max(event.id);
+                    state.task_events.publish(event);
+                }
+            }
+            None => {
+                tracing::warn!("Task event listener lost its connection, catching up");
+
+                for event in db::get_task_events_after(&state.pool, last_seen, CATCH_UP_LIMIT).await? {
+                    last_seen = event.id;
+                    state.task_events.publish(event);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use uuid::Uuid;
+
+use crate::models::{FeedHearing, Task, HEARING_CANCELLED};
+
+const PRODID: &str = "-//Law Office//Task Management//HE";
+
+/// Right-hand side of every UID, so that the same task has the same UID in
+/// the feed and in invites and calendars update the event in place.
+const UID_DOMAIN: &str = "tasks.law-office";
+
+/// Octets per content line before it is folded (RFC 5545 §3.1).
+const MAX_LINE_OCTETS: usize = 75;
+
+/// Hearings are entered with a start time only.
+const HEARING_DURATION_MINUTES: i64 = 60;
+
+#[derive(Debug, Clone)]
+pub enum EventTime {
+    AllDay(NaiveDate),
+    Timed {
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct CalendarEvent {
+    pub uid: String,
+    pub summary: String,
+    pub description: Option<String>,
+    pub location: Option<String>,
+    pub url: Option<String>,
+    pub time: EventTime,
+    /// Grows with every change, so clients replace their copy
+    pub sequence: i64,
+    pub last_modified: DateTime<Utc>,
+    pub cancelled: bool,
+}
+
+pub fn task_uid(task_id: Uuid) -> String {
+    format!("task-{}@{}", task_id, UID_DOMAIN)
+}
+
+pub fn hearing_uid(hearing_id: Uuid) -> String {
+    format!("hearing-{}@{}", hearing_id, UID_DOMAIN)
+}
+
+/// An all-day event on the task's due date, or `None` without one. Closed
+/// tasks give a cancelled event, which removes it from the calendar.
+pub fn task_calendar_event(task: &Task, url: &str) -> Option<CalendarEvent> {
+    let due_date = task.due_date?;
+
+    let mut description = format!("עדיפות: {}\nסטטוס: {}", task.priority, task.status);
+    if let Some(text) = task.description.as_deref().filter(|d| !d.is_empty()) {
+        description.push_str("\n\n");
+        description.push_str(text);
+    }
+
+    Some(CalendarEvent {
+        uid: task_uid(task.id),
+        summary: format!("{} ({})", task.title, task.task_id),
+        description: Some(description),
+        location: None,
+        url: Some(url.to_string()),
+        time: EventTime::AllDay(due_date),
+        sequence: sequence(task.created_at, task.updated_at),
+        last_modified: task.updated_at,
+        cancelled: matches!(task.status.as_str(), "הושלמה" | "בוטלה"),
+    })
+}
+
+pub fn hearing_calendar_event(feed_hearing: &FeedHearing) -> CalendarEvent {
+    let hearing = &feed_hearing.hearing;
+
+    let location = match (&hearing.court, &hearing.courtroom) {
+        (Some(court), Some(room)) => Some(format!("{}, אולם {}", court, room)),
+        (Some(court), None) => Some(court.clone()),
+        (None, Some(room)) => Some(format!("אולם {}", room)),
+        (None, None) => None,
+    };
+
+    let mut description = format!("תיק {}: {}", feed_hearing.matter_number, feed_hearing.matter_title);
+    if let Some(case_number) = &feed_hearing.case_number {
+        description.push_str(&format!("\nמספר הליך: {}", case_number));
+    }
+    if let Some(judge) = &hearing.judge {
+        description.push_str(&format!("\nבפני: {}", judge));
+    }
+    if let Some(notes) = hearing.notes.as_deref().filter(|n| !n.is_empty()) {
+        description.push_str("\n\n");
+        description.push_str(notes);
+    }
+
+    CalendarEvent {
+        uid: hearing_uid(hearing.id),
+        summary: format!("דיון {} – {}", hearing.hearing_type, feed_hearing.matter_number),
+        description: Some(description),
+        location,
+        url: None,
+        time: EventTime::Timed {
+            start: hearing.scheduled_at,
+            end: hearing.scheduled_at + Duration::minutes(HEARING_DURATION_MINUTES),
+        },
+        sequence: sequence(hearing.created_at, hearing.updated_at),
+        last_modified: hearing.updated_at,
+        cancelled: hearing.status == HEARING_CANCELLED,
+    }
+}
+
+/// A VCALENDAR object with the given events, as served to subscribed
+/// calendars (`METHOD:PUBLISH`) or attached to an email.
+pub fn render_calendar(name: &str, events: &[CalendarEvent]) -> String {
+    let now = Utc::now();
+    let mut lines: Vec<String> = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!("PRODID:{}", PRODID),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+        format!("X-WR-CALNAME:{}", escape_text(name)),
+    ];
+
+    for event in events {
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}", event.uid));
+        lines.push(format!("DTSTAMP:{}", format_utc(now)));
+        lines.push(format!("LAST-MODIFIED:{}", format_utc(event.last_modified)));
+        lines.push(format!("SEQUENCE:{}", event.sequence));
+
+        match &event.time {
+            EventTime::AllDay(date) => {
+                lines.push(format!("DTSTART;VALUE=DATE:{}", date.format("%Y%m%d")));
+                lines.push(format!(
+                    "DTEND;VALUE=DATE:{}",
+                    (*date + Duration::days(1)).format("%Y%m%d")
+                ));
+                lines.push("TRANSP:TRANSPARENT".to_string());
+            }
+            EventTime::Timed { start, end } => {
+                lines.push(format!("DTSTART:{}", format_utc(*start)));
+                lines.push(format!("DTEND:{}", format_utc(*end)));
+            }
+        }
+
+        lines.push(format!("SUMMARY:{}", escape_text(&event.summary)));
+        if let Some(description) = &event.description {
+            lines.push(format!("DESCRIPTION:{}", escape_text(description)));
+        }
+        if let Some(location) = &event.location {
+            lines.push(format!("LOCATION:{}", escape_text(location)));
+        }
+        if let Some(url) = &event.url {
+            lines.push(format!("URL:{}", url));
+        }
+        lines.push(format!(
+            "STATUS:{}",
+            if event.cancelled { "CANCELLED" } else { "CONFIRMED" }
+        ));
+        lines.push("END:VEVENT".to_string());
+    }
+
+    lines.push("END:VCALENDAR".to_string());
+
+    lines.iter().map(|line| fold_line(line)).collect()
+}
+
+/// Seconds between creation and the last change; increases on every update
+/// without having to be stored.
+fn sequence(created_at: DateTime<Utc>, updated_at: DateTime<Utc>) -> i64 {
+    (updated_at - created_at).num_seconds().max(0)
+}
+
+fn format_utc(time: DateTime<Utc>) -> String {
+    time.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Escapes a TEXT value (RFC 5545 §3.3.11).
+pub(crate) fn escape_text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Splits a content line into CRLF-terminated lines of at most 75 octets,
+/// never inside a UTF-8 character; continuation lines start with a space.
+pub(crate) fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + 8);
+    let mut octets = 0;
+
+    for ch in line.chars() {
+        if octets + ch.len_utf8() > MAX_LINE_OCTETS {
+            folded.push_str("\r\n ");
+            octets = 1;
+        }
+        folded.push(ch);
+        octets += ch.len_utf8();
+    }
+
+    folded.push_str("\r\n");
+    folded
+}
@@ -0,0 +1,306 @@
+use anyhow::{anyhow, Result};
+use hmac::{Hmac, Mac};
+use mail_parser::{HeaderValue, MessageParser, MimeHeaders};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::models::NewAttachment;
+
+/// Local-part prefix of per-task reply addresses: `reply+<task><signature>@domain`.
+const REPLY_PREFIX: &str = "reply+";
+
+/// Hex characters of the HMAC kept in a reply address.
+const REPLY_SIGNATURE_LEN: usize = 16;
+
+/// Longest comment created from a reply, matching the comments API.
+pub const MAX_REPLY_CHARS: usize = 10000;
+
+/// Headers, besides To and Cc, in which MTAs record the envelope recipient.
+const ENVELOPE_HEADERS: [&str; 3] = ["Delivered-To", "X-Original-To", "Envelope-To"];
+
+/// Lines that end the new text of a reply: mobile signatures and the
+/// separators mail clients put above forwarded or quoted messages.
+const REPLY_CUT_PREFIXES: [&str; 8] = [
+    "-----Original Message-----",
+    "-----הודעה מקורית-----",
+    "________________________________",
+    "Sent from my ",
+    "Get Outlook for ",
+    "נשלח מה-",
+    "נשלח מ-",
+    "נשלח מהטלפון",
+];
+
+/// A received message, reduced to what inbound processing needs.
+#[derive(Debug)]
+pub struct ParsedEmail {
+    pub message_id: String,
+    pub sender: Option<String>,
+    pub subject: Option<String>,
+    /// Lower-cased To, Cc and envelope recipients
+    pub recipients: Vec<String>,
+    pub text: String,
+    pub attachments: Vec<NewAttachment>,
+    /// Out-of-office and other automatic replies, which must not become comments
+    pub is_auto_reply: bool,
+    /// Every `Authentication-Results` header, as received
+    pub authentication_results: Vec<String>,
+}
+
+/// Address that routes replies to a notification back to its task. The
+/// signature stops anyone from commenting on a task by guessing its id.
+pub fn reply_address(secret: &str, domain: &str, task_id: Uuid) -> String {
+    format!(
+        "{}{}{}@{}",
+        REPLY_PREFIX,
+        task_id.simple(),
+        reply_signature(secret, task_id),
+        domain
+    )
+}
+
+/// The task a reply address belongs to, if it is one of ours and the
+/// signature checks out.
+pub fn task_id_from_reply_address(secret: &str, domain: &str, address: &str) -> Option<Uuid> {
+    let (local, address_domain) = address.rsplit_once('@')?;
+    if !address_domain.eq_ignore_ascii_case(domain) {
+        return None;
+    }
+
+    let token = local.to_lowercase();
+    let token = token.strip_prefix(REPLY_PREFIX)?;
+    if token.len() != 32 + REPLY_SIGNATURE_LEN {
+        return None;
+    }
+
+    let (id, signature) = token.split_at(32);
+    let task_id = Uuid::parse_str(id).ok()?;
+    constant_time_eq(signature, &reply_signature(secret, task_id)).then_some(task_id)
+}
+
+fn reply_signature(secret: &str, task_id: Uuid) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(b"reply:");
+    mac.update(task_id.as_bytes());
+
+    mac.finalize()
+        .into_bytes()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<String>()[..REPLY_SIGNATURE_LEN]
+        .to_string()
+}
+
+/// Compares secrets without leaking how much of them matched.
+pub fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len() && a.bytes().zip(b.bytes()).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+pub fn parse_email(raw: &[u8]) -> Result<ParsedEmail> {
+    let message = MessageParser::default()
+        .parse(raw)
+        .ok_or_else(|| anyhow!("Not a valid RFC 822 message"))?;
+
+    // Without a Message-ID, the content itself identifies redeliveries
+    let message_id = match message.message_id() {
+        Some(id) => id.to_string(),
+        None => {
+            let digest: String = Sha256::digest(raw).iter().map(|b| format!("{:02x}", b)).collect();
+            format!("sha256:{}", digest)
+        }
+    };
+
+    let sender = message
+        .from()
+        .and_then(|a| a.first())
+        .and_then(|a| a.address())
+        .map(|a| a.to_lowercase());
+
+    let mut recipients: Vec<String> = message
+        .to()
+        .into_iter()
+        .chain(message.cc())
+        .flat_map(|list| list.iter())
+        .filter_map(|a| a.address())
+        .map(|a| a.to_lowercase())
+        .collect();
+    for header in ENVELOPE_HEADERS {
+        for value in message.header_values(header) {
+            if let Some(text) = value.as_text() {
+                recipients.push(text.trim().trim_matches(['<', '>']).to_lowercase());
+            }
+        }
+    }
+    recipients.sort();
+    recipients.dedup();
+
+    let authentication_results = message
+        .header_values("Authentication-Results")
+        .filter_map(|value| value.as_text())
+        .map(str::to_string)
+        .collect();
+
+    let is_auto_reply = header_text(&message, "Auto-Submitted")
+        .map_or(false, |v| !v.eq_ignore_ascii_case("no"))
+        || message.header("X-Autoreply").is_some()
+        || message.header("X-Autorespond").is_some()
+        || header_text(&message, "Precedence").map_or(false, |v| {
+            ["bulk", "junk", "list", "auto_reply"].contains(&v.to_lowercase().as_str())
+        });
+
+    // mail-parser converts HTML-only messages to text
+    let text = message
+        .body_text(0)
+        .map(|t| t.replace("\r\n", "\n"))
+        .unwrap_or_default();
+
+    let attachments = message
+        .attachments()
+        .enumerate()
+        .filter(|(_, part)| {
+            // Inline images are signature logos and the like
+            let inline = part
+                .content_disposition()
+                .map_or(false, |cd| cd.ctype().eq_ignore_ascii_case("inline"));
+            !(inline && part.content_id().is_some()) && !part.contents().is_empty()
+        })
+        .map(|(i, part)| NewAttachment {
+            file_name: part
+                .attachment_name()
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("attachment-{}", i + 1)),
+            mime_type: part.content_type().map(|ct| match ct.subtype() {
+                Some(subtype) => format!("{}/{}", ct.ctype(), subtype),
+                None => ct.ctype().to_string(),
+            }),
+            content: part.contents().to_vec(),
+        })
+        .collect();
+
+    Ok(ParsedEmail {
+        message_id,
+        sender,
+        subject: message.subject().map(|s| s.trim().to_string()),
+        recipients,
+        text,
+        attachments,
+        is_auto_reply,
+        authentication_results,
+    })
+}
+
+/// Whether the MTA identified by `authserv_id` vouches for `sender`: one of
+/// its `Authentication-Results` headers (RFC 8601) has a DMARC pass, a DKIM
+/// pass or an SPF pass for the sender's domain. Headers from any
+/// other authserv-id are ignored, as anyone can add them.
+pub fn sender_authenticated(results: &[String], authserv_id: &str, sender: &str) -> bool {
+    let domain = match sender.rsplit_once('@') {
+        Some((_, domain)) if !authserv_id.is_empty() => domain.to_lowercase(),
+        _ => return false,
+    };
+
+    results.iter().any(|header| {
+        let header = strip_comments(header);
+        let mut parts = header.split(';');
+        let trusted = parts
+            .next()
+            .and_then(|id| id.split_whitespace().next())
+            .map_or(false, |id| id.eq_ignore_ascii_case(authserv_id));
+
+        trusted
+            && parts.any(|result| {
+                let mut tokens = result.split_whitespace();
+                let method = match tokens.next().and_then(|t| t.split_once('=')) {
+                    Some((method, outcome)) if outcome.eq_ignore_ascii_case("pass") => {
+                        method.to_lowercase()
+                    }
+                    _ => return false,
+                };
+
+                tokens.filter_map(|t| t.split_once('=')).any(|(property, value)| {
+                    match (method.as_str(), property.to_lowercase().as_str()) {
+                        ("dmarc", "header.from") | ("dkim", "header.d") => {
+                            value.eq_ignore_ascii_case(&domain)
+                        }
+                        ("spf", "smtp.mailfrom") => {
+                            let mailfrom = value.rsplit('@').next().unwrap_or(value);
+                            mailfrom.eq_ignore_ascii_case(&domain)
+                        }
+                        _ => false,
+                    }
+                })
+            })
+    })
+}
+
+/// Drops the parenthesised comments MTAs put in header values.
+fn strip_comments(value: &str) -> String {
+    let mut depth = 0;
+    value
+        .chars()
+        .filter(|ch| match ch {
+            '(' => {
+                depth += 1;
+                false
+            }
+            ')' if depth > 0 => {
+                depth -= 1;
+                false
+            }
+            _ => depth == 0,
+        })
+        .collect()
+}
+
+fn header_text<'a>(message: &'a mail_parser::Message<'a>, name: &'a str) -> Option<&'a str> {
+    match message.header(name)? {
+        HeaderValue::Text(text) => Some(text.trim()),
+        _ => None,
+    }
+}
+
+/// The new text of a reply: everything above the quoted message, the
+/// "On ... wrote:" line or the signature.
+pub fn strip_quoted_reply(text: &str) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut end = lines.len();
+
+    for (i, line) in lines.iter().enumerate() {
+        let trimmed = line.trim();
+        let next = lines.get(i + 1).map_or("", |l| l.trim());
+
+        let is_cut = trimmed.starts_with('>')
+            // RFC 3676 signature delimiter
+            || *line == "-- "
+            || trimmed == "--"
+            || REPLY_CUT_PREFIXES.iter().any(|p| trimmed.starts_with(p))
+            || is_attribution_line(trimmed, next)
+            || is_forwarded_header(trimmed, next);
+
+        if is_cut {
+            end = i;
+            break;
+        }
+    }
+
+    lines[..end].join("\n").trim().to_string()
+}
+
+/// `On <date>, <name> wrote:` (possibly wrapped over two lines) and its
+/// Hebrew forms, e.g. Gmail's `בתאריך ... מאת ...:`.
+fn is_attribution_line(line: &str, next: &str) -> bool {
+    let english = line.starts_with("On ")
+        && (line.ends_with("wrote:") || next.ends_with("wrote:"));
+    let hebrew = (line.starts_with("בתאריך") || line.starts_with("ב-"))
+        && (line.ends_with(':') || next.ends_with(':'))
+        && (line.contains("מאת") || line.contains("כתב") || next.contains("כתב"));
+
+    english || hebrew
+}
+
+/// Outlook's quoted header block: `From:` followed by `Sent:` or `Date:`.
+fn is_forwarded_header(line: &str, next: &str) -> bool {
+    (line.starts_with("From:") && (next.starts_with("Sent:") || next.starts_with("Date:")))
+        || (line.starts_with("מאת:") && (next.starts_with("נשלח:") || next.starts_with("תאריך:")))
+}
@@ -0,0 +1,169 @@
+use anyhow::Result;
+use askama::Template;
+use uuid::Uuid;
+
+use crate::models::{
+    Client, Invoice, InvoiceLine, InvoiceableEntry, Matter, NewInvoiceLine, DEFAULT_TIMEZONE,
+    INVOICE_ISSUED,
+};
+
+/// Groups entries into one line per matter, task and hourly rate, in the
+/// order the entries come in. Entries without a rate are billed at zero.
+pub fn build_invoice_lines(entries: &[InvoiceableEntry]) -> Vec<NewInvoiceLine> {
+    let mut keys: Vec<(&str, Option<Uuid>, i64)> = Vec::new();
+    let mut lines: Vec<NewInvoiceLine> = Vec::new();
+
+    for item in entries {
+        let entry = &item.entry;
+        let rate = entry.hourly_rate_agorot.unwrap_or(0);
+        let key = (item.matter_number.as_str(), entry.task_id, rate);
+
+        let index = match keys.iter().position(|k| *k == key) {
+            Some(index) => index,
+            None => {
+                keys.push(key);
+                lines.push(NewInvoiceLine {
+                    description: line_description(item),
+                    entries: 0,
+                    minutes: 0,
+                    hourly_rate_agorot: rate,
+                    amount_agorot: 0,
+                });
+                lines.len() - 1
+            }
+        };
+
+        let line = &mut lines[index];
+        line.entries += 1;
+        line.minutes += entry.duration_minutes.unwrap_or(0);
+        line.amount_agorot += entry.amount_agorot;
+    }
+
+    lines
+}
+
+fn line_description(item: &InvoiceableEntry) -> String {
+    match (&item.task_title, &item.task_code) {
+        (Some(title), Some(code)) => format!("תיק {} – {} ({})", item.matter_number, title, code),
+        (Some(title), None) => format!("תיק {} – {}", item.matter_number, title),
+        _ => format!("תיק {} – שירותים משפטיים", item.matter_number),
+    }
+}
+
+/// VAT on the subtotal, rounded half up to the agora.
+pub fn vat_amount(subtotal_agorot: i64, vat_rate_basis_points: i32) -> i64 {
+    (subtotal_agorot * vat_rate_basis_points as i64 + 5_000) / 10_000
+}
+
+/// `1,234.50 ₪`
+pub fn format_shekels(agorot: i64) -> String {
+    let sign = if agorot < 0 { "-" } else { "" };
+    let agorot = agorot.abs();
+    let shekels = (agorot / 100).to_string();
+
+    let mut grouped = String::with_capacity(shekels.len() + shekels.len() / 3);
+    for (i, ch) in shekels.chars().enumerate() {
+        if i > 0 && (shekels.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(ch);
+    }
+
+    format!("{}{}.{:02} ₪", sign, grouped, agorot % 100)
+}
+
+/// `3:05`
+fn format_hours(minutes: i32) -> String {
+    format!("{}:{:02}", minutes / 60, minutes % 60)
+}
+
+/// Who the invoice is from, as printed in its header.
+pub struct InvoiceIssuer<'a> {
+    pub name: &'a str,
+    pub tax_id: &'a str,
+}
+
+/// The invoice as a printable right-to-left Hebrew HTML document.
+pub fn render_invoice_html(
+    issuer: &InvoiceIssuer,
+    invoice: &Invoice,
+    lines: &[InvoiceLine],
+    client: &Client,
+    matter: Option<&Matter>,
+) -> Result<String> {
+    let is_issued = invoice.status == INVOICE_ISSUED;
+    let title = match &invoice.invoice_number {
+        Some(number) if is_issued => format!("חשבונית מס {}", number),
+        _ => "טיוטת חשבונית".to_string(),
+    };
+    let issue_date = invoice
+        .issued_at
+        .map(|at| at.with_timezone(&DEFAULT_TIMEZONE).format("%d/%m/%Y").to_string())
+        .unwrap_or_default();
+
+    let rows: Vec<InvoiceRow> = lines
+        .iter()
+        .map(|line| InvoiceRow {
+            description: &line.description,
+            hours: format_hours(line.minutes),
+            rate: format_shekels(line.hourly_rate_agorot),
+            amount: format_shekels(line.amount_agorot),
+        })
+        .collect();
+
+    let document = InvoiceHtml {
+        title: &title,
+        is_issued,
+        issue_date: &issue_date,
+        issuer,
+        client,
+        matter,
+        period: &format!(
+            "{} – {}",
+            invoice.period_from.format("%d/%m/%Y"),
+            invoice.period_to.format("%d/%m/%Y")
+        ),
+        rows: &rows,
+        subtotal: &format_shekels(invoice.subtotal_agorot),
+        vat_rate: &format_vat_rate(invoice.vat_rate_basis_points),
+        vat: &format_shekels(invoice.vat_agorot),
+        total: &format_shekels(invoice.total_agorot),
+        notes: invoice.notes.as_deref(),
+    };
+
+    Ok(document.render()?)
+}
+
+/// `18%`, `17.5%`
+fn format_vat_rate(basis_points: i32) -> String {
+    if basis_points % 100 == 0 {
+        format!("{}%", basis_points / 100)
+    } else {
+        format!("{}%", basis_points as f64 / 100.0)
+    }
+}
+
+struct InvoiceRow<'a> {
+    description: &'a str,
+    hours: String,
+    rate: String,
+    amount: String,
+}
+
+#[derive(Template)]
+#[template(path = "invoice.html")]
+struct InvoiceHtml<'a> {
+    title: &'a str,
+    is_issued: bool,
+    issue_date: &'a str,
+    issuer: &'a InvoiceIssuer<'a>,
+    client: &'a Client,
+    matter: Option<&'a Matter>,
+    period: &'a str,
+    rows: &'a [InvoiceRow<'a>],
+    subtotal: &'a str,
+    vat_rate: &'a str,
+    vat: &'a str,
+    total: &'a str,
+    notes: Option<&'a str>,
+}
@@ -0,0 +1,102 @@
+use crate::models::User;
+
+/// Extracts the `@name` tokens from a comment body.
+///
+/// A mention runs until the first character that is not a letter, digit,
+/// `.`, `_` or `-`, so `@dana.levi,` yields `dana.levi`. Trailing dots are
+/// dropped so that a mention at the end of a sentence still resolves.
+pub fn extract_mentions(body: &str) -> Vec<String> {
+    let mut mentions: Vec<String> = Vec::new();
+    let mut chars = body.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        if c != '@' {
+            continue;
+        }
+
+        // Skip e-mail addresses such as "dana@office.co.il"
+        let preceded_by_word = body[..i]
+            .chars()
+            .next_back()
+            .map_or(false, |p| p.is_alphanumeric());
+        if preceded_by_word {
+            continue;
+        }
+
+        let mut token = String::new();
+        while let Some(&(_, next)) = chars.peek() {
+            if next.is_alphanumeric() || matches!(next, '.' | '_' | '-') {
+                token.push(next);
+                chars.next();
+            } else {
+                break;
+            }
+        }
+
+        let token = token.trim_end_matches('.').to_lowercase();
+        if !token.is_empty() && !mentions.contains(&token) {
+            mentions.push(token);
+        }
+    }
+
+    mentions
+}
+
+/// Resolves `@name` mentions in a comment body against the known users.
+///
+/// A token matches a user by full name (spaces written as `_` or `.`), by
+/// first name when it is unambiguous, or by the local part of the e-mail.
+pub fn resolve_mentions<'a>(body: &str, users: &'a [User]) -> Vec<&'a User> {
+    let mut resolved: Vec<&User> = Vec::new();
+
+    for token in extract_mentions(body) {
+        let by_name: Vec<&User> = users
+            .iter()
+            .filter(|u| {
+                let name = u.name.trim().to_lowercase();
+                name.replace(' ', "_") == token
+                    || name.replace(' ', ".") == token
+                    || name.replace(' ', "-") == token
+                    || name == token
+            })
+            .collect();
+
+        let by_email: Vec<&User> = users
+            .iter()
+            .filter(|u| {
+                u.email
+                    .split('@')
+                    .next()
+                    .map_or(false, |local| local.to_lowercase() == token)
+            })
+            .collect();
+
+        let by_first_name: Vec<&User> = users
+            .iter()
+            .filter(|u| {
+                u.name
+                    .split_whitespace()
+                    .next()
+                    .map_or(false, |first| first.to_lowercase() == token)
+            })
+            .collect();
+
+        let matched = if !by_name.is_empty() {
+            by_name
+        } else if !by_email.is_empty() {
+            by_email
+        } else if by_first_name.len() == 1 {
+            by_first_name
+        } else {
+            Vec::new()
+        };
+
+        for user in matched {
+            if !resolved.iter().any(|r| r.id == user.id) {
+                resolved.push(user);
+            }
+        }
+    }
+
+    resolved
+}
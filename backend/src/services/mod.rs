@@ -1,3 +1,41 @@
 pub mod email;
+pub mod mentions;
+pub mod rrule;
+pub mod recurring;
+pub mod placeholders;
+pub mod outbox;
+pub mod notifications;
+pub mod digest;
+pub mod reminders;
+pub mod push;
+pub mod events;
+pub mod webhooks;
+pub mod inbound;
+pub mod court_calendar;
+pub mod deadlines;
+pub mod ical;
+pub mod invoices;
+pub mod conflicts;
+pub mod vcard;
+pub mod documents;
 
 pub use email::*;
+pub use mentions::*;
+pub use rrule::*;
+pub use recurring::*;
+pub use placeholders::*;
+pub use outbox::*;
+pub use notifications::*;
+pub use digest::*;
+pub use reminders::*;
+pub use push::*;
+pub use events::*;
+pub use webhooks::*;
+pub use inbound::*;
+pub use court_calendar::*;
+pub use deadlines::*;
+pub use ical::*;
+pub use invoices::*;
+pub use conflicts::*;
+pub use vcard::*;
+pub use documents::*;
@@ -0,0 +1,148 @@
+use anyhow::Result;
+use chrono::Utc;
+use sqlx::PgConnection;
+
+use crate::{
+    db,
+    models::{
+        NewEmail, NewPush, NotificationEvent, NotificationPreference, NotificationSettings,
+        Task, TaskFieldChange,
+    },
+    utils::Config,
+    AppState,
+};
+
+/// Title shown on every push notification; the body carries the subject.
+const PUSH_TITLE: &str = "משימות GH";
+
+/// Who should be emailed about an update, according to the configured
+/// field rules. The assignee hears about changes to
+/// `NOTIFY_ASSIGNEE_FIELDS` (a previous assignee is told about the
+/// reassignment too) and the creator about changes to
+/// `NOTIFY_CREATOR_FIELDS`. Whoever made the change is never emailed.
+pub fn update_notification_recipients(
+    config: &Config,
+    before: &Task,
+    after: &Task,
+    changes: &[TaskFieldChange],
+    actor_email: Option<&str>,
+) -> Vec<String> {
+    let assignee_fields = config.notify_assignee_fields_as_vec();
+    let creator_fields = config.notify_creator_fields_as_vec();
+    let touches = |fields: &[&str]| changes.iter().any(|c| fields.contains(&c.field));
+
+    let mut candidates: Vec<&str> = Vec::new();
+
+    if touches(&assignee_fields) {
+        candidates.push(&after.assigned_to_email);
+
+        let reassigned = changes.iter().any(|c| c.field == "assigned_to");
+        if reassigned && assignee_fields.contains(&"assigned_to") {
+            candidates.push(&before.assigned_to_email);
+        }
+    }
+
+    if touches(&creator_fields) {
+        candidates.push(&after.created_by_email);
+    }
+
+    let mut recipients: Vec<String> = Vec::new();
+    for email in candidates {
+        let is_actor = actor_email.map_or(false, |a| a.eq_ignore_ascii_case(email));
+        let is_duplicate = recipients.iter().any(|r| r.eq_ignore_ascii_case(email));
+
+        if !email.is_empty() && !is_actor && !is_duplicate {
+            recipients.push(email.to_string());
+        }
+    }
+
+    recipients
+}
+
+/// Which preference governs an update email to `recipient`: the new assignee
+/// of a reassigned task hears about the assignment, otherwise status changes
+/// take precedence over other edits.
+pub fn update_event_for(recipient: &str, task: &Task, changes: &[TaskFieldChange]) -> NotificationEvent {
+    let changed = |field: &str| changes.iter().any(|c| c.field == field);
+
+    if changed("assigned_to") && recipient.eq_ignore_ascii_case(&task.assigned_to_email) {
+        NotificationEvent::TaskAssigned
+    } else if changed("status") {
+        NotificationEvent::StatusChanged
+    } else {
+        NotificationEvent::TaskUpdated
+    }
+}
+
+/// Delivers a notification according to the recipient's preferences: it may
+/// be dropped, added to the in-app inbox, pushed to the user's browsers,
+/// batched for the digest, or queued for email (push and email are held back
+/// until quiet hours end). Addresses that do not belong
+/// to a user get the email right away. Pass the transaction of the change
+/// that triggered the notification.
+pub async fn dispatch_notification(
+    state: &AppState,
+    conn: &mut PgConnection,
+    event: NotificationEvent,
+    priority: &str,
+    email: NewEmail,
+) -> Result<()> {
+    let user = match db::get_user_by_email(&state.pool, &email.recipient).await? {
+        Some(user) => user,
+        None => {
+            db::enqueue_email(&mut *conn, &email).await?;
+            return Ok(());
+        }
+    };
+
+    if !user.is_active {
+        return Ok(());
+    }
+
+    let preference = db::get_notification_preference(&state.pool, user.id, event)
+        .await?
+        .unwrap_or_else(|| NotificationPreference::default_for(user.id, event));
+
+    if !preference.allows_priority(priority) {
+        return Ok(());
+    }
+
+    if preference.in_app_enabled {
+        db::create_in_app_notification(&mut *conn, user.id, event, &email.subject, email.task_id)
+            .await?;
+    }
+
+    let settings = db::get_notification_settings(&state.pool, user.id)
+        .await?
+        .unwrap_or_else(|| NotificationSettings::default_for(user.id));
+    let quiet_end = settings.quiet_hours_end_after(Utc::now());
+
+    if preference.push_enabled && state.push_service.is_enabled() {
+        let push = NewPush {
+            title: PUSH_TITLE.to_string(),
+            body: email.subject.clone(),
+            url: email.task_id.map(|id| state.email_service.task_url(id)),
+            task_id: email.task_id,
+            send_after: quiet_end,
+        };
+        db::enqueue_push(&mut *conn, user.id, &push).await?;
+    }
+
+    if !preference.email_enabled {
+        return Ok(());
+    }
+
+    if preference.digest {
+        db::add_digest_item(&mut *conn, user.id, event, &email.subject, email.task_id).await?;
+        return Ok(());
+    }
+
+    let email = NewEmail {
+        send_after: quiet_end,
+        ..email
+    };
+
+    db::enqueue_email(&mut *conn, &email).await?;
+
+    Ok(())
+}
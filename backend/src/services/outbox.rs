@@ -0,0 +1,75 @@
+use anyhow::Result;
+use std::time::Duration;
+
+use crate::{db, AppState};
+
+/// How often the worker polls the outbox for due messages.
+const OUTBOX_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Messages claimed per poll.
+const OUTBOX_BATCH_SIZE: i64 = 20;
+
+/// How long a claimed message stays reserved before another worker may retry it.
+const OUTBOX_LOCK_SECONDS: i64 = 300;
+
+/// First retry delay; doubles after every failed attempt up to the maximum.
+const RETRY_BASE_SECONDS: i64 = 30;
+const RETRY_MAX_SECONDS: i64 = 6 * 60 * 60;
+
+/// Starts the background worker that delivers queued emails.
+pub fn spawn_email_outbox_worker(state: AppState) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(OUTBOX_POLL_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            if let Err(e) = process_email_outbox(&state).await {
+                tracing::error!("Email outbox worker failed: {:?}", e);
+            }
+        }
+    });
+}
+
+/// Sends one batch of due messages, recording the outcome of each.
+pub async fn process_email_outbox(state: &AppState) -> Result<usize> {
+    let emails = db::claim_due_emails(&state.pool, OUTBOX_BATCH_SIZE, OUTBOX_LOCK_SECONDS).await?;
+    let max_attempts = state.config.email_max_attempts_as_i32();
+    let mut sent = 0;
+
+    for email in emails {
+        match state.email_service.send(&email).await {
+            Ok(response) => {
+                tracing::info!("Email sent successfully to {}", email.recipient);
+                db::mark_email_sent(&state.pool, email.id, &response).await?;
+                sent += 1;
+            }
+            Err(e) => {
+                let retry = if email.attempts >= max_attempts {
+                    tracing::error!(
+                        "Giving up on email {} to {} after {} attempts: {:?}",
+                        email.id, email.recipient, email.attempts, e
+                    );
+                    None
+                } else {
+                    tracing::warn!(
+                        "Failed to send email {} to {} (attempt {}): {:?}",
+                        email.id, email.recipient, email.attempts, e
+                    );
+                    Some(retry_delay_seconds(email.attempts))
+                };
+
+                db::mark_email_failed(&state.pool, email.id, &e.to_string(), retry).await?;
+            }
+        }
+    }
+
+    Ok(sent)
+}
+
+fn retry_delay_seconds(attempts: i32) -> i64 {
+    let exponent = attempts.saturating_sub(1).clamp(0, 20) as u32;
+    RETRY_BASE_SECONDS
+        .saturating_mul(2_i64.pow(exponent))
+        .min(RETRY_MAX_SECONDS)
+}
@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+
+/// Names of the `{placeholder}`s used in a text, in order of first use.
+/// `{{` and `}}` are literal braces.
+pub fn find_placeholders(text: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+
+    for segment in parse(text) {
+        if let Segment::Placeholder(name) = segment {
+            if !names.iter().any(|n| n == name) {
+                names.push(name.to_string());
+            }
+        }
+    }
+
+    names
+}
+
+/// Replaces every `{name}` in `text` with its value. Returns the names that
+/// have no value if any are missing.
+pub fn fill_placeholders(text: &str, values: &HashMap<String, String>) -> Result<String, Vec<String>> {
+    let mut output = String::with_capacity(text.len());
+    let mut missing: Vec<String> = Vec::new();
+
+    for segment in parse(text) {
+        match segment {
+            Segment::Text(t) => output.push_str(t),
+            Segment::Brace(c) => output.push(c),
+            Segment::Placeholder(name) => match values.get(name) {
+                Some(value) => output.push_str(value),
+                None => {
+                    if !missing.iter().any(|m| m == name) {
+                        missing.push(name.to_string());
+                    }
+                }
+            },
+        }
+    }
+
+    if missing.is_empty() {
+        Ok(output)
+    } else {
+        Err(missing)
+    }
+}
+
+enum Segment<'a> {
+    Text(&'a str),
+    Brace(char),
+    Placeholder(&'a str),
+}
+
+fn parse(text: &str) -> Vec<Segment<'_>> {
+    let mut segments = Vec::new();
+    let mut rest = text;
+
+    while let Some(pos) = rest.find(['{', '}']) {
+        if pos > 0 {
+            segments.push(Segment::Text(&rest[..pos]));
+        }
+        rest = &rest[pos..];
+
+        if rest.starts_with("{{") || rest.starts_with("}}") {
+            segments.push(Segment::Brace(rest.as_bytes()[0] as char));
+            rest = &rest[2..];
+            continue;
+        }
+
+        if rest.starts_with('{') {
+            if let Some(end) = rest.find('}') {
+                let name = rest[1..end].trim();
+                let is_name = !name.is_empty()
+                    && name.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '.');
+
+                if is_name {
+                    segments.push(Segment::Placeholder(name));
+                    rest = &rest[end + 1..];
+                    continue;
+                }
+            }
+        }
+
+        // A lone brace that does not start a placeholder is kept as text
+        segments.push(Segment::Text(&rest[..1]));
+        rest = &rest[1..];
+    }
+
+    if !rest.is_empty() {
+        segments.push(Segment::Text(rest));
+    }
+
+    segments
+}
@@ -0,0 +1,389 @@
+use aes_gcm::{
+    aead::{Aead, KeyInit},
+    Aes128Gcm, Nonce,
+};
+use anyhow::{anyhow, bail, Result};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::Utc;
+use hkdf::Hkdf;
+use p256::{
+    ecdh::EphemeralSecret,
+    ecdsa::{signature::Signer, Signature, SigningKey},
+    elliptic_curve::sec1::ToEncodedPoint,
+    PublicKey,
+};
+use rand::{rngs::OsRng, RngCore};
+use serde_json::json;
+use sha2::Sha256;
+use std::time::Duration;
+
+use crate::{
+    db,
+    models::{PushDelivery, PushPayload, PushSubscription},
+    AppState,
+};
+
+/// How often the worker polls for due push deliveries.
+const PUSH_POLL_INTERVAL: Duration = Duration::from_secs(5);
+const PUSH_BATCH_SIZE: i64 = 50;
+const PUSH_LOCK_SECONDS: i64 = 120;
+
+/// Push messages are only useful while fresh, so give up sooner than email.
+const PUSH_MAX_ATTEMPTS: i32 = 5;
+const PUSH_RETRY_BASE_SECONDS: i64 = 30;
+
+/// How long the push service keeps a message for an offline device.
+const PUSH_TTL_SECONDS: u32 = 24 * 60 * 60;
+
+/// Record size announced in the aes128gcm header; the whole message must fit
+/// in a single record.
+const RECORD_SIZE: u32 = 4096;
+
+/// Domains of the browser push services: FCM (Chrome, Edge, Opera), Mozilla
+/// autopush (Firefox), Apple (Safari) and WNS (legacy Edge). Subdomains are
+/// accepted too.
+const PUSH_SERVICE_DOMAINS: &[&str] = &[
+    "fcm.googleapis.com",
+    "android.googleapis.com",
+    "push.services.mozilla.com",
+    "push.apple.com",
+    "notify.windows.com",
+];
+
+/// Result of handing a message to a browser's push service.
+#[derive(Debug)]
+pub enum PushOutcome {
+    Delivered,
+    /// The subscription expired or was revoked (404/410) and should be pruned
+    Gone,
+    /// Temporary failure (network, 429, 5xx); worth retrying
+    Retry(String),
+    /// Permanent failure for this message (e.g. 400, 403, 413)
+    Rejected(String),
+}
+
+/// Sends Web Push messages (RFC 8030) with aes128gcm payload encryption
+/// (RFC 8291) and VAPID authentication (RFC 8292). Disabled when no VAPID
+/// keys are configured.
+#[derive(Clone)]
+pub struct PushService {
+    client: reqwest::Client,
+    vapid: Option<VapidKeys>,
+}
+
+#[derive(Clone)]
+struct VapidKeys {
+    signing_key: SigningKey,
+    /// Uncompressed public key, base64url
+    public_key: String,
+    /// `mailto:` or `https:` contact for the push service operator
+    subject: String,
+}
+
+impl PushService {
+    /// `private_key` is the raw 32-byte P-256 scalar and `public_key` the
+    /// uncompressed point, both base64url, as produced by `web-push generate-vapid-keys`.
+    pub fn new(public_key: &str, private_key: &str, subject: String) -> Result<Self> {
+        // Push services answer directly; a redirect could lead anywhere
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .redirect(reqwest::redirect::Policy::none())
+            .build()?;
+
+        if public_key.is_empty() || private_key.is_empty() {
+            return Ok(Self { client, vapid: None });
+        }
+
+        let secret = decode_base64url(private_key)?;
+        let signing_key = SigningKey::from_slice(&secret)
+            .map_err(|_| anyhow!("VAPID_PRIVATE_KEY is not a valid P-256 private key"))?;
+        let derived = URL_SAFE_NO_PAD.encode(
+            signing_key
+                .verifying_key()
+                .to_encoded_point(false)
+                .as_bytes(),
+        );
+
+        if derived != public_key.trim_end_matches('=') {
+            bail!("VAPID_PUBLIC_KEY does not match VAPID_PRIVATE_KEY");
+        }
+
+        Ok(Self {
+            client,
+            vapid: Some(VapidKeys {
+                signing_key,
+                public_key: derived,
+                subject,
+            }),
+        })
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.vapid.is_some()
+    }
+
+    /// Key the browser needs as `applicationServerKey` when subscribing.
+    pub fn public_key(&self) -> Option<&str> {
+        self.vapid.as_ref().map(|v| v.public_key.as_str())
+    }
+
+    pub async fn send(&self, subscription: &PushSubscription, payload: &[u8]) -> Result<PushOutcome> {
+        let vapid = self
+            .vapid
+            .as_ref()
+            .ok_or_else(|| anyhow!("Web Push is not configured"))?;
+
+        let body = encrypt_payload(
+            &decode_base64url(&subscription.p256dh)?,
+            &decode_base64url(&subscription.auth)?,
+            payload,
+        )?;
+        // Subscriptions stored before endpoints were checked
+        if let Err(e) = validate_push_endpoint(&subscription.endpoint) {
+            return Ok(PushOutcome::Rejected(e.to_string()));
+        }
+
+        let authorization = vapid_authorization(vapid, &subscription.endpoint)?;
+
+        let response = self
+            .client
+            .post(&subscription.endpoint)
+            .header("Content-Encoding", "aes128gcm")
+            .header("Content-Type", "application/octet-stream")
+            .header("TTL", PUSH_TTL_SECONDS.to_string())
+            .header("Authorization", authorization)
+            .body(body)
+            .send()
+            .await;
+
+        let response = match response {
+            Ok(response) => response,
+            Err(e) => return Ok(PushOutcome::Retry(e.to_string())),
+        };
+
+        let status = response.status().as_u16();
+        let outcome = match status {
+            200..=299 => PushOutcome::Delivered,
+            404 | 410 => PushOutcome::Gone,
+            429 | 500..=599 => {
+                PushOutcome::Retry(format!("{} {}", status, response.text().await.unwrap_or_default()))
+            }
+            _ => PushOutcome::Rejected(format!(
+                "{} {}",
+                status,
+                response.text().await.unwrap_or_default()
+            )),
+        };
+
+        Ok(outcome)
+    }
+}
+
+/// Validates a subscription's keys before it is stored.
+pub fn validate_push_keys(p256dh: &str, auth: &str) -> Result<()> {
+    let public_key = decode_base64url(p256dh)?;
+    PublicKey::from_sec1_bytes(&public_key).map_err(|_| anyhow!("p256dh is not a P-256 public key"))?;
+
+    if decode_base64url(auth)?.len() != 16 {
+        bail!("auth must be 16 bytes");
+    }
+
+    Ok(())
+}
+
+/// Endpoints must be HTTPS URLs of a known browser push service; anything
+/// else would have the push worker POST to addresses of the caller's choosing.
+pub fn validate_push_endpoint(endpoint: &str) -> Result<()> {
+    let url = reqwest::Url::parse(endpoint)?;
+    if url.scheme() != "https" {
+        bail!("endpoint must use https");
+    }
+
+    let host = url
+        .host_str()
+        .ok_or_else(|| anyhow!("endpoint has no host"))?
+        .trim_end_matches('.')
+        .to_ascii_lowercase();
+    let is_push_service = PUSH_SERVICE_DOMAINS
+        .iter()
+        .any(|domain| host == *domain || host.ends_with(&format!(".{}", domain)));
+    if !is_push_service {
+        bail!("{} is not a known push service", host);
+    }
+
+    Ok(())
+}
+
+/// Encrypts `plaintext` for the subscriber as a single aes128gcm record
+/// (RFC 8188) keyed per RFC 8291.
+fn encrypt_payload(ua_public: &[u8], auth_secret: &[u8], plaintext: &[u8]) -> Result<Vec<u8>> {
+    // 16 bytes of AEAD tag and 1 padding delimiter must fit in the record
+    if plaintext.len() > RECORD_SIZE as usize - 17 {
+        bail!("Push payload of {} bytes is too large", plaintext.len());
+    }
+
+    let ua_public_key = PublicKey::from_sec1_bytes(ua_public)
+        .map_err(|_| anyhow!("Subscription p256dh is not a P-256 public key"))?;
+
+    // Fresh application server key pair for every message
+    let as_secret = EphemeralSecret::random(&mut OsRng);
+    let as_public = as_secret.public_key().to_encoded_point(false);
+    let shared = as_secret.diffie_hellman(&ua_public_key);
+
+    // IKM = HKDF(auth_secret, ecdh_secret, "WebPush: info" || 0 || ua_public || as_public)
+    let mut key_info = b"WebPush: info\0".to_vec();
+    key_info.extend_from_slice(ua_public);
+    key_info.extend_from_slice(as_public.as_bytes());
+    let mut ikm = [0u8; 32];
+    Hkdf::<Sha256>::new(Some(auth_secret), shared.raw_secret_bytes())
+        .expand(&key_info, &mut ikm)
+        .map_err(|_| anyhow!("HKDF expand failed"))?;
+
+    let mut salt = [0u8; 16];
+    OsRng.fill_bytes(&mut salt);
+    let hkdf = Hkdf::<Sha256>::new(Some(&salt), &ikm);
+
+    let mut cek = [0u8; 16];
+    hkdf.expand(b"Content-Encoding: aes128gcm\0", &mut cek)
+        .map_err(|_| anyhow!("HKDF expand failed"))?;
+    let mut nonce = [0u8; 12];
+    hkdf.expand(b"Content-Encoding: nonce\0", &mut nonce)
+        .map_err(|_| anyhow!("HKDF expand failed"))?;
+
+    // A single record, so it ends with the last-record delimiter and no padding
+    let mut record = plaintext.to_vec();
+    record.push(0x02);
+
+    let cipher = Aes128Gcm::new_from_slice(&cek).map_err(|_| anyhow!("Invalid content key"))?;
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), record.as_slice())
+        .map_err(|_| anyhow!("Payload encryption failed"))?;
+
+    // Header: salt || record size || key id length || key id (as_public)
+    let mut body = Vec::with_capacity(16 + 4 + 1 + as_public.len() + ciphertext.len());
+    body.extend_from_slice(&salt);
+    body.extend_from_slice(&RECORD_SIZE.to_be_bytes());
+    body.push(as_public.len() as u8);
+    body.extend_from_slice(as_public.as_bytes());
+    body.extend_from_slice(&ciphertext);
+
+    Ok(body)
+}
+
+/// `Authorization: vapid t=<jwt>, k=<public key>` for the endpoint's origin.
+fn vapid_authorization(vapid: &VapidKeys, endpoint: &str) -> Result<String> {
+    let url = reqwest::Url::parse(endpoint)?;
+    let audience = url.origin().ascii_serialization();
+    let expires = (Utc::now() + chrono::Duration::hours(12)).timestamp();
+
+    let header = URL_SAFE_NO_PAD.encode(br#"{"typ":"JWT","alg":"ES256"}"#);
+    let claims = URL_SAFE_NO_PAD.encode(serde_json::to_vec(&json!({
+        "aud": audience,
+        "exp": expires,
+        "sub": vapid.subject,
+    }))?);
+    let signing_input = format!("{}.{}", header, claims);
+
+    // ES256 signatures in JWTs are the raw 64-byte r || s
+    let signature: Signature = vapid.signing_key.sign(signing_input.as_bytes());
+    let token = format!("{}.{}", signing_input, URL_SAFE_NO_PAD.encode(signature.to_bytes()));
+
+    Ok(format!("vapid t={}, k={}", token, vapid.public_key))
+}
+
+/// Browsers hand out keys as unpadded base64url, but accept padded and
+/// standard-alphabet variants too.
+fn decode_base64url(value: &str) -> Result<Vec<u8>> {
+    let normalized: String = value
+        .trim()
+        .trim_end_matches('=')
+        .chars()
+        .map(|c| match c {
+            '+' => '-',
+            '/' => '_',
+            c => c,
+        })
+        .collect();
+
+    Ok(URL_SAFE_NO_PAD.decode(normalized)?)
+}
+
+/// Starts the background worker that delivers queued push messages.
+pub fn spawn_push_worker(state: AppState) {
+    if !state.push_service.is_enabled() {
+        tracing::info!("🔕 Web Push disabled (no VAPID keys configured)");
+        return;
+    }
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(PUSH_POLL_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            if let Err(e) = process_push_deliveries(&state).await {
+                tracing::error!("Push worker failed: {:?}", e);
+            }
+        }
+    });
+}
+
+/// Sends one batch of due push messages, pruning subscriptions that are gone.
+pub async fn process_push_deliveries(state: &AppState) -> Result<usize> {
+    let deliveries = db::claim_due_push_deliveries(&state.pool, PUSH_BATCH_SIZE, PUSH_LOCK_SECONDS).await?;
+    let mut sent = 0;
+
+    for delivery in deliveries {
+        match deliver(state, &delivery).await {
+            Ok(true) => sent += 1,
+            Ok(false) => {}
+            Err(e) => {
+                tracing::warn!("Failed to send push {}: {:?}", delivery.id, e);
+                db::mark_push_failed(&state.pool, delivery.id, &e.to_string(), None).await?;
+            }
+        }
+    }
+
+    Ok(sent)
+}
+
+async fn deliver(state: &AppState, delivery: &PushDelivery) -> Result<bool> {
+    let subscription = match db::get_push_subscription_by_id(&state.pool, delivery.subscription_id).await? {
+        Some(subscription) => subscription,
+        None => return Ok(false),
+    };
+
+    let payload = serde_json::to_vec(&PushPayload {
+        title: &delivery.title,
+        body: &delivery.body,
+        icon: "/images/icon-192.png",
+        badge: "/images/icon-192.png",
+        url: delivery.url.as_deref().unwrap_or("/"),
+        dir: "rtl",
+        lang: "he",
+    })?;
+
+    match state.push_service.send(&subscription, &payload).await? {
+        PushOutcome::Delivered => {
+            db::mark_push_sent(&state.pool, delivery).await?;
+            Ok(true)
+        }
+        PushOutcome::Gone => {
+            tracing::info!("Pruning expired push subscription {}", subscription.id);
+            db::prune_push_subscription(&state.pool, subscription.id).await?;
+            Ok(false)
+        }
+        PushOutcome::Retry(error) => {
+            let retry = (delivery.attempts < PUSH_MAX_ATTEMPTS).then(|| {
+                PUSH_RETRY_BASE_SECONDS * 2_i64.pow(delivery.attempts.clamp(1, 10) as u32 - 1)
+            });
+            db::mark_push_failed(&state.pool, delivery.id, &error, retry).await?;
+            Ok(false)
+        }
+        PushOutcome::Rejected(error) => {
+            tracing::warn!("Push {} rejected: {}", delivery.id, error);
+            db::mark_push_failed(&state.pool, delivery.id, &error, None).await?;
+            Ok(false)
+        }
+    }
+}
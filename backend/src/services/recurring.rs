@@ -0,0 +1,146 @@
+use anyhow::Result;
+use chrono::{Duration, NaiveDate, Utc};
+
+use crate::{
+    db,
+    models::{
+        NewTaskEvent, NotificationEvent, RecurringTaskSeries, Task, TaskEventType, DEFAULT_TIMEZONE,
+    },
+    services::dispatch_notification,
+    AppState,
+};
+
+/// How often the scheduler looks for occurrences that are due.
+const RECURRING_TASKS_INTERVAL: std::time::Duration = std::time::Duration::from_secs(15 * 60);
+
+/// Unique index that makes each occurrence of a series a single task.
+const SERIES_OCCURRENCE_INDEX: &str = "idx_tasks_series_occurrence";
+
+/// Unique constraint on the human-readable task id.
+const TASK_ID_CONSTRAINT: &str = "tasks_task_id_key";
+
+/// How many more task ids to try after a collision.
+const TASK_ID_ATTEMPTS: u32 = 3;
+
+/// Starts the background job that turns recurring series into concrete tasks.
+pub fn spawn_recurring_tasks_job(state: AppState) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(RECURRING_TASKS_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            match materialize_recurring_tasks(&state).await {
+                Ok(0) => {}
+                Ok(count) => tracing::info!("🔁 Created {} recurring task occurrence(s)", count),
+                Err(e) => tracing::error!("Recurring task scheduler failed: {:?}", e),
+            }
+        }
+    });
+}
+
+/// Creates every occurrence that has come within its series' lead time.
+pub async fn materialize_recurring_tasks(state: &AppState) -> Result<usize> {
+    let today = Utc::now().with_timezone(&DEFAULT_TIMEZONE).date_naive();
+    let mut created = 0;
+
+    for series in db::get_due_recurring_series(&state.pool, today).await? {
+        match materialize_series(state, &series, today).await {
+            Ok(count) => created += count,
+            Err(e) => tracing::error!("Failed to materialize series {}: {:?}", series.id, e),
+        }
+    }
+
+    Ok(created)
+}
+
+/// Walks a series forward from its next occurrence, creating tasks until the
+/// next occurrence is outside the lead window. Safe to run concurrently from
+/// several instances: the series only advances if nobody else moved it first,
+/// and the unique (series_id, occurrence_date) index rejects duplicates.
+pub async fn materialize_series(
+    state: &AppState,
+    series: &RecurringTaskSeries,
+    today: NaiveDate,
+) -> Result<usize> {
+    let rule = series.rule()?;
+    let lead = Duration::days(series.lead_days as i64);
+    let mut created = 0;
+    let mut current = series.next_occurrence;
+
+    while let Some(date) = current {
+        if date - lead > today {
+            break;
+        }
+
+        let task = create_occurrence(state, series, date).await?;
+        let next = rule.next_after(series.start_date, date);
+
+        if !db::advance_recurring_series(&state.pool, series.id, date, next, task.is_some()).await? {
+            break;
+        }
+
+        if task.is_some() {
+            created += 1;
+        }
+
+        current = next;
+    }
+
+    Ok(created)
+}
+
+/// Creates the task for one occurrence, or returns `None` if it already exists.
+pub async fn create_occurrence(
+    state: &AppState,
+    series: &RecurringTaskSeries,
+    date: NaiveDate,
+) -> Result<Option<Task>> {
+    if db::get_series_occurrence_task(&state.pool, series.id, date)
+        .await?
+        .is_some()
+    {
+        return Ok(None);
+    }
+
+    let req = series.task_request(date);
+
+    // Task ids only have a random suffix per second, so a backfill creating
+    // many occurrences at once may hit a taken one; retry with a fresh id
+    let mut attempts = 0;
+    let (task, mut tx) = loop {
+        let task_id = req.generate_task_id();
+
+        // Create the task and queue its notification together
+        let mut tx = state.pool.begin().await?;
+
+        match db::create_task(&mut *tx, &req, &task_id).await {
+            Ok(task) => break (task, tx),
+            Err(e) if violates(&e, SERIES_OCCURRENCE_INDEX) => return Ok(None),
+            Err(e) if violates(&e, TASK_ID_CONSTRAINT) && attempts < TASK_ID_ATTEMPTS => {
+                attempts += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    };
+
+    db::record_task_event(&mut *tx, &NewTaskEvent::for_task(TaskEventType::TaskCreated, &task))
+        .await?;
+    let email = state.email_service.build_task_notification(&task)?;
+    dispatch_notification(state, &mut tx, NotificationEvent::TaskCreated, &task.priority, email)
+        .await?;
+
+    tx.commit().await?;
+
+    Ok(Some(task))
+}
+
+/// Whether `err` is a unique violation of the given index or constraint.
+fn violates(err: &anyhow::Error, constraint: &str) -> bool {
+    match err.downcast_ref::<sqlx::Error>() {
+        Some(sqlx::Error::Database(db_err)) => {
+            db_err.code().as_deref() == Some("23505") && db_err.constraint() == Some(constraint)
+        }
+        _ => false,
+    }
+}
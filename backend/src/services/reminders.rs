@@ -0,0 +1,225 @@
+use anyhow::Result;
+use chrono::{Duration, NaiveDate, Timelike, Utc};
+
+use crate::{
+    db,
+    models::{NewEmail, NotificationEvent, Task, UpcomingExpiry, DEFAULT_TIMEZONE},
+    services::dispatch_notification,
+    AppState,
+};
+
+/// How often the reminder job looks at upcoming and overdue tasks.
+const REMINDER_INTERVAL: std::time::Duration = std::time::Duration::from_secs(15 * 60);
+
+/// Starts the background job that reminds assignees of approaching due dates,
+/// escalates overdue tasks and reminds lawyers of their matters' limitation
+/// and renewal dates.
+pub fn spawn_task_reminder_job(state: AppState) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(REMINDER_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            match process_task_reminders(&state).await {
+                Ok(0) => {}
+                Ok(count) => tracing::info!("⏰ Queued {} due-date reminder(s)", count),
+                Err(e) => tracing::error!("Task reminder job failed: {:?}", e),
+            }
+
+            match process_expiry_reminders(&state).await {
+                Ok(0) => {}
+                Ok(count) => tracing::info!("⚖️ Queued {} expiry reminder(s)", count),
+                Err(e) => tracing::error!("Expiry reminder job failed: {:?}", e),
+            }
+        }
+    });
+}
+
+/// Queues every reminder and escalation that is due and has not been sent.
+/// Nothing goes out before the configured reminder hour, office time, so
+/// same-day reminders arrive in the morning.
+pub async fn process_task_reminders(state: &AppState) -> Result<usize> {
+    let now = Utc::now().with_timezone(&DEFAULT_TIMEZONE);
+    if now.hour() < state.config.reminder_hour_as_u32() {
+        return Ok(0);
+    }
+
+    let today = now.date_naive();
+    let offsets = state.config.reminder_offsets_as_vec();
+    let horizon = today + Duration::days(offsets.first().copied().unwrap_or(0));
+    let mut queued = 0;
+
+    for task in db::get_open_tasks_due_by(&state.pool, horizon).await? {
+        match remind_task(state, &task, today, &offsets).await {
+            Ok(count) => queued += count,
+            Err(e) => tracing::error!("Failed to process reminders for task {}: {:?}", task.id, e),
+        }
+    }
+
+    Ok(queued)
+}
+
+async fn remind_task(state: &AppState, task: &Task, today: NaiveDate, offsets: &[i64]) -> Result<usize> {
+    let due = match task.due_date {
+        Some(due) => due,
+        None => return Ok(0),
+    };
+    let days_left = (due - today).num_days();
+    let mut queued = 0;
+
+    if days_left >= 0 {
+        // Only the closest offset that has been reached, so a job that was
+        // down for a while does not send a burst of stale reminders
+        if let Some(offset) = offsets.iter().rev().find(|offset| days_left <= **offset) {
+            let email = state.email_service.build_due_reminder(task, days_left)?;
+            if send_reminder(state, task, due, &format!("before_{}d", offset), email, false).await? {
+                queued += 1;
+            }
+        }
+
+        return Ok(queued);
+    }
+
+    // Overdue: first the creator, later an admin
+    let days_overdue = -days_left;
+
+    if days_overdue >= state.config.escalate_creator_after_days_as_i64() {
+        let email = state
+            .email_service
+            .build_overdue_escalation(task, days_overdue, &task.created_by_email)?;
+        if send_reminder(state, task, due, "overdue_creator", email, true).await? {
+            queued += 1;
+        }
+    }
+
+    if days_overdue >= state.config.escalate_admin_after_days_as_i64() {
+        let email = state
+            .email_service
+            .build_overdue_escalation(task, days_overdue, &state.config.admin_email)?;
+        if send_reminder(state, task, due, "overdue_admin", email, true).await? {
+            queued += 1;
+        }
+    }
+
+    Ok(queued)
+}
+
+/// Records the reminder and queues its email in one transaction, so each
+/// reminder goes out at most once. Reminders respect the recipient's
+/// notification preferences; escalations always go out.
+async fn send_reminder(
+    state: &AppState,
+    task: &Task,
+    due: NaiveDate,
+    reminder_key: &str,
+    email: NewEmail,
+    escalation: bool,
+) -> Result<bool> {
+    let mut tx = state.pool.begin().await?;
+
+    if !db::record_task_reminder(&mut *tx, task.id, due, reminder_key, &email.recipient).await? {
+        return Ok(false);
+    }
+
+    if escalation {
+        db::enqueue_email(&mut *tx, &email).await?;
+    } else {
+        dispatch_notification(state, &mut tx, NotificationEvent::DueSoon, &task.priority, email)
+            .await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(true)
+}
+
+/// Queues every reminder of an unresolved matter expiry that is due. The
+/// matter's lawyer (or the admin, if the matter has none) is reminded at each
+/// configured lead time; once the date passes, both are told. These always
+/// go out, whatever the recipient's notification preferences.
+pub async fn process_expiry_reminders(state: &AppState) -> Result<usize> {
+    let now = Utc::now().with_timezone(&DEFAULT_TIMEZONE);
+    if now.hour() < state.config.reminder_hour_as_u32() {
+        return Ok(0);
+    }
+
+    let today = now.date_naive();
+    let offsets = state.config.expiry_reminder_offsets_as_vec();
+    let horizon = today + Duration::days(offsets.first().copied().unwrap_or(0));
+    let mut queued = 0;
+
+    for expiry in db::get_upcoming_expiries(&state.pool, today, horizon, None, None).await? {
+        match remind_expiry(state, &expiry, &offsets).await {
+            Ok(count) => queued += count,
+            Err(e) => tracing::error!("Failed to process reminders for expiry {}: {:?}", expiry.expiry.id, e),
+        }
+    }
+
+    Ok(queued)
+}
+
+async fn remind_expiry(state: &AppState, expiry: &UpcomingExpiry, offsets: &[i64]) -> Result<usize> {
+    let lawyer_email = expiry
+        .lawyer_email
+        .clone()
+        .unwrap_or_else(|| state.config.admin_email.clone());
+    let days_left = expiry.days_left as i64;
+
+    if days_left >= 0 {
+        // As with tasks, only the closest lead time that has been reached
+        if let Some(offset) = offsets.iter().rev().find(|offset| days_left <= **offset) {
+            let email = state.email_service.build_expiry_reminder(expiry, &lawyer_email)?;
+            if send_expiry_reminder(state, expiry, &format!("before_{}d", offset), email).await? {
+                return Ok(1);
+            }
+        }
+
+        return Ok(0);
+    }
+
+    let mut queued = 0;
+
+    let email = state.email_service.build_expiry_reminder(expiry, &lawyer_email)?;
+    if send_expiry_reminder(state, expiry, "expired", email).await? {
+        queued += 1;
+    }
+
+    if lawyer_email != state.config.admin_email {
+        let email = state
+            .email_service
+            .build_expiry_reminder(expiry, &state.config.admin_email)?;
+        if send_expiry_reminder(state, expiry, "expired_admin", email).await? {
+            queued += 1;
+        }
+    }
+
+    Ok(queued)
+}
+
+async fn send_expiry_reminder(
+    state: &AppState,
+    expiry: &UpcomingExpiry,
+    reminder_key: &str,
+    email: NewEmail,
+) -> Result<bool> {
+    let mut tx = state.pool.begin().await?;
+
+    if !db::record_expiry_reminder(
+        &mut *tx,
+        expiry.expiry.id,
+        expiry.expiry.expires_on,
+        reminder_key,
+        &email.recipient,
+    )
+    .await?
+    {
+        return Ok(false);
+    }
+
+    db::enqueue_email(&mut *tx, &email).await?;
+
+    tx.commit().await?;
+
+    Ok(true)
+}
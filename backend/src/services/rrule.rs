@@ -0,0 +1,479 @@
+use anyhow::{anyhow, bail, Result};
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use std::collections::VecDeque;
+
+/// Upper bound on the number of periods scanned when looking for the next
+/// occurrence, so that a rule which never matches cannot loop forever.
+const MAX_PERIODS: u32 = 10_000;
+
+/// Largest accepted `INTERVAL`; anything beyond would only reach dates
+/// centuries away.
+const MAX_INTERVAL: u32 = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// A `BYDAY` entry such as `MO`, `1MO` (first Monday) or `-1FR` (last Friday).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByDay {
+    pub ordinal: Option<i32>,
+    pub weekday: Weekday,
+}
+
+/// The supported subset of an RFC 5545 RRULE: `FREQ`, `INTERVAL`, `BYDAY`,
+/// `BYMONTHDAY`, `COUNT` and `UNTIL`, evaluated on whole dates.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecurrenceRule {
+    pub freq: Frequency,
+    pub interval: u32,
+    pub by_day: Vec<ByDay>,
+    pub by_month_day: Vec<i32>,
+    pub count: Option<u32>,
+    pub until: Option<NaiveDate>,
+}
+
+impl RecurrenceRule {
+    /// Parses a rule such as `FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH;COUNT=10`.
+    /// A leading `RRULE:` is accepted.
+    pub fn parse(input: &str) -> Result<Self> {
+        let input = input.trim();
+        let input = input.strip_prefix("RRULE:").unwrap_or(input);
+
+        let mut freq = None;
+        let mut interval = 1;
+        let mut by_day = Vec::new();
+        let mut by_month_day = Vec::new();
+        let mut count = None;
+        let mut until = None;
+
+        for part in input.split(';').filter(|p| !p.is_empty()) {
+            let (key, value) = part
+                .split_once('=')
+                .ok_or_else(|| anyhow!("Invalid RRULE part: {}", part))?;
+
+            match key.to_ascii_uppercase().as_str() {
+                "FREQ" => {
+                    freq = Some(match value.to_ascii_uppercase().as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        other => bail!("Unsupported FREQ: {}", other),
+                    })
+                }
+                "INTERVAL" => {
+                    interval = value
+                        .parse::<u32>()
+                        .ok()
+                        .filter(|i| (1..=MAX_INTERVAL).contains(i))
+                        .ok_or_else(|| anyhow!("Invalid INTERVAL: {}", value))?;
+                }
+                "BYDAY" => {
+                    by_day = value
+                        .split(',')
+                        .map(parse_by_day)
+                        .collect::<Result<Vec<_>>>()?;
+                }
+                "BYMONTHDAY" => {
+                    by_month_day = value
+                        .split(',')
+                        .map(|d| {
+                            d.parse::<i32>()
+                                .ok()
+                                .filter(|d| *d != 0 && (-31..=31).contains(d))
+                                .ok_or_else(|| anyhow!("Invalid BYMONTHDAY: {}", d))
+                        })
+                        .collect::<Result<Vec<_>>>()?;
+                }
+                "COUNT" => {
+                    count = Some(
+                        value
+                            .parse::<u32>()
+                            .ok()
+                            .filter(|c| *c > 0)
+                            .ok_or_else(|| anyhow!("Invalid COUNT: {}", value))?,
+                    );
+                }
+                "UNTIL" => {
+                    let date = value.get(..8).unwrap_or(value);
+                    until = Some(
+                        NaiveDate::parse_from_str(date, "%Y%m%d")
+                            .map_err(|_| anyhow!("Invalid UNTIL: {}", value))?,
+                    );
+                }
+                "WKST" if value.eq_ignore_ascii_case("MO") => {}
+                other => bail!("Unsupported RRULE part: {}", other),
+            }
+        }
+
+        let freq = freq.ok_or_else(|| anyhow!("RRULE must specify FREQ"))?;
+
+        if count.is_some() && until.is_some() {
+            bail!("RRULE cannot specify both COUNT and UNTIL");
+        }
+
+        let has_ordinals = by_day.iter().any(|d| d.ordinal.is_some());
+        if has_ordinals && freq != Frequency::Monthly {
+            bail!("Numbered BYDAY values are only supported with FREQ=MONTHLY");
+        }
+        if !by_day.is_empty() && freq == Frequency::Yearly {
+            bail!("BYDAY is not supported with FREQ=YEARLY");
+        }
+        if !by_month_day.is_empty() && !matches!(freq, Frequency::Monthly | Frequency::Daily) {
+            bail!("BYMONTHDAY is only supported with FREQ=MONTHLY or FREQ=DAILY");
+        }
+
+        Ok(Self {
+            freq,
+            interval,
+            by_day,
+            by_month_day,
+            count,
+            until,
+        })
+    }
+
+    /// All occurrences of the rule, in order, starting from `dtstart`.
+    pub fn occurrences(&self, dtstart: NaiveDate) -> Occurrences<'_> {
+        Occurrences {
+            rule: self,
+            dtstart,
+            period: 0,
+            buffer: VecDeque::new(),
+            emitted: 0,
+            done: false,
+        }
+    }
+
+    /// The first occurrence strictly after `after`, if the series has one.
+    pub fn next_after(&self, dtstart: NaiveDate, after: NaiveDate) -> Option<NaiveDate> {
+        self.occurrences(dtstart).find(|d| *d > after)
+    }
+
+    /// The first occurrence on or after `from`, if the series has one.
+    pub fn next_on_or_after(&self, dtstart: NaiveDate, from: NaiveDate) -> Option<NaiveDate> {
+        self.occurrences(dtstart).find(|d| *d >= from)
+    }
+
+    /// Candidate dates for the `period`-th interval, unsorted and unfiltered
+    /// by `dtstart`, or `None` once the period is past the last representable
+    /// date.
+    fn period_dates(&self, dtstart: NaiveDate, period: u32) -> Option<Vec<NaiveDate>> {
+        let step = period.checked_mul(self.interval)?;
+
+        let dates = match self.freq {
+            Frequency::Daily => {
+                let date = dtstart.checked_add_signed(Duration::days(step as i64))?;
+                let weekday_ok = self.by_day.is_empty()
+                    || self.by_day.iter().any(|d| d.weekday == date.weekday());
+                let month_day_ok = self.by_month_day.is_empty()
+                    || self.by_month_day.iter().any(|d| month_day_matches(date, *d));
+
+                if weekday_ok && month_day_ok {
+                    vec![date]
+                } else {
+                    Vec::new()
+                }
+            }
+            Frequency::Weekly => {
+                let week_start = dtstart
+                    .checked_sub_signed(Duration::days(dtstart.weekday().num_days_from_monday() as i64))?
+                    .checked_add_signed(Duration::weeks(step as i64))?;
+                let day_in_week = |weekday: Weekday| {
+                    week_start.checked_add_signed(Duration::days(weekday.num_days_from_monday() as i64))
+                };
+
+                if self.by_day.is_empty() {
+                    vec![day_in_week(dtstart.weekday())?]
+                } else {
+                    self.by_day
+                        .iter()
+                        .map(|d| day_in_week(d.weekday))
+                        .collect::<Option<Vec<_>>>()?
+                }
+            }
+            Frequency::Monthly => {
+                let (year, month) = add_months(dtstart.year(), dtstart.month(), step)?;
+                NaiveDate::from_ymd_opt(year, month, 1)?;
+
+                if self.by_day.is_empty() && self.by_month_day.is_empty() {
+                    return Some(
+                        NaiveDate::from_ymd_opt(year, month, dtstart.day())
+                            .into_iter()
+                            .collect(),
+                    );
+                }
+
+                let by_month_day: Vec<NaiveDate> = self
+                    .by_month_day
+                    .iter()
+                    .filter_map(|d| resolve_month_day(year, month, *d))
+                    .collect();
+                let by_day: Vec<NaiveDate> = self
+                    .by_day
+                    .iter()
+                    .flat_map(|d| weekdays_in_month(year, month, *d))
+                    .collect();
+
+                if self.by_day.is_empty() {
+                    by_month_day
+                } else if self.by_month_day.is_empty() {
+                    by_day
+                } else {
+                    // Both given: the day must satisfy both (RFC 5545 limit semantics)
+                    by_day
+                        .into_iter()
+                        .filter(|d| by_month_day.contains(d))
+                        .collect()
+                }
+            }
+            Frequency::Yearly => {
+                let year = dtstart.year().checked_add(i32::try_from(step).ok()?)?;
+                NaiveDate::from_ymd_opt(year, 1, 1)?;
+
+                NaiveDate::from_ymd_opt(year, dtstart.month(), dtstart.day())
+                    .into_iter()
+                    .collect()
+            }
+        };
+
+        Some(dates)
+    }
+}
+
+/// Iterator over the occurrences of a [`RecurrenceRule`].
+pub struct Occurrences<'a> {
+    rule: &'a RecurrenceRule,
+    dtstart: NaiveDate,
+    period: u32,
+    buffer: VecDeque<NaiveDate>,
+    emitted: u32,
+    done: bool,
+}
+
+impl Iterator for Occurrences<'_> {
+    type Item = NaiveDate;
+
+    fn next(&mut self) -> Option<NaiveDate> {
+        loop {
+            if self.done {
+                return None;
+            }
+
+            if let Some(date) = self.buffer.pop_front() {
+                if self.rule.until.map_or(false, |until| date > until)
+                    || self.rule.count.map_or(false, |count| self.emitted >= count)
+                {
+                    self.done = true;
+                    return None;
+                }
+
+                self.emitted += 1;
+                return Some(date);
+            }
+
+            if self.period >= MAX_PERIODS {
+                self.done = true;
+                return None;
+            }
+
+            let Some(dates) = self.rule.period_dates(self.dtstart, self.period) else {
+                self.done = true;
+                return None;
+            };
+            let mut dates: Vec<NaiveDate> =
+                dates.into_iter().filter(|d| *d >= self.dtstart).collect();
+            dates.sort();
+            dates.dedup();
+
+            self.buffer.extend(dates);
+            self.period += 1;
+        }
+    }
+}
+
+fn parse_by_day(value: &str) -> Result<ByDay> {
+    let value = value.trim().to_ascii_uppercase();
+    if value.len() < 2 {
+        bail!("Invalid BYDAY: {}", value);
+    }
+
+    let (ordinal, day) = value.split_at(value.len() - 2);
+    let weekday = match day {
+        "MO" => Weekday::Mon,
+        "TU" => Weekday::Tue,
+        "WE" => Weekday::Wed,
+        "TH" => Weekday::Thu,
+        "FR" => Weekday::Fri,
+        "SA" => Weekday::Sat,
+        "SU" => Weekday::Sun,
+        _ => bail!("Invalid BYDAY: {}", value),
+    };
+
+    let ordinal = if ordinal.is_empty() {
+        None
+    } else {
+        Some(
+            ordinal
+                .trim_start_matches('+')
+                .parse::<i32>()
+                .ok()
+                .filter(|n| *n != 0 && (-5..=5).contains(n))
+                .ok_or_else(|| anyhow!("Invalid BYDAY: {}", value))?,
+        )
+    };
+
+    Ok(ByDay { ordinal, weekday })
+}
+
+/// `None` if the resulting year does not fit.
+fn add_months(year: i32, month: u32, months: u32) -> Option<(i32, u32)> {
+    let index = year as i64 * 12 + (month as i64 - 1) + months as i64;
+    Some((i32::try_from(index.div_euclid(12)).ok()?, index.rem_euclid(12) as u32 + 1))
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    add_months(year, month, 1)
+        .and_then(|(next_year, next_month)| NaiveDate::from_ymd_opt(next_year, next_month, 1))
+        .and_then(|d| d.pred_opt())
+        .map_or(31, |d| d.day())
+}
+
+/// Resolves a `BYMONTHDAY` value, where negative values count from the end
+/// of the month (`-1` is the last day).
+fn resolve_month_day(year: i32, month: u32, day: i32) -> Option<NaiveDate> {
+    let last = days_in_month(year, month) as i32;
+    let day = if day < 0 { last + day + 1 } else { day };
+
+    if day < 1 || day > last {
+        return None;
+    }
+
+    NaiveDate::from_ymd_opt(year, month, day as u32)
+}
+
+fn month_day_matches(date: NaiveDate, day: i32) -> bool {
+    resolve_month_day(date.year(), date.month(), day) == Some(date)
+}
+
+/// Dates in the month matching a `BYDAY` entry: every such weekday, or only
+/// the n-th (from the end when negative).
+fn weekdays_in_month(year: i32, month: u32, by_day: ByDay) -> Vec<NaiveDate> {
+    let all: Vec<NaiveDate> = (1..=days_in_month(year, month))
+        .filter_map(|d| NaiveDate::from_ymd_opt(year, month, d))
+        .filter(|d| d.weekday() == by_day.weekday)
+        .collect();
+
+    match by_day.ordinal {
+        None => all,
+        Some(n) if n > 0 => all.get(n as usize - 1).copied().into_iter().collect(),
+        Some(n) => all
+            .len()
+            .checked_sub(n.unsigned_abs() as usize)
+            .and_then(|i| all.get(i))
+            .copied()
+            .into_iter()
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn first(rule: &str, dtstart: NaiveDate, n: usize) -> Vec<NaiveDate> {
+        RecurrenceRule::parse(rule).unwrap().occurrences(dtstart).take(n).collect()
+    }
+
+    #[test]
+    fn weekly_by_day() {
+        // Wednesday 1 January 2025
+        assert_eq!(
+            first("FREQ=WEEKLY;BYDAY=MO,TH", date(2025, 1, 1), 4),
+            vec![date(2025, 1, 2), date(2025, 1, 6), date(2025, 1, 9), date(2025, 1, 13)]
+        );
+        assert_eq!(
+            first("RRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=MO", date(2025, 1, 6), 3),
+            vec![date(2025, 1, 6), date(2025, 1, 20), date(2025, 2, 3)]
+        );
+    }
+
+    #[test]
+    fn monthly_numbered_by_day() {
+        assert_eq!(
+            first("FREQ=MONTHLY;BYDAY=1SU", date(2025, 1, 1), 3),
+            vec![date(2025, 1, 5), date(2025, 2, 2), date(2025, 3, 2)]
+        );
+        assert_eq!(
+            first("FREQ=MONTHLY;BYDAY=-1FR", date(2025, 1, 1), 3),
+            vec![date(2025, 1, 31), date(2025, 2, 28), date(2025, 3, 28)]
+        );
+    }
+
+    #[test]
+    fn last_day_of_month() {
+        assert_eq!(
+            first("FREQ=MONTHLY;BYMONTHDAY=-1", date(2024, 1, 15), 4),
+            vec![date(2024, 1, 31), date(2024, 2, 29), date(2024, 3, 31), date(2024, 4, 30)]
+        );
+    }
+
+    #[test]
+    fn month_day_missing_from_month_is_skipped() {
+        assert_eq!(
+            first("FREQ=MONTHLY", date(2025, 1, 31), 3),
+            vec![date(2025, 1, 31), date(2025, 3, 31), date(2025, 5, 31)]
+        );
+    }
+
+    #[test]
+    fn count_and_until() {
+        let rule = RecurrenceRule::parse("FREQ=DAILY;COUNT=3").unwrap();
+        assert_eq!(rule.occurrences(date(2025, 1, 1)).count(), 3);
+
+        let rule = RecurrenceRule::parse("FREQ=WEEKLY;UNTIL=20250115T000000Z").unwrap();
+        assert_eq!(
+            rule.occurrences(date(2025, 1, 1)).collect::<Vec<_>>(),
+            vec![date(2025, 1, 1), date(2025, 1, 8), date(2025, 1, 15)]
+        );
+        assert_eq!(rule.next_after(date(2025, 1, 1), date(2025, 1, 15)), None);
+
+        assert!(RecurrenceRule::parse("FREQ=DAILY;COUNT=3;UNTIL=20250115").is_err());
+    }
+
+    #[test]
+    fn interval_is_capped() {
+        assert!(RecurrenceRule::parse("FREQ=DAILY;INTERVAL=0").is_err());
+        assert!(RecurrenceRule::parse("FREQ=DAILY;INTERVAL=1001").is_err());
+        assert!(RecurrenceRule::parse("FREQ=DAILY;INTERVAL=4294967295").is_err());
+        assert!(RecurrenceRule::parse("FREQ=DAILY;INTERVAL=1000").is_ok());
+    }
+
+    #[test]
+    fn iteration_ends_at_the_last_representable_date() {
+        for rule in ["FREQ=DAILY", "FREQ=WEEKLY;BYDAY=MO,FR", "FREQ=MONTHLY", "FREQ=YEARLY"] {
+            let rule = format!("{};INTERVAL=1000", rule);
+            let rule = RecurrenceRule::parse(&rule).unwrap();
+            assert!(rule.occurrences(date(2025, 1, 1)).count() > 0);
+            assert!(rule.occurrences(NaiveDate::MAX).count() <= 1);
+        }
+    }
+
+    #[test]
+    fn rejects_unsupported_rules() {
+        assert!(RecurrenceRule::parse("INTERVAL=2").is_err());
+        assert!(RecurrenceRule::parse("FREQ=HOURLY").is_err());
+        assert!(RecurrenceRule::parse("FREQ=WEEKLY;BYDAY=1MO").is_err());
+        assert!(RecurrenceRule::parse("FREQ=YEARLY;BYDAY=MO").is_err());
+        assert!(RecurrenceRule::parse("FREQ=MONTHLY;BYMONTHDAY=32").is_err());
+    }
+}
@@ -0,0 +1,357 @@
+use mail_parser::decoders::{charsets::map::charset_decoder, quoted_printable::quoted_printable_decode};
+
+use crate::{
+    models::{Contact, ContactPoint, CreateContactRequest},
+    services::ical::{escape_text, fold_line},
+};
+
+/// Parameter types that say how to reach someone rather than which number
+/// or address it is, so they are not taken as labels.
+const IGNORED_TYPES: &[&str] = &["internet", "pref", "voice", "x400", "msg"];
+
+/// Cards for the contacts, as one vCard 3.0 file (RFC 2426).
+pub fn render_vcards(contacts: &[Contact]) -> String {
+    contacts.iter().map(render_vcard).collect()
+}
+
+fn render_vcard(contact: &Contact) -> String {
+    let mut lines: Vec<String> = vec![
+        "BEGIN:VCARD".to_string(),
+        "VERSION:3.0".to_string(),
+        format!("UID:urn:uuid:{}", contact.id),
+        format!("FN:{}", escape_text(&contact.name)),
+    ];
+
+    if contact.contact_type == "organization" {
+        lines.push("N:;;;;".to_string());
+        lines.push(format!("ORG:{}", escape_text(&contact.name)));
+        lines.push("X-ABSHOWAS:COMPANY".to_string());
+    } else {
+        // Hebrew and English names alike are written given name first
+        let (given, family) = match contact.name.trim().rsplit_once(' ') {
+            Some((given, family)) => (given, family),
+            None => ("", contact.name.trim()),
+        };
+        lines.push(format!("N:{};{};;;", escape_text(family), escape_text(given)));
+        if let Some(organization) = &contact.organization {
+            lines.push(format!("ORG:{}", escape_text(organization)));
+        }
+    }
+
+    if let Some(job_title) = &contact.job_title {
+        lines.push(format!("TITLE:{}", escape_text(job_title)));
+    }
+    for phone in contact.phones.iter() {
+        lines.push(format!("TEL{}:{}", type_param(&phone.label, None), escape_text(&phone.value)));
+    }
+    for email in contact.emails.iter() {
+        lines.push(format!(
+            "EMAIL{}:{}",
+            type_param(&email.label, Some("INTERNET")),
+            escape_text(&email.value)
+        ));
+    }
+    for address in contact.addresses.iter() {
+        lines.push(format!("ADR{}:;;{};;;;", type_param(&address.label, None), escape_text(&address.value)));
+    }
+    if !contact.roles.is_empty() {
+        let roles: Vec<String> = contact.roles.iter().map(|r| escape_text(r)).collect();
+        lines.push(format!("CATEGORIES:{}", roles.join(",")));
+    }
+    if let Some(id_number) = &contact.id_number {
+        lines.push(format!("X-ID-NUMBER:{}", escape_text(id_number)));
+    }
+    if let Some(notes) = contact.notes.as_deref().filter(|n| !n.is_empty()) {
+        lines.push(format!("NOTE:{}", escape_text(notes)));
+    }
+    lines.push(format!("REV:{}", contact.updated_at.format("%Y%m%dT%H%M%SZ")));
+    lines.push("END:VCARD".to_string());
+
+    lines.iter().map(|line| fold_line(line)).collect()
+}
+
+fn type_param(label: &Option<String>, base: Option<&str>) -> String {
+    let label = label
+        .as_deref()
+        .map(|l| l.replace('"', ""))
+        .filter(|l| !l.trim().is_empty());
+
+    match (base, label) {
+        (Some(base), Some(label)) => format!(";TYPE={},\"{}\"", base, label),
+        (Some(base), None) => format!(";TYPE={}", base),
+        (None, Some(label)) => format!(";TYPE=\"{}\"", label),
+        (None, None) => String::new(),
+    }
+}
+
+/// Reads every card in a vCard file (versions 2.1 to 4.0), each as a
+/// contact to create or the reason it could not be read. Quoted-printable
+/// values, as in 2.1 exports from Android and older Outlook, are decoded
+/// using their `CHARSET` (UTF-8 if not given).
+pub fn parse_vcards(text: &str) -> Vec<Result<CreateContactRequest, String>> {
+    let mut cards = Vec::new();
+    let mut current: Option<Vec<(String, Vec<String>, String)>> = None;
+    let mut unreadable: Option<String> = None;
+
+    for line in unfold(text) {
+        let (name, params, value) = match split_content_line(&line) {
+            Some(parts) => parts,
+            None => continue,
+        };
+
+        match name.as_str() {
+            "BEGIN" if value.eq_ignore_ascii_case("VCARD") => {
+                current = Some(Vec::new());
+                unreadable = None;
+            }
+            "END" if value.eq_ignore_ascii_case("VCARD") => {
+                if let Some(properties) = current.take() {
+                    let number = cards.len() + 1;
+                    cards.push(match unreadable.take() {
+                        Some(property) => {
+                            Err(format!("Card {} has an unreadable {} value", number, property))
+                        }
+                        None => card_to_contact(number, &properties),
+                    });
+                }
+            }
+            _ => {
+                if let Some(properties) = current.as_mut() {
+                    match decode_value(&params, value) {
+                        Some(value) => properties.push((name, params, value)),
+                        None => {
+                            unreadable.get_or_insert(name);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    cards
+}
+
+/// Decodes a quoted-printable value to text; other values are returned as
+/// they are. `None` if it is not valid quoted-printable or the charset is
+/// unknown.
+fn decode_value(params: &[String], value: String) -> Option<String> {
+    if !is_quoted_printable(params) {
+        return Some(value);
+    }
+
+    let charset = params.iter().find_map(|param| {
+        let (key, value) = param.split_once('=')?;
+        key.trim().eq_ignore_ascii_case("CHARSET").then(|| value.trim_matches('"').trim())
+    });
+    let bytes = quoted_printable_decode(value.as_bytes())?;
+
+    // The charset decoders cover everything except UTF-8 itself
+    match charset.filter(|c| !c.eq_ignore_ascii_case("UTF-8") && !c.eq_ignore_ascii_case("US-ASCII")) {
+        Some(charset) => charset_decoder(charset.as_bytes()).map(|decoder| decoder(&bytes)),
+        None => Some(String::from_utf8_lossy(&bytes).into_owned()),
+    }
+}
+
+/// `ENCODING=QUOTED-PRINTABLE`, or the bare `QUOTED-PRINTABLE` of vCard 2.1.
+fn is_quoted_printable(params: &[String]) -> bool {
+    params.iter().any(|param| {
+        let value = match param.split_once('=') {
+            Some((key, value)) if key.trim().eq_ignore_ascii_case("ENCODING") => value,
+            Some(_) => return false,
+            None => param.as_str(),
+        };
+        value.trim().eq_ignore_ascii_case("QUOTED-PRINTABLE")
+    })
+}
+
+fn card_to_contact(
+    number: usize,
+    properties: &[(String, Vec<String>, String)],
+) -> Result<CreateContactRequest, String> {
+    let mut full_name: Option<String> = None;
+    let mut structured_name: Option<String> = None;
+    let mut organization: Option<String> = None;
+    let mut is_organization = false;
+    let mut contact = CreateContactRequest {
+        contact_type: None,
+        name: String::new(),
+        organization: None,
+        job_title: None,
+        id_number: None,
+        roles: Vec::new(),
+        phones: Vec::new(),
+        emails: Vec::new(),
+        addresses: Vec::new(),
+        notes: None,
+    };
+
+    for (name, params, value) in properties {
+        match name.as_str() {
+            "FN" => full_name = Some(unescape(value)).filter(|v| !v.trim().is_empty()),
+            "N" => {
+                let parts = split_unescaped(value, ';');
+                let family = parts.first().cloned().unwrap_or_default();
+                let given = parts.get(1).cloned().unwrap_or_default();
+                let name = format!("{} {}", given, family).trim().to_string();
+                structured_name = Some(name).filter(|n| !n.is_empty());
+            }
+            "ORG" => {
+                organization = split_unescaped(value, ';')
+                    .into_iter()
+                    .next()
+                    .filter(|o| !o.trim().is_empty());
+            }
+            "KIND" => is_organization = value.eq_ignore_ascii_case("org"),
+            "X-ABSHOWAS" => is_organization = value.eq_ignore_ascii_case("company"),
+            "TITLE" => contact.job_title = Some(unescape(value)),
+            "TEL" => contact.phones.push(contact_point(params, unescape(value))),
+            "EMAIL" => contact.emails.push(contact_point(params, unescape(value))),
+            "ADR" => {
+                let address = split_unescaped(value, ';')
+                    .into_iter()
+                    .map(|part| part.trim().to_string())
+                    .filter(|part| !part.is_empty())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                if !address.is_empty() {
+                    contact.addresses.push(contact_point(params, address));
+                }
+            }
+            "CATEGORIES" => contact.roles.extend(
+                split_unescaped(value, ',')
+                    .into_iter()
+                    .map(|role| role.trim().to_string())
+                    .filter(|role| !role.is_empty()),
+            ),
+            "X-ID-NUMBER" => contact.id_number = Some(unescape(value)),
+            "NOTE" => contact.notes = Some(unescape(value)),
+            _ => {}
+        }
+    }
+
+    if structured_name.is_none() && organization.is_some() && full_name == organization {
+        is_organization = true;
+    }
+
+    contact.name = match full_name.or(structured_name) {
+        Some(name) => name,
+        None if is_organization || organization.is_some() => {
+            is_organization = true;
+            organization.clone().unwrap_or_default()
+        }
+        None => return Err(format!("Card {} has no name", number)),
+    };
+    contact.contact_type = Some(if is_organization { "organization" } else { "person" }.to_string());
+    if !is_organization {
+        contact.organization = organization;
+    }
+
+    Ok(contact)
+}
+
+fn contact_point(params: &[String], value: String) -> ContactPoint {
+    let label = params
+        .iter()
+        .filter_map(|param| {
+            let (key, values) = param.split_once('=')?;
+            key.eq_ignore_ascii_case("TYPE").then_some(values)
+        })
+        .flat_map(|values| values.split(','))
+        .map(|v| v.trim_matches('"').trim().to_lowercase())
+        .find(|v| !v.is_empty() && !IGNORED_TYPES.contains(&v.as_str()));
+
+    ContactPoint { label, value: value.trim().to_string() }
+}
+
+/// Joins folded lines back together. A quoted-printable value ending in `=`
+/// goes on unindented on the next line; the soft line break is kept for the
+/// decoder.
+fn unfold(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut soft_break = false;
+    for line in text.split('\n') {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        match line.strip_prefix(' ').or_else(|| line.strip_prefix('\t')) {
+            _ if soft_break => {
+                let last = lines.last_mut().unwrap();
+                last.push('\n');
+                last.push_str(line);
+            }
+            Some(rest) if !lines.is_empty() => lines.last_mut().unwrap().push_str(rest),
+            _ => lines.push(line.to_string()),
+        }
+
+        soft_break = lines.last().map_or(false, |last| {
+            last.ends_with('=')
+                && split_content_line(last).map_or(false, |(_, params, _)| is_quoted_printable(&params))
+        });
+    }
+    lines
+}
+
+/// `item1.TEL;TYPE=CELL:050-1234567` → (`TEL`, [`TYPE=CELL`], `050-1234567`)
+fn split_content_line(line: &str) -> Option<(String, Vec<String>, String)> {
+    let mut in_quotes = false;
+    let mut colon = None;
+    for (i, ch) in line.char_indices() {
+        match ch {
+            '"' => in_quotes = !in_quotes,
+            ':' if !in_quotes => {
+                colon = Some(i);
+                break;
+            }
+            _ => {}
+        }
+    }
+    let colon = colon?;
+
+    let mut head = line[..colon].split(';');
+    let name = head.next()?;
+    let name = name.rsplit('.').next().unwrap_or(name).trim().to_uppercase();
+    let params = head.map(str::to_string).collect();
+
+    Some((name, params, line[colon + 1..].to_string()))
+}
+
+/// Splits on `separator` where it is not escaped, unescaping each part.
+fn split_unescaped(value: &str, separator: char) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut chars = value.chars();
+
+    while let Some(ch) = chars.next() {
+        if ch == '\\' {
+            current.push(ch);
+            if let Some(next) = chars.next() {
+                current.push(next);
+            }
+        } else if ch == separator {
+            parts.push(unescape(&current));
+            current.clear();
+        } else {
+            current.push(ch);
+        }
+    }
+    parts.push(unescape(&current));
+
+    parts
+}
+
+fn unescape(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            unescaped.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => unescaped.push('\n'),
+            Some(other) => unescaped.push(other),
+            None => {}
+        }
+    }
+
+    unescaped
+}
@@ -0,0 +1,234 @@
+use anyhow::Result;
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use serde_json::json;
+use sha2::Sha256;
+use std::time::Duration;
+
+use crate::{
+    db,
+    models::{WebhookDelivery, WebhookSubscription},
+    AppState,
+};
+
+/// How often the worker polls for due deliveries.
+const WEBHOOK_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Deliveries claimed per poll.
+const WEBHOOK_BATCH_SIZE: i64 = 20;
+
+/// How long a claimed delivery stays reserved before another worker may retry it.
+pub const WEBHOOK_LOCK_SECONDS: i64 = 120;
+
+/// Receivers must answer within this time.
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// First retry delay; doubles after every failed attempt up to the maximum.
+const RETRY_BASE_SECONDS: i64 = 30;
+const RETRY_MAX_SECONDS: i64 = 6 * 60 * 60;
+
+/// Characters of the receiver's response kept in the delivery log.
+const RESPONSE_BODY_LIMIT: usize = 2000;
+
+/// Signs and posts webhook payloads.
+#[derive(Clone)]
+pub struct WebhookService {
+    client: reqwest::Client,
+}
+
+/// What the receiver made of one attempt.
+#[derive(Debug)]
+pub struct WebhookResponse {
+    pub status: Option<u16>,
+    pub body: Option<String>,
+    pub error: Option<String>,
+}
+
+impl WebhookResponse {
+    pub fn is_success(&self) -> bool {
+        self.status.map_or(false, |s| (200..300).contains(&s))
+    }
+}
+
+impl WebhookService {
+    pub fn new() -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(WEBHOOK_TIMEOUT)
+            .user_agent("LawOffice-Webhooks/1.0")
+            .build()?;
+
+        Ok(Self { client })
+    }
+
+    /// Posts the delivery's envelope, signed with the subscription's secret.
+    ///
+    /// Receivers verify `X-Webhook-Signature: sha256=<hex>`, the HMAC-SHA256
+    /// of `<X-Webhook-Timestamp>.<raw body>`, and should reject stale
+    /// timestamps to prevent replays.
+    pub async fn send(&self, subscription: &WebhookSubscription, delivery: &WebhookDelivery) -> Result<WebhookResponse> {
+        let body = serde_json::to_vec(&json!({
+            "id": delivery.id,
+            "event": delivery.event_type,
+            "event_id": delivery.event_id,
+            "created_at": delivery.created_at,
+            "data": delivery.payload,
+        }))?;
+        let timestamp = Utc::now().timestamp().to_string();
+        let signature = sign_webhook(&subscription.secret, &timestamp, &body);
+
+        let response = self
+            .client
+            .post(&subscription.url)
+            .header("Content-Type", "application/json")
+            .header("X-Webhook-Id", delivery.id.to_string())
+            .header("X-Webhook-Event", &delivery.event_type)
+            .header("X-Webhook-Timestamp", &timestamp)
+            .header("X-Webhook-Signature", format!("sha256={}", signature))
+            .body(body)
+            .send()
+            .await;
+
+        let response = match response {
+            Ok(response) => response,
+            Err(e) => {
+                return Ok(WebhookResponse {
+                    status: None,
+                    body: None,
+                    error: Some(e.to_string()),
+                })
+            }
+        };
+
+        let status = response.status();
+        let body: String = response
+            .text()
+            .await
+            .unwrap_or_default()
+            .chars()
+            .take(RESPONSE_BODY_LIMIT)
+            .collect();
+        let error = (!status.is_success()).then(|| format!("Receiver answered {}", status));
+
+        Ok(WebhookResponse {
+            status: Some(status.as_u16()),
+            body: (!body.is_empty()).then_some(body),
+            error,
+        })
+    }
+}
+
+/// Hex HMAC-SHA256 of `<timestamp>.<body>`.
+pub fn sign_webhook(secret: &str, timestamp: &str, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(timestamp.as_bytes());
+    mac.update(b".");
+    mac.update(body);
+
+    mac.finalize()
+        .into_bytes()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+pub fn generate_webhook_secret() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    format!("whsec_{}", hex)
+}
+
+/// Starts the background worker that delivers queued webhook events.
+pub fn spawn_webhook_worker(state: AppState) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(WEBHOOK_POLL_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            if let Err(e) = process_webhook_deliveries(&state).await {
+                tracing::error!("Webhook worker failed: {:?}", e);
+            }
+        }
+    });
+}
+
+/// Sends one batch of due deliveries, recording the outcome of each.
+pub async fn process_webhook_deliveries(state: &AppState) -> Result<usize> {
+    let deliveries =
+        db::claim_due_webhook_deliveries(&state.pool, WEBHOOK_BATCH_SIZE, WEBHOOK_LOCK_SECONDS).await?;
+    let mut delivered = 0;
+
+    for delivery in deliveries {
+        let subscription = match db::get_webhook_subscription_by_id(&state.pool, delivery.subscription_id).await? {
+            Some(subscription) => subscription,
+            None => continue,
+        };
+
+        if attempt_webhook_delivery(state, &subscription, &delivery).await?.status == "delivered" {
+            delivered += 1;
+        }
+    }
+
+    Ok(delivered)
+}
+
+/// Makes one attempt at a claimed delivery and records it in the log. A
+/// subscription that keeps failing is disabled.
+pub async fn attempt_webhook_delivery(
+    state: &AppState,
+    subscription: &WebhookSubscription,
+    delivery: &WebhookDelivery,
+) -> Result<WebhookDelivery> {
+    let response = state.webhook_service.send(subscription, delivery).await?;
+    let status = response.status.map(i32::from);
+
+    if response.is_success() {
+        return db::mark_webhook_delivered(&state.pool, delivery, status.unwrap_or(200), response.body.as_deref())
+            .await;
+    }
+
+    let error = response.error.unwrap_or_else(|| "Unknown error".to_string());
+    let retry = if delivery.attempts >= state.config.webhook_max_attempts_as_i32() {
+        tracing::error!(
+            "Giving up on webhook delivery {} to {} after {} attempts: {}",
+            delivery.id, subscription.url, delivery.attempts, error
+        );
+        None
+    } else {
+        tracing::warn!(
+            "Webhook delivery {} to {} failed (attempt {}): {}",
+            delivery.id, subscription.url, delivery.attempts, error
+        );
+        Some(retry_delay_seconds(delivery.attempts))
+    };
+
+    let (failed, failures) = db::mark_webhook_failed(
+        &state.pool,
+        delivery,
+        status,
+        response.body.as_deref(),
+        &error,
+        retry,
+    )
+    .await?;
+
+    let limit = state.config.webhook_disable_after_failures_as_i32();
+    if failures >= limit {
+        let reason = format!("{} consecutive failed deliveries, last: {}", failures, error);
+        tracing::error!("Disabling webhook {} ({}): {}", subscription.id, subscription.url, reason);
+        db::disable_webhook_subscription(&state.pool, subscription.id, &reason).await?;
+    }
+
+    Ok(failed)
+}
+
+fn retry_delay_seconds(attempts: i32) -> i64 {
+    let exponent = attempts.saturating_sub(1).clamp(0, 20) as u32;
+    RETRY_BASE_SECONDS
+        .saturating_mul(2_i64.pow(exponent))
+        .min(RETRY_MAX_SECONDS)
+}
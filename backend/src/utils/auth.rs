@@ -0,0 +1,61 @@
+use axum::{async_trait, extract::FromRequestParts, http::{header::AUTHORIZATION, request::Parts}};
+use uuid::Uuid;
+
+use crate::{
+    utils::{extract_token_from_header, verify_jwt, AppError, Claims},
+    AppState,
+};
+
+/// The caller identified by the `Authorization: Bearer <jwt>` header.
+#[derive(Debug, Clone)]
+pub struct AuthUser {
+    pub id: Uuid,
+    pub email: String,
+    pub role: String,
+}
+
+impl AuthUser {
+    pub fn is_admin(&self) -> bool {
+        self.role == "admin"
+    }
+
+    pub fn require_admin(&self) -> Result<(), AppError> {
+        if !self.is_admin() {
+            return Err(AppError::Forbidden("Admin access required".to_string()));
+        }
+
+        Ok(())
+    }
+}
+
+impl TryFrom<Claims> for AuthUser {
+    type Error = AppError;
+
+    fn try_from(claims: Claims) -> Result<Self, Self::Error> {
+        let id = Uuid::parse_str(&claims.sub)
+            .map_err(|_| AppError::Unauthorized("Invalid token subject".to_string()))?;
+
+        Ok(Self {
+            id,
+            email: claims.email,
+            role: claims.role,
+        })
+    }
+}
+
+#[async_trait]
+impl FromRequestParts<AppState> for AuthUser {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, Self::Rejection> {
+        let header = parts
+            .headers
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok());
+
+        let token = extract_token_from_header(header)?;
+        let claims = verify_jwt(&token, &state.config.jwt_secret)?;
+
+        AuthUser::try_from(claims)
+    }
+}
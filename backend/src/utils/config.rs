@@ -1,5 +1,12 @@
 use serde::Deserialize;
 
+/// Task fields whose changes are emailed to the assignee.
+pub const DEFAULT_NOTIFY_ASSIGNEE_FIELDS: &str =
+    "title,description,category,assigned_to,due_date,priority,status";
+
+/// Task fields whose changes are emailed to the task's creator.
+pub const DEFAULT_NOTIFY_CREATOR_FIELDS: &str = "assigned_to,due_date,status";
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct Config {
     pub smtp_username: String,
@@ -8,8 +15,30 @@ pub struct Config {
     pub smtp_port: String,
     pub jwt_secret: String,
     pub api_base_url: String,
+    pub frontend_base_url: String,
     pub admin_email: String,
     pub rate_limit_requests_per_minute: String,
+    pub email_max_attempts: String,
+    pub notify_assignee_fields: String,
+    pub notify_creator_fields: String,
+    pub reminder_offsets_days: String,
+    pub reminder_hour: String,
+    pub escalate_creator_after_days: String,
+    pub escalate_admin_after_days: String,
+    pub vapid_public_key: String,
+    pub vapid_private_key: String,
+    pub vapid_subject: String,
+    pub webhook_max_attempts: String,
+    pub webhook_disable_after_failures: String,
+    pub inbound_email_secret: String,
+    pub inbound_email_domain: String,
+    pub inbound_intake_address: String,
+    pub inbound_intake_category: String,
+    pub inbound_authserv_id: String,
+    pub vat_rate_percent: String,
+    pub invoice_issuer_name: String,
+    pub invoice_issuer_tax_id: String,
+    pub expiry_reminder_offsets_days: String,
 }
 
 impl Config {
@@ -27,10 +56,54 @@ impl Config {
                 .unwrap_or_else(|_| "dev-secret".to_string()),
             api_base_url: std::env::var("API_BASE_URL")
                 .unwrap_or_else(|_| "http://localhost:8000".to_string()),
+            frontend_base_url: std::env::var("FRONTEND_BASE_URL")
+                .unwrap_or_else(|_| "http://localhost:8080".to_string()),
             admin_email: std::env::var("ADMIN_EMAIL")
                 .unwrap_or_else(|_| "admin@localhost".to_string()),
             rate_limit_requests_per_minute: std::env::var("RATE_LIMIT_REQUESTS_PER_MINUTE")
                 .unwrap_or_else(|_| "60".to_string()),
+            email_max_attempts: std::env::var("EMAIL_MAX_ATTEMPTS")
+                .unwrap_or_else(|_| "8".to_string()),
+            notify_assignee_fields: std::env::var("NOTIFY_ASSIGNEE_FIELDS")
+                .unwrap_or_else(|_| DEFAULT_NOTIFY_ASSIGNEE_FIELDS.to_string()),
+            notify_creator_fields: std::env::var("NOTIFY_CREATOR_FIELDS")
+                .unwrap_or_else(|_| DEFAULT_NOTIFY_CREATOR_FIELDS.to_string()),
+            reminder_offsets_days: std::env::var("REMINDER_OFFSETS_DAYS")
+                .unwrap_or_else(|_| "7,2,0".to_string()),
+            reminder_hour: std::env::var("REMINDER_HOUR")
+                .unwrap_or_else(|_| "8".to_string()),
+            escalate_creator_after_days: std::env::var("ESCALATE_CREATOR_AFTER_DAYS")
+                .unwrap_or_else(|_| "1".to_string()),
+            escalate_admin_after_days: std::env::var("ESCALATE_ADMIN_AFTER_DAYS")
+                .unwrap_or_else(|_| "3".to_string()),
+            vapid_public_key: std::env::var("VAPID_PUBLIC_KEY")
+                .unwrap_or_else(|_| "".to_string()),
+            vapid_private_key: std::env::var("VAPID_PRIVATE_KEY")
+                .unwrap_or_else(|_| "".to_string()),
+            vapid_subject: std::env::var("VAPID_SUBJECT")
+                .unwrap_or_else(|_| "".to_string()),
+            webhook_max_attempts: std::env::var("WEBHOOK_MAX_ATTEMPTS")
+                .unwrap_or_else(|_| "8".to_string()),
+            webhook_disable_after_failures: std::env::var("WEBHOOK_DISABLE_AFTER_FAILURES")
+                .unwrap_or_else(|_| "25".to_string()),
+            inbound_email_secret: std::env::var("INBOUND_EMAIL_SECRET")
+                .unwrap_or_else(|_| "".to_string()),
+            inbound_email_domain: std::env::var("INBOUND_EMAIL_DOMAIN")
+                .unwrap_or_else(|_| "".to_string()),
+            inbound_intake_address: std::env::var("INBOUND_INTAKE_ADDRESS")
+                .unwrap_or_else(|_| "".to_string()),
+            inbound_intake_category: std::env::var("INBOUND_INTAKE_CATEGORY")
+                .unwrap_or_else(|_| "מינהלית".to_string()),
+            inbound_authserv_id: std::env::var("INBOUND_AUTHSERV_ID")
+                .unwrap_or_else(|_| "".to_string()),
+            vat_rate_percent: std::env::var("VAT_RATE_PERCENT")
+                .unwrap_or_else(|_| "18".to_string()),
+            invoice_issuer_name: std::env::var("INVOICE_ISSUER_NAME")
+                .unwrap_or_else(|_| "".to_string()),
+            invoice_issuer_tax_id: std::env::var("INVOICE_ISSUER_TAX_ID")
+                .unwrap_or_else(|_| "".to_string()),
+            expiry_reminder_offsets_days: std::env::var("EXPIRY_REMINDER_OFFSETS_DAYS")
+                .unwrap_or_else(|_| "90,30,14,7,1".to_string()),
         }
     }
 
@@ -41,4 +114,84 @@ impl Config {
     pub fn rate_limit_as_u32(&self) -> u32 {
         self.rate_limit_requests_per_minute.parse().unwrap_or(60)
     }
+
+    pub fn email_max_attempts_as_i32(&self) -> i32 {
+        self.email_max_attempts.parse().unwrap_or(8)
+    }
+
+    pub fn notify_assignee_fields_as_vec(&self) -> Vec<&str> {
+        split_list(&self.notify_assignee_fields)
+    }
+
+    pub fn notify_creator_fields_as_vec(&self) -> Vec<&str> {
+        split_list(&self.notify_creator_fields)
+    }
+
+    /// Days before the due date to remind the assignee, largest first.
+    pub fn reminder_offsets_as_vec(&self) -> Vec<i64> {
+        split_offsets(&self.reminder_offsets_days)
+    }
+
+    pub fn reminder_hour_as_u32(&self) -> u32 {
+        self.reminder_hour.parse().unwrap_or(8).min(23)
+    }
+
+    pub fn escalate_creator_after_days_as_i64(&self) -> i64 {
+        self.escalate_creator_after_days.parse().unwrap_or(1)
+    }
+
+    pub fn escalate_admin_after_days_as_i64(&self) -> i64 {
+        self.escalate_admin_after_days.parse().unwrap_or(3)
+    }
+
+    pub fn webhook_max_attempts_as_i32(&self) -> i32 {
+        self.webhook_max_attempts.parse().unwrap_or(8)
+    }
+
+    /// Consecutive failed attempts, across all deliveries, that disable a webhook.
+    pub fn webhook_disable_after_failures_as_i32(&self) -> i32 {
+        self.webhook_disable_after_failures.parse().unwrap_or(25).max(1)
+    }
+
+    /// VAT charged on invoices, in basis points (18% is 1800).
+    pub fn vat_rate_basis_points(&self) -> i32 {
+        self.vat_rate_percent
+            .parse::<f64>()
+            .map(|percent| (percent * 100.0).round() as i32)
+            .unwrap_or(1800)
+            .clamp(0, 10000)
+    }
+
+    /// Days before a matter's expiry date to remind its lawyer, largest first.
+    pub fn expiry_reminder_offsets_as_vec(&self) -> Vec<i64> {
+        split_offsets(&self.expiry_reminder_offsets_days)
+    }
+
+    /// Contact sent to push services with every VAPID token; defaults to the admin.
+    pub fn vapid_subject_or_default(&self) -> String {
+        if self.vapid_subject.is_empty() {
+            format!("mailto:{}", self.admin_email)
+        } else {
+            self.vapid_subject.clone()
+        }
+    }
+}
+
+fn split_list(value: &str) -> Vec<&str> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+fn split_offsets(value: &str) -> Vec<i64> {
+    let mut offsets: Vec<i64> = split_list(value)
+        .into_iter()
+        .filter_map(|d| d.parse().ok())
+        .filter(|d| *d >= 0)
+        .collect();
+    offsets.sort_unstable_by(|a, b| b.cmp(a));
+    offsets.dedup();
+    offsets
 }
@@ -1,7 +1,9 @@
 pub mod errors;
 pub mod jwt;
 pub mod config;
+pub mod auth;
 
 pub use errors::*;
 pub use jwt::*;
 pub use config::*;
+pub use auth::*;